@@ -0,0 +1,209 @@
+// Copyright 2026 Oxide Computer Company
+
+//! The serde attributes that would fix the names of component schemas, for
+//! applying the naming rules' fixes to the Rust types from which a spec was
+//! generated.
+
+use heck::ToPascalCase;
+use openapiv3::{OpenAPI, ReferenceOr, Schema, SchemaKind, StringType, Type};
+use serde_json::{json, Value};
+
+use crate::naming::{suggest_rename, Case, Target};
+
+/// The serde attributes to add to the Rust types behind a spec's component
+/// schemas so that their names follow the conventions; see
+/// [`crate::attribute_plan`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AttributePlan {
+    fixes: Vec<AttributeFix>,
+}
+
+/// An attribute to add to a Rust type, or to one of its fields or variants.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AttributeFix {
+    type_name: String,
+    member: Option<String>,
+    name: String,
+    attribute: String,
+}
+
+impl AttributePlan {
+    pub fn is_empty(&self) -> bool {
+        self.fixes.is_empty()
+    }
+
+    /// The attributes to add, ordered by type and then by member, with the
+    /// attributes for each type itself first.
+    pub fn fixes(&self) -> &[AttributeFix] {
+        &self.fixes
+    }
+
+    /// The plan as a JSON array of objects, one for each attribute, with
+    /// `type`, `member` (null for the type itself), `name`, and `attribute`
+    /// members.
+    pub fn to_json(&self) -> Value {
+        self.fixes
+            .iter()
+            .map(|fix| {
+                json!({
+                    "type": fix.type_name,
+                    "member": fix.member,
+                    "name": fix.name,
+                    "attribute": fix.attribute,
+                })
+            })
+            .collect()
+    }
+}
+
+impl AttributeFix {
+    /// The name of the Rust type, taken to be that of its component schema.
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    /// The field or variant to which the attribute belongs, or `None` if it
+    /// belongs to the type itself. The Rust name is inferred from the name in
+    /// the spec: `snake_case` for a field and `PascalCase` for a variant.
+    pub fn member(&self) -> Option<&str> {
+        self.member.as_deref()
+    }
+
+    /// The name in the spec that the attribute fixes: that of the type, or of
+    /// the property or enumerated value.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The attribute, e.g. `#[serde(rename = "time_created")]`.
+    pub fn attribute(&self) -> &str {
+        &self.attribute
+    }
+}
+
+impl std::fmt::Display for AttributeFix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.member {
+            Some(member) => write!(f, "{}::{}: {}", self.type_name, member, self.attribute),
+            None => write!(f, "{}: {}", self.type_name, self.attribute),
+        }
+    }
+}
+
+/// The serde attributes that would bring the names of a spec's component
+/// schemas, their properties, and their enumerated values in line with the
+/// conventions, grouped by the Rust type to which each belongs. Where every
+/// field (or variant) of a type needs renaming, `rename_all` on the type
+/// stands in for a `rename` on each.
+///
+/// Only the members of the component schemas themselves (including those
+/// combined with `allOf`, and the variants of `oneOf` and `anyOf` unions) are
+/// covered: the Rust types behind inline schemas nested within them have
+/// names that the spec doesn't record. [`crate::rename_map`] lists every rename.
+pub fn attribute_plan(spec: &OpenAPI) -> AttributePlan {
+    let mut plan = AttributePlan::default();
+    let schemas = spec.components.iter().flat_map(|c| c.schemas.iter());
+    for (type_name, schema) in schemas {
+        let ReferenceOr::Item(schema) = schema else {
+            continue;
+        };
+        if let Some(rename) = suggest_rename(type_name, Target::Type) {
+            plan.fixes.push(AttributeFix {
+                type_name: type_name.clone(),
+                member: None,
+                name: type_name.clone(),
+                attribute: format!("#[serde(rename = \"{}\")]", rename),
+            });
+        }
+
+        let mut fields = Vec::new();
+        let mut variants = Vec::new();
+        members(schema, &mut fields, &mut variants);
+        plan.add(type_name, &fields, Target::Property);
+        plan.add(type_name, &variants, Target::EnumValue);
+    }
+    plan.fixes.sort_by(|a, b| {
+        (&a.type_name, &a.member, &a.attribute).cmp(&(&b.type_name, &b.member, &b.attribute))
+    });
+    plan
+}
+
+impl AttributePlan {
+    /// Add the attributes for the fields or variants of a type, given their
+    /// names in the spec.
+    fn add(&mut self, type_name: &str, names: &[&str], target: Target) {
+        let renames = names
+            .iter()
+            .filter_map(|name| Some((*name, suggest_rename(name, target)?)))
+            .collect::<Vec<_>>();
+        if renames.is_empty() {
+            return;
+        }
+        if renames.len() == names.len() {
+            self.fixes.push(AttributeFix {
+                type_name: type_name.to_string(),
+                member: None,
+                name: type_name.to_string(),
+                attribute: format!("#[serde(rename_all = \"{}\")]", Case::Snake),
+            });
+            return;
+        }
+        for (name, rename) in renames {
+            let member = match target {
+                Target::EnumValue => name.to_pascal_case(),
+                _ => rename.clone(),
+            };
+            self.fixes.push(AttributeFix {
+                type_name: type_name.to_string(),
+                member: Some(member),
+                name: name.to_string(),
+                attribute: format!("#[serde(rename = \"{}\")]", rename),
+            });
+        }
+    }
+}
+
+/// Collect the names in the spec of the fields and variants of the Rust type
+/// behind a component schema.
+fn members<'a>(schema: &'a Schema, fields: &mut Vec<&'a str>, variants: &mut Vec<&'a str>) {
+    match &schema.schema_kind {
+        SchemaKind::Type(Type::Object(object)) => {
+            fields.extend(object.properties.keys().map(String::as_str));
+        }
+        SchemaKind::Type(Type::String(StringType { enumeration, .. })) => {
+            variants.extend(enumeration.iter().flatten().map(String::as_str));
+        }
+        SchemaKind::AllOf { all_of } => {
+            for subschema in all_of {
+                if let ReferenceOr::Item(subschema) = subschema {
+                    members(subschema, fields, variants);
+                }
+            }
+        }
+        SchemaKind::OneOf { one_of: union } | SchemaKind::AnyOf { any_of: union } => {
+            for subschema in union {
+                let ReferenceOr::Item(subschema) = subschema else {
+                    continue;
+                };
+                match &subschema.schema_kind {
+                    // An externally tagged variant is an object with a single
+                    // property named after the variant.
+                    SchemaKind::Type(Type::Object(object))
+                        if object.properties.len() == 1
+                            && object.required.len() == 1
+                            && object.properties.contains_key(&object.required[0]) =>
+                    {
+                        variants.push(&object.required[0]);
+                    }
+                    SchemaKind::Type(Type::String(StringType { enumeration, .. })) => {
+                        variants.extend(enumeration.iter().flatten().map(String::as_str));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        _ => {}
+    }
+}
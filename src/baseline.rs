@@ -0,0 +1,77 @@
+// Copyright 2026 Oxide Computer Company
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::Diagnostic;
+
+/// The problems already known in a spec, so that only new ones are reported
+/// while a large spec is brought into line. Problems are counted by check and
+/// location rather than matched by message, so that rewording a message
+/// doesn't resurface them: if a location has more problems of a kind than the
+/// baseline allows, all of them are reported.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Baseline {
+    pub(crate) counts: BTreeMap<String, BTreeMap<String, usize>>,
+}
+
+impl Baseline {
+    /// A baseline that grandfathers the given problems.
+    pub fn new<'a, I: IntoIterator<Item = &'a Diagnostic>>(findings: I) -> Self {
+        let mut baseline = Baseline::default();
+        for finding in findings {
+            *baseline
+                .counts
+                .entry(finding.code().to_string())
+                .or_default()
+                .entry(key(finding).to_string())
+                .or_default() += 1;
+        }
+        baseline
+    }
+
+    /// Read a baseline written by [`Baseline::to_json`].
+    pub fn from_json(value: Value) -> Result<Self, serde_json::Error> {
+        Ok(Baseline {
+            counts: serde_json::from_value(value)?,
+        })
+    }
+
+    /// The baseline as a JSON object mapping each code to an object that maps
+    /// JSON pointers to the number of problems there.
+    pub fn to_json(&self) -> Value {
+        serde_json::to_value(&self.counts).expect("counts are serializable")
+    }
+
+    /// The problems that the baseline doesn't account for.
+    pub fn filter(&self, findings: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        let current = Baseline::new(&findings);
+        findings
+            .into_iter()
+            .filter(|finding| {
+                let allowed = self.count(finding.code(), key(finding));
+                current.count(finding.code(), key(finding)) > allowed
+            })
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    fn count(&self, code: &str, key: &str) -> usize {
+        self.counts
+            .get(code)
+            .and_then(|counts| counts.get(key))
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+/// Where a problem is, for counting: its JSON pointer, or the empty string
+/// (the pointer to the whole document) for problems with the spec as a whole.
+fn key(finding: &Diagnostic) -> &str {
+    finding.location().pointer().unwrap_or("")
+}
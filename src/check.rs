@@ -0,0 +1,162 @@
+// Copyright 2026 Oxide Computer Company
+
+//! Checks defined outside this crate, e.g. for an organization's own
+//! conventions, which are applied in the same pass as the built-in ones.
+
+use std::sync::Arc;
+
+use openapiv3::{OpenAPI, Operation, Schema};
+
+use crate::{Diagnostic, Lint, Location};
+
+/// A check defined outside this crate, registered with
+/// [`crate::ValidatorBuilder::check`] or [`crate::Config::with_check`].
+///
+/// What it finds is treated like what the built-in checks find: it can be
+/// disabled, given a severity, or waived by the code of its [`Lint`].
+///
+/// ```
+/// use openapi_lint::{Check, Context, Lint, Location, Severity};
+///
+/// static NO_BETA: Lint = Lint::custom(
+///     "acme/no-beta",
+///     "https://acme.example/api-guidelines#beta",
+///     Severity::Error,
+///     "Beta paths are not published.",
+///     "Remove the operation from the published spec.",
+/// );
+///
+/// struct NoBeta;
+///
+/// impl Check for NoBeta {
+///     fn lint(&self) -> &'static Lint {
+///         &NO_BETA
+///     }
+///
+///     fn check(&self, cx: &mut Context<'_>) {
+///         for (path, method, op) in cx.spec().operations() {
+///             if path.starts_with("/beta/") {
+///                 cx.report(
+///                     format!("{} is a beta path.", path),
+///                     Location::operation(path, method, op),
+///                 );
+///             }
+///         }
+///     }
+/// }
+///
+/// let validator = openapi_lint::Validator::builder().check(NoBeta).build();
+/// # let spec: openapiv3::OpenAPI = serde_json::from_str(
+/// #     r#"{"openapi": "3.0.3", "info": {"title": "", "version": ""}, "paths": {}}"#,
+/// # ).unwrap();
+/// assert!(validator.validate(&spec).is_empty());
+/// ```
+pub trait Check: Send + Sync {
+    /// The check's code, severity, and documentation.
+    fn lint(&self) -> &'static Lint;
+
+    /// Report the problems in the spec with [`Context::report`].
+    fn check(&self, cx: &mut Context<'_>);
+}
+
+/// What a [`Check`] is given: the spec, and somewhere to report problems.
+pub struct Context<'a> {
+    spec: &'a OpenAPI,
+    lint: &'static Lint,
+    findings: Vec<Diagnostic>,
+}
+
+impl<'a> Context<'a> {
+    pub(crate) fn new(spec: &'a OpenAPI, lint: &'static Lint) -> Self {
+        Self {
+            spec,
+            lint,
+            findings: Vec::new(),
+        }
+    }
+
+    pub fn spec(&self) -> &'a OpenAPI {
+        self.spec
+    }
+
+    /// Every schema in the spec (other than references), with the name of
+    /// the component schema that it is or is within, if any, and the
+    /// operation or component to which the built-in checks would attribute a
+    /// problem with it.
+    pub fn schemas(&self) -> impl Iterator<Item = (Option<String>, &'a Schema, Location)> {
+        crate::located_schemas(self.spec)
+            .map(|(name, schema, location)| (name.map(str::to_string), schema, location))
+    }
+
+    /// Report a problem found by the check. The location may be
+    /// `Location::default()` for a problem with the spec as a whole.
+    pub fn report<S: Into<String>>(&mut self, message: S, location: Location) {
+        self.findings
+            .push(Diagnostic::new(self.lint, message.into()).at(location));
+    }
+
+    pub(crate) fn into_findings(self) -> Vec<Diagnostic> {
+        self.findings
+    }
+}
+
+/// A check of each schema by a closure, as registered with
+/// [`crate::Config::with_schema_check`].
+pub(crate) struct SchemaCheck<F> {
+    pub(crate) lint: &'static Lint,
+    pub(crate) check: F,
+}
+
+impl<F> Check for SchemaCheck<F>
+where
+    F: Fn(Option<&str>, &Schema) -> Option<String> + Send + Sync,
+{
+    fn lint(&self) -> &'static Lint {
+        self.lint
+    }
+
+    fn check(&self, cx: &mut Context<'_>) {
+        for (name, schema, location) in cx.schemas() {
+            if let Some(message) = (self.check)(name.as_deref(), schema) {
+                cx.report(message, location);
+            }
+        }
+    }
+}
+
+/// A check of each operation by a closure, as registered with
+/// [`crate::Config::with_operation_check`].
+pub(crate) struct OperationCheck<F> {
+    pub(crate) lint: &'static Lint,
+    pub(crate) check: F,
+}
+
+impl<F> Check for OperationCheck<F>
+where
+    F: Fn(&str, &str, &Operation) -> Option<String> + Send + Sync,
+{
+    fn lint(&self) -> &'static Lint {
+        self.lint
+    }
+
+    fn check(&self, cx: &mut Context<'_>) {
+        for (path, method, op) in cx.spec().operations() {
+            if let Some(message) = (self.check)(path, method, op) {
+                cx.report(message, Location::operation(path, method, op));
+            }
+        }
+    }
+}
+
+/// The checks registered with a [`crate::Config`], which are listed by code
+/// when it is debugged.
+#[derive(Clone, Default)]
+pub(crate) struct Checks(pub(crate) Vec<Arc<dyn Check>>);
+
+impl std::fmt::Debug for Checks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().map(|check| check.lint().code()))
+            .finish()
+    }
+}
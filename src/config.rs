@@ -0,0 +1,708 @@
+// Copyright 2026 Oxide Computer Company
+
+use std::collections::BTreeMap;
+
+use openapiv3::{Operation, Schema};
+use regex::Regex;
+use serde_json::{json, Value};
+
+use crate::{
+    check::{Checks, OperationCheck, SchemaCheck},
+    naming::{Case, Target},
+    Category, Check, Diagnostic, Lint, Profile, Severity,
+};
+
+/// An element of a spec that can be required to carry an extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Element {
+    /// Every operation.
+    Operation,
+    /// Every tag declared at the top level of the spec.
+    Tag,
+    /// Every named component schema.
+    Schema,
+    /// Every operation parameter.
+    Parameter,
+}
+
+/// Settings that control which rules are applied and how.
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub(crate) external: bool,
+    pub(crate) strict_parse: bool,
+    pub(crate) draft_2020_12: bool,
+    pub(crate) retry_after_statuses: Vec<u16>,
+    pub(crate) location_statuses: Vec<u16>,
+    pub(crate) idempotent_tags: Vec<String>,
+    pub(crate) allowed_methods: Vec<String>,
+    pub(crate) patch_content_types: Vec<String>,
+    pub(crate) update_suffix: Option<String>,
+    pub(crate) request_body_unions: bool,
+    pub(crate) bounded_query_arrays: bool,
+    pub(crate) nullable_collections: bool,
+    pub(crate) allowed_html_tags: Vec<String>,
+    pub(crate) min_heading_level: usize,
+    pub(crate) max_table_columns: Option<usize>,
+    pub(crate) max_description_line_length: Option<usize>,
+    pub(crate) max_anonymous_types: Option<usize>,
+    pub(crate) max_bytes_per_operation: Option<usize>,
+    pub(crate) max_title_length: Option<usize>,
+    pub(crate) max_findings: Option<usize>,
+    pub(crate) required_extensions: Vec<(Element, String)>,
+    pub(crate) redirect_operations: Vec<String>,
+    pub(crate) singular_type_names: bool,
+    pub(crate) singular_examples: bool,
+    pub(crate) sorted_components: bool,
+    pub(crate) resource_path_parameters: bool,
+    pub(crate) literal_path_prefixes: bool,
+    pub(crate) path_parameter_suffixes: Vec<String>,
+    pub(crate) extension_schemas: BTreeMap<String, Schema>,
+    pub(crate) severities: BTreeMap<String, Severity>,
+    pub(crate) all_lints: bool,
+    pub(crate) lints: BTreeMap<String, bool>,
+    pub(crate) category_lints: BTreeMap<Category, bool>,
+    pub(crate) category_severities: BTreeMap<Category, Severity>,
+    pub(crate) allowed_names: Vec<String>,
+    pub(crate) allowed_name_patterns: Vec<Regex>,
+    pub(crate) checks: Checks,
+    pub(crate) profile: Option<String>,
+    pub(crate) cases: BTreeMap<Target, Case>,
+    pub(crate) tag_order: Vec<String>,
+    pub(crate) allowed_status_codes: Vec<u16>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            external: false,
+            strict_parse: false,
+            draft_2020_12: false,
+            retry_after_statuses: vec![429, 503],
+            location_statuses: Vec::new(),
+            idempotent_tags: Vec::new(),
+            allowed_methods: ["get", "post", "put", "delete"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            patch_content_types: vec!["application/merge-patch+json".to_string()],
+            update_suffix: Some("Update".to_string()),
+            request_body_unions: false,
+            bounded_query_arrays: true,
+            nullable_collections: false,
+            allowed_html_tags: Vec::new(),
+            min_heading_level: 3,
+            max_table_columns: Some(4),
+            max_description_line_length: None,
+            max_anonymous_types: None,
+            max_bytes_per_operation: Some(32 * 1024),
+            max_title_length: Some(60),
+            max_findings: None,
+            required_extensions: Vec::new(),
+            redirect_operations: Vec::new(),
+            singular_type_names: false,
+            singular_examples: false,
+            sorted_components: false,
+            resource_path_parameters: true,
+            literal_path_prefixes: true,
+            path_parameter_suffixes: vec!["_id".to_string(), "_name".to_string()],
+            extension_schemas: crate::extensions::default_schemas(),
+            severities: BTreeMap::new(),
+            all_lints: true,
+            lints: BTreeMap::new(),
+            category_lints: BTreeMap::new(),
+            category_severities: BTreeMap::new(),
+            allowed_names: Vec::new(),
+            allowed_name_patterns: Vec::new(),
+            checks: Checks::default(),
+            profile: None,
+            cases: BTreeMap::new(),
+            tag_order: Vec::new(),
+            allowed_status_codes: Vec::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Also apply the rules for external (i.e. published) APIs.
+    pub fn with_external(&mut self, external: bool) -> &mut Self {
+        self.external = external;
+        self
+    }
+
+    /// Report every field in a raw document that is not part of OpenAPI
+    /// 3.0.3, not just those that look like misspellings.
+    pub fn with_strict_parse(&mut self, strict_parse: bool) -> &mut Self {
+        self.strict_parse = strict_parse;
+        self
+    }
+
+    /// Treat the raw document as one whose schemas were generated for JSON
+    /// Schema draft 2020-12 (e.g. by schemars) and have yet to be converted to
+    /// OpenAPI 3.0: rather than reporting every newer keyword, report only
+    /// those that the conversion does not handle.
+    pub fn with_draft_2020_12(&mut self, draft_2020_12: bool) -> &mut Self {
+        self.draft_2020_12 = draft_2020_12;
+        self
+    }
+
+    /// Response status codes that must declare a `Retry-After` header; by
+    /// default 429 (Too Many Requests) and 503 (Service Unavailable). An
+    /// empty list disables the rule.
+    pub fn with_retry_after_statuses<I: IntoIterator<Item = u16>>(
+        &mut self,
+        statuses: I,
+    ) -> &mut Self {
+        self.retry_after_statuses = statuses.into_iter().collect();
+        self
+    }
+
+    /// Status codes of POST responses (e.g. 201 Created) that must declare a
+    /// `Location` header pointing at the created resource; by default none,
+    /// which disables the rule.
+    pub fn with_location_statuses<I: IntoIterator<Item = u16>>(
+        &mut self,
+        statuses: I,
+    ) -> &mut Self {
+        self.location_statuses = statuses.into_iter().collect();
+        self
+    }
+
+    /// Status codes that responses may use although they aren't in the IANA
+    /// registry; by default none.
+    pub fn with_allowed_status_codes<I: IntoIterator<Item = u16>>(
+        &mut self,
+        statuses: I,
+    ) -> &mut Self {
+        self.allowed_status_codes = statuses.into_iter().collect();
+        self
+    }
+
+    /// Operation tags that mark POST operations as idempotent, requiring them
+    /// to accept an `Idempotency-Key` header. Operations may also be marked
+    /// with the `x-idempotent: true` extension.
+    pub fn with_idempotent_tags<I, S>(&mut self, tags: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.idempotent_tags = tags.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// The HTTP methods that operations may use; by default GET, POST, PUT,
+    /// and DELETE.
+    pub fn with_allowed_methods<I, S>(&mut self, methods: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.allowed_methods = methods
+            .into_iter()
+            .map(|method| method.as_ref().to_lowercase())
+            .collect();
+        self
+    }
+
+    /// The request content types that PATCH operations (if allowed) may
+    /// accept; by default only JSON Merge Patch.
+    pub fn with_patch_content_types<I, S>(&mut self, content_types: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.patch_content_types = content_types.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// The suffix that names the schema for the body of a PUT operation
+    /// relative to the resource it replaces (e.g. `ProjectUpdate` for
+    /// `Project`); by default "Update". `None` disables the check.
+    pub fn with_update_suffix<S: Into<String>>(&mut self, suffix: Option<S>) -> &mut Self {
+        self.update_suffix = suffix.map(Into::into);
+        self
+    }
+
+    /// Allow request bodies whose schema is a top-level `oneOf`; disallowed by
+    /// default.
+    pub fn with_request_body_unions(&mut self, allowed: bool) -> &mut Self {
+        self.request_body_unions = allowed;
+        self
+    }
+
+    /// Require array-typed query parameters to declare `maxItems` and to have
+    /// scalar items; on by default.
+    pub fn with_bounded_query_arrays(&mut self, enabled: bool) -> &mut Self {
+        self.bounded_query_arrays = enabled;
+        self
+    }
+
+    /// Allow properties that are nullable arrays or maps; disallowed by
+    /// default.
+    pub fn with_nullable_collections(&mut self, allowed: bool) -> &mut Self {
+        self.nullable_collections = allowed;
+        self
+    }
+
+    /// HTML tags (e.g. "br") that external descriptions may contain; by
+    /// default none.
+    pub fn with_allowed_html_tags<I, S>(&mut self, tags: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.allowed_html_tags = tags
+            .into_iter()
+            .map(|tag| tag.as_ref().to_lowercase())
+            .collect();
+        self
+    }
+
+    /// The highest (i.e. lowest-numbered) Markdown heading level that external
+    /// descriptions may use; by default 3, as levels 1 and 2 are used by the
+    /// documentation page itself. 1 allows all headings.
+    pub fn with_min_heading_level(&mut self, level: usize) -> &mut Self {
+        self.min_heading_level = level;
+        self
+    }
+
+    /// The maximum number of columns in a Markdown table in an external
+    /// description; by default 4. `None` allows tables of any width.
+    pub fn with_max_table_columns(&mut self, columns: Option<usize>) -> &mut Self {
+        self.max_table_columns = columns;
+        self
+    }
+
+    /// The maximum length of a line (outside of code blocks) in an external
+    /// description; by default unlimited.
+    pub fn with_max_description_line_length(&mut self, length: Option<usize>) -> &mut Self {
+        self.max_description_line_length = length;
+        self
+    }
+
+    /// The maximum number of inline (i.e. unnamed) types in the spec for which
+    /// SDK generators must synthesize names; by default unlimited. Lowering
+    /// this over time drives inline types toward named components.
+    pub fn with_max_anonymous_types(&mut self, max: Option<usize>) -> &mut Self {
+        self.max_anonymous_types = max;
+        self
+    }
+
+    /// The size, in bytes of compact JSON, that the spec may reach per
+    /// operation before it is considered bloated; by default 32 KiB. `None`
+    /// turns the check off.
+    pub fn with_max_bytes_per_operation(&mut self, max: Option<usize>) -> &mut Self {
+        self.max_bytes_per_operation = max;
+        self
+    }
+
+    /// The maximum length, in characters, of a component schema's title,
+    /// which documentation renders as a heading; by default 60. `None` turns
+    /// the check off.
+    pub fn with_max_title_length(&mut self, max: Option<usize>) -> &mut Self {
+        self.max_title_length = max;
+        self
+    }
+
+    /// Stop validating after this many problems have been found, leaving the
+    /// rest of the spec unchecked (see [`crate::Report::truncated`]), so that
+    /// a spec with thousands of problems doesn't produce an enormous log;
+    /// unlimited by default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is `Some(0)`, which would check nothing; `None` is the
+    /// way to lift the limit.
+    pub fn with_max_findings(&mut self, max: Option<usize>) -> &mut Self {
+        assert_ne!(
+            max,
+            Some(0),
+            "the maximum number of findings must be at least 1"
+        );
+        self.max_findings = max;
+        self
+    }
+
+    /// Require every element of the given kind to carry the named extension
+    /// (e.g. `x-cli-command` on every operation), for tools that depend on it.
+    /// May be called repeatedly to require several extensions.
+    pub fn with_required_extension<S: Into<String>>(
+        &mut self,
+        element: Element,
+        name: S,
+    ) -> &mut Self {
+        self.required_extensions.push((element, name.into()));
+        self
+    }
+
+    /// Operations (by operation ID) that may have 3xx redirect responses, such
+    /// as the console's login endpoints; by default none.
+    pub fn with_redirect_operations<I, S>(&mut self, operation_ids: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.redirect_operations = operation_ids.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// The canonical order of the top-level tags: those listed come first, in
+    /// the order given, and the rest follow alphabetically. By default none
+    /// are listed, so that all are in alphabetical order.
+    pub fn with_tag_order<I, S>(&mut self, tags: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tag_order = tags.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Flag component schemas with plural names (e.g. `Disks`); off by
+    /// default.
+    pub fn with_singular_type_names(&mut self, enabled: bool) -> &mut Self {
+        self.singular_type_names = enabled;
+        self
+    }
+
+    /// Require examples of parameters, headers, and media types to be given
+    /// with the singular `example` field rather than the `examples` map; by
+    /// default the map is required.
+    pub fn with_singular_examples(&mut self, enabled: bool) -> &mut Self {
+        self.singular_examples = enabled;
+        self
+    }
+
+    /// Require the entries of each components section (schemas, responses,
+    /// and so on) to be sorted by name, so that diffs of generated specs stay
+    /// reviewable; off by default.
+    pub fn with_sorted_components(&mut self, enabled: bool) -> &mut Self {
+        self.sorted_components = enabled;
+        self
+    }
+
+    /// Require a path parameter that follows a collection (e.g.
+    /// `/instances/{instance}`) to be named for the resource; on by default.
+    pub fn with_resource_path_parameters(&mut self, enabled: bool) -> &mut Self {
+        self.resource_path_parameters = enabled;
+        self
+    }
+
+    /// Require each path to start with a literal segment rather than a
+    /// parameter (e.g. `/instances/{instance}`, not `/{instance}`); on by
+    /// default.
+    pub fn with_literal_path_prefixes(&mut self, enabled: bool) -> &mut Self {
+        self.literal_path_prefixes = enabled;
+        self
+    }
+
+    /// The suffixes that a path parameter named for its resource may carry
+    /// (e.g. `{instance_id}`); by default "_id" and "_name". The bare name
+    /// (e.g. `{instance}`) is always allowed.
+    pub fn with_path_parameter_suffixes<I, S>(&mut self, suffixes: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.path_parameter_suffixes = suffixes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Register the schema that values of the named extension must match
+    /// (replacing any previously registered). Schemas for
+    /// `x-dropshot-pagination`, `x-idempotent`, `x-location`,
+    /// `x-mutually-exclusive`, `x-openapi-lint`, and `x-path-template` are
+    /// registered by default.
+    pub fn with_extension_schema<S: Into<String>>(&mut self, name: S, schema: Schema) -> &mut Self {
+        self.extension_schemas.insert(name.into(), schema);
+        self
+    }
+
+    /// Report what the check with the given code (e.g.
+    /// "naming/redundant-uuid-suffix") finds at the given severity rather
+    /// than its default, e.g. to roll out a new check as a warning before
+    /// making it an error. Codes that name no check are ignored.
+    pub fn with_severity<S: Into<String>>(&mut self, code: S, severity: Severity) -> &mut Self {
+        self.severities.insert(code.into(), severity);
+        self
+    }
+
+    /// Turn the check with the given code on or off. Turning a check on only
+    /// undoes turning it off: checks that the other settings leave off (such
+    /// as those of the external rules) stay off. Codes that name no check
+    /// are ignored.
+    pub fn with_lint<S: Into<String>>(&mut self, code: S, enabled: bool) -> &mut Self {
+        self.lints.insert(code.into(), enabled);
+        self
+    }
+
+    /// Turn every check on or off, forgetting any turned on or off
+    /// individually so far; e.g. turn all off and then turn a few on with
+    /// [`Config::with_lint`] to apply just those.
+    pub fn with_all_lints(&mut self, enabled: bool) -> &mut Self {
+        self.all_lints = enabled;
+        self.lints.clear();
+        self.category_lints.clear();
+        self
+    }
+
+    /// Turn the checks in the given category on or off, as for
+    /// [`Config::with_lint`]. A check turned on or off by code stays so.
+    pub fn with_category(&mut self, category: Category, enabled: bool) -> &mut Self {
+        self.category_lints.insert(category, enabled);
+        self
+    }
+
+    /// Report what the checks in the given category find at the given
+    /// severity, as for [`Config::with_severity`]. A severity set by code
+    /// takes precedence.
+    pub fn with_category_severity(&mut self, category: Category, severity: Severity) -> &mut Self {
+        self.category_severities.insert(category, severity);
+        self
+    }
+
+    /// Apply the selection of checks and severities of the given profile,
+    /// replacing any made so far, and whether to apply the external rules.
+    /// Other settings are kept, and later changes to the selection of
+    /// checks and severities refine the profile's.
+    pub fn with_profile(&mut self, profile: &Profile) -> &mut Self {
+        self.external = profile.external;
+        self.all_lints = profile.all_lints;
+        self.lints = profile.lints.clone();
+        self.severities = profile.severities.clone();
+        self.category_lints.clear();
+        self.category_severities.clear();
+        self.profile = Some(profile.name.clone());
+        self
+    }
+
+    /// The name of the profile last applied, if any.
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    /// Require names of the given kind to follow the given case convention
+    /// rather than the default (see [`Target`]), e.g. for a third-party spec
+    /// that deliberately uses camelCase properties.
+    pub fn with_case(&mut self, target: Target, case: Case) -> &mut Self {
+        self.cases.insert(target, case);
+        self
+    }
+
+    /// Names (of types, properties, parameters, operations, enumeration
+    /// values, or path segments) that the naming rules don't apply to, e.g.
+    /// because they're generated by a tool that can't be changed.
+    pub fn with_allowed_names<I, S>(&mut self, names: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_names.extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    /// Patterns for names that the naming rules don't apply to, as for
+    /// [`Config::with_allowed_names`]. A name is exempt if a pattern matches
+    /// any part of it, so anchor patterns (e.g. `^x_.*$`) to match whole
+    /// names.
+    pub fn with_allowed_name_patterns<I>(&mut self, patterns: I) -> &mut Self
+    where
+        I: IntoIterator<Item = Regex>,
+    {
+        self.allowed_name_patterns.extend(patterns);
+        self
+    }
+
+    /// Whether the naming rules don't apply to `name`.
+    pub(crate) fn allows_name(&self, name: &str) -> bool {
+        self.allowed_names.iter().any(|allowed| allowed == name)
+            || self
+                .allowed_name_patterns
+                .iter()
+                .any(|pattern| pattern.is_match(name))
+    }
+
+    /// Also apply a check defined outside this crate.
+    pub fn with_check<C: Check + 'static>(&mut self, check: C) -> &mut Self {
+        self.checks.0.push(std::sync::Arc::new(check));
+        self
+    }
+
+    /// Also apply a closure to every schema, with the name of the component
+    /// schema that it is or is within, if any; each message it returns is
+    /// reported as a problem found by the given check. For a rule that needs
+    /// more than one schema at a time, implement [`Check`] instead.
+    pub fn with_schema_check<F>(&mut self, lint: &'static Lint, check: F) -> &mut Self
+    where
+        F: Fn(Option<&str>, &Schema) -> Option<String> + Send + Sync + 'static,
+    {
+        self.with_check(SchemaCheck { lint, check })
+    }
+
+    /// Also apply a closure to every operation, with its path and method;
+    /// each message it returns is reported as a problem found by the given
+    /// check.
+    pub fn with_operation_check<F>(&mut self, lint: &'static Lint, check: F) -> &mut Self
+    where
+        F: Fn(&str, &str, &Operation) -> Option<String> + Send + Sync + 'static,
+    {
+        self.with_check(OperationCheck { lint, check })
+    }
+
+    /// The check with the given code, whether built in or registered with
+    /// [`Config::with_check`].
+    pub(crate) fn lint(&self, code: &str) -> Option<&'static Lint> {
+        crate::explain(code).or_else(|| {
+            self.checks
+                .0
+                .iter()
+                .map(|check| check.lint())
+                .find(|lint| lint.code() == code)
+        })
+    }
+
+    /// Whether the check with the given code is turned on.
+    pub(crate) fn is_enabled(&self, code: &str) -> bool {
+        self.lints
+            .get(code)
+            .or_else(|| self.by_category(code, &self.category_lints))
+            .copied()
+            .unwrap_or(self.all_lints)
+    }
+
+    /// The setting for the first of the categories of the check with the
+    /// given code that has one.
+    fn by_category<'a, T>(&self, code: &str, settings: &'a BTreeMap<Category, T>) -> Option<&'a T> {
+        self.lint(code)?
+            .categories()
+            .iter()
+            .find_map(|category| settings.get(category))
+    }
+
+    /// Drop the problems found by checks that are turned off, and give the
+    /// rest the severity configured for their check, if any.
+    pub(crate) fn apply(&self, findings: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        findings
+            .into_iter()
+            .filter_map(|finding| self.adjust(finding))
+            .collect()
+    }
+
+    /// A finding as configured: `None` if its check is disabled, or with its
+    /// severity overridden.
+    pub(crate) fn adjust(&self, mut finding: Diagnostic) -> Option<Diagnostic> {
+        if !self.is_enabled(finding.code()) {
+            return None;
+        }
+        finding.severity = self.severity(finding.lint());
+        Some(finding)
+    }
+
+    /// The severity at which what the given check finds is reported.
+    pub(crate) fn severity(&self, lint: &Lint) -> Severity {
+        self.severities
+            .get(lint.code())
+            .or_else(|| self.by_category(lint.code(), &self.category_severities))
+            .copied()
+            .unwrap_or(lint.default_severity())
+    }
+
+    /// Whether the raw document is treated as targeting JSON Schema draft
+    /// 2020-12, as set with [`Config::with_draft_2020_12`].
+    pub fn draft_2020_12(&self) -> bool {
+        self.draft_2020_12
+    }
+
+    /// The maximum number of problems to find, as set with
+    /// [`Config::with_max_findings`].
+    pub fn max_findings(&self) -> Option<usize> {
+        self.max_findings
+    }
+
+    /// The case convention for names of the given kind.
+    pub(crate) fn case(&self, target: Target) -> Case {
+        self.cases.get(&target).copied().unwrap_or(target.case())
+    }
+
+    /// The configuration as applied, with every setting resolved: whether
+    /// each check (built in or registered) is enabled and at what severity,
+    /// the case convention for each kind of name, and the other settings.
+    /// Recorded alongside what was found, it answers why a check did or
+    /// didn't report something.
+    pub fn to_json(&self) -> Value {
+        let custom = self.checks.0.iter().map(|check| check.lint());
+        let lints = crate::lints()
+            .chain(custom)
+            .map(|lint| {
+                let setting = json!({
+                    "enabled": self.is_enabled(lint.code()),
+                    "severity": self.severity(lint).to_string(),
+                });
+                (lint.code().to_string(), setting)
+            })
+            .collect::<serde_json::Map<_, _>>();
+        let cases = [
+            Target::Type,
+            Target::Property,
+            Target::Parameter,
+            Target::OperationId,
+            Target::EnumValue,
+            Target::PathSegment,
+        ]
+        .into_iter()
+        .map(|target| (target.kind().to_string(), json!(self.case(target).name())))
+        .collect::<serde_json::Map<_, _>>();
+        let mut required_extensions = serde_json::Map::new();
+        for (element, name) in &self.required_extensions {
+            let names = required_extensions
+                .entry(crate::element_name(*element))
+                .or_insert_with(|| json!([]));
+            if let Value::Array(names) = names {
+                names.push(json!(name));
+            }
+        }
+
+        json!({
+            "profile": self.profile,
+            "external": self.external,
+            "strict_parse": self.strict_parse,
+            "draft_2020_12": self.draft_2020_12,
+            "lints": lints,
+            "cases": cases,
+            "allowed_names": self.allowed_names,
+            "allowed_name_patterns": self
+                .allowed_name_patterns
+                .iter()
+                .map(Regex::as_str)
+                .collect::<Vec<_>>(),
+            "retry_after_statuses": self.retry_after_statuses,
+            "location_statuses": self.location_statuses,
+            "allowed_status_codes": self.allowed_status_codes,
+            "idempotent_tags": self.idempotent_tags,
+            "allowed_methods": self.allowed_methods,
+            "patch_content_types": self.patch_content_types,
+            "update_suffix": self.update_suffix,
+            "request_body_unions": self.request_body_unions,
+            "bounded_query_arrays": self.bounded_query_arrays,
+            "nullable_collections": self.nullable_collections,
+            "allowed_html_tags": self.allowed_html_tags,
+            "min_heading_level": self.min_heading_level,
+            "max_table_columns": self.max_table_columns,
+            "max_description_line_length": self.max_description_line_length,
+            "max_anonymous_types": self.max_anonymous_types,
+            "max_bytes_per_operation": self.max_bytes_per_operation,
+            "max_title_length": self.max_title_length,
+            "max_findings": self.max_findings,
+            "required_extensions": required_extensions,
+            "redirect_operations": self.redirect_operations,
+            "tag_order": self.tag_order,
+            "singular_type_names": self.singular_type_names,
+            "singular_examples": self.singular_examples,
+            "sorted_components": self.sorted_components,
+            "resource_path_parameters": self.resource_path_parameters,
+            "literal_path_prefixes": self.literal_path_prefixes,
+            "path_parameter_suffixes": self.path_parameter_suffixes,
+            "extension_schemas": self.extension_schemas.keys().collect::<Vec<_>>(),
+        })
+    }
+}
@@ -0,0 +1,358 @@
+// Copyright 2026 Oxide Computer Company
+
+//! Loading a [`Config`] from an `openapi-lint.toml` file, so that a lint
+//! policy can be shared between services.
+
+use std::collections::BTreeMap;
+
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::{
+    naming::{Case, Target},
+    Category, Config, Element, Profile, Severity,
+};
+
+/// The name of the file that the CLI reads its settings from by default.
+pub const CONFIG_FILE: &str = "openapi-lint.toml";
+
+/// A problem with a configuration file.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// The file couldn't be read.
+    Read {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+    /// The file isn't valid TOML or doesn't have the expected structure.
+    Parse(toml::de::Error),
+    /// A lint code that names neither a check nor a category.
+    UnknownLint(String),
+    /// A severity other than "error", "warning", or "advice".
+    UnknownSeverity(String),
+    /// A profile other than "internal", "external", or "strict".
+    UnknownProfile(String),
+    /// A case convention other than those named by [`Case::name`].
+    UnknownCase(String),
+    /// A name pattern that isn't a valid regular expression.
+    InvalidPattern(regex::Error),
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Read { path, .. } => write!(f, "failed to read {}", path.display()),
+            ConfigError::Parse(err) => write!(f, "invalid configuration: {}", err),
+            ConfigError::UnknownLint(code) => {
+                write!(f, "there is no check or category with the code {}", code)
+            }
+            ConfigError::UnknownSeverity(severity) => write!(
+                f,
+                "unknown severity \"{}\"; expected \"error\", \"warning\", or \"advice\"",
+                severity,
+            ),
+            ConfigError::UnknownProfile(profile) => write!(
+                f,
+                "unknown profile \"{}\"; expected \"internal\", \"external\", or \"strict\"",
+                profile,
+            ),
+            ConfigError::UnknownCase(case) => write!(
+                f,
+                "unknown case \"{}\"; expected \"snake_case\", \"SCREAMING_SNAKE_CASE\", \
+                \"kebab-case\", \"PascalCase\", or \"camelCase\"",
+                case,
+            ),
+            ConfigError::InvalidPattern(_) => f.write_str("invalid name pattern"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Read { source, .. } => Some(source),
+            ConfigError::InvalidPattern(err) => Some(err),
+            // The parse error, with its line and column, is part of the
+            // message.
+            ConfigError::Parse(_)
+            | ConfigError::UnknownLint(_)
+            | ConfigError::UnknownSeverity(_)
+            | ConfigError::UnknownProfile(_)
+            | ConfigError::UnknownCase(_) => None,
+        }
+    }
+}
+
+/// The contents of the file. Every setting is optional and defaults as in
+/// [`Config::default`].
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct ConfigFile {
+    profile: Option<String>,
+    external: Option<bool>,
+    strict_parse: Option<bool>,
+    draft_2020_12: Option<bool>,
+    request_body_unions: Option<bool>,
+    bounded_query_arrays: Option<bool>,
+    nullable_collections: Option<bool>,
+    singular_type_names: Option<bool>,
+    singular_examples: Option<bool>,
+    sorted_components: Option<bool>,
+    resource_path_parameters: Option<bool>,
+    literal_path_prefixes: Option<bool>,
+    path_parameter_suffixes: Option<Vec<String>>,
+    update_suffix: Option<String>,
+    min_heading_level: Option<usize>,
+    max_table_columns: Option<usize>,
+    max_description_line_length: Option<usize>,
+    max_anonymous_types: Option<usize>,
+    max_bytes_per_operation: Option<usize>,
+    max_title_length: Option<usize>,
+    max_findings: Option<usize>,
+    location_statuses: Option<Vec<u16>>,
+    tag_order: Option<Vec<String>>,
+    #[serde(default)]
+    allow: Allow,
+    #[serde(default)]
+    lints: BTreeMap<String, bool>,
+    #[serde(default)]
+    severities: BTreeMap<String, String>,
+    #[serde(default)]
+    required_extensions: RequiredExtensions,
+    #[serde(default)]
+    cases: Cases,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct Allow {
+    methods: Option<Vec<String>>,
+    patch_content_types: Option<Vec<String>>,
+    html_tags: Option<Vec<String>>,
+    redirect_operations: Option<Vec<String>>,
+    idempotent_tags: Option<Vec<String>>,
+    retry_after_statuses: Option<Vec<u16>>,
+    status_codes: Option<Vec<u16>>,
+    #[serde(default)]
+    names: Vec<String>,
+    #[serde(default)]
+    name_patterns: Vec<String>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct Cases {
+    r#type: Option<String>,
+    property: Option<String>,
+    parameter: Option<String>,
+    operation_id: Option<String>,
+    enum_value: Option<String>,
+    path_segment: Option<String>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RequiredExtensions {
+    #[serde(default)]
+    operation: Vec<String>,
+    #[serde(default)]
+    tag: Vec<String>,
+    #[serde(default)]
+    schema: Vec<String>,
+    #[serde(default)]
+    parameter: Vec<String>,
+}
+
+impl Config {
+    /// Read settings from a TOML file such as `openapi-lint.toml`. Requires
+    /// the `fs` feature (enabled by default).
+    #[cfg(feature = "fs")]
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> Result<Config, ConfigError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|source| ConfigError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Config::from_toml(&contents)
+    }
+
+    /// Parse settings in the format of `openapi-lint.toml`; see the README.
+    pub fn from_toml(contents: &str) -> Result<Config, ConfigError> {
+        let file = toml::from_str::<ConfigFile>(contents).map_err(ConfigError::Parse)?;
+        let mut config = Config::default();
+
+        // The profile comes first, so that the other settings refine it.
+        if let Some(name) = file.profile {
+            let profile = Profile::by_name(&name).ok_or(ConfigError::UnknownProfile(name))?;
+            config.with_profile(&profile);
+        }
+        if let Some(external) = file.external {
+            config.with_external(external);
+        }
+        if let Some(strict_parse) = file.strict_parse {
+            config.with_strict_parse(strict_parse);
+        }
+        if let Some(draft_2020_12) = file.draft_2020_12 {
+            config.with_draft_2020_12(draft_2020_12);
+        }
+        if let Some(allowed) = file.request_body_unions {
+            config.with_request_body_unions(allowed);
+        }
+        if let Some(enabled) = file.bounded_query_arrays {
+            config.with_bounded_query_arrays(enabled);
+        }
+        if let Some(allowed) = file.nullable_collections {
+            config.with_nullable_collections(allowed);
+        }
+        if let Some(enabled) = file.singular_type_names {
+            config.with_singular_type_names(enabled);
+        }
+        if let Some(enabled) = file.singular_examples {
+            config.with_singular_examples(enabled);
+        }
+        if let Some(enabled) = file.sorted_components {
+            config.with_sorted_components(enabled);
+        }
+        if let Some(enabled) = file.resource_path_parameters {
+            config.with_resource_path_parameters(enabled);
+        }
+        if let Some(enabled) = file.literal_path_prefixes {
+            config.with_literal_path_prefixes(enabled);
+        }
+        if let Some(suffixes) = file.path_parameter_suffixes {
+            config.with_path_parameter_suffixes(suffixes);
+        }
+        if let Some(suffix) = file.update_suffix {
+            // An empty suffix disables the check, as TOML has no null.
+            config.with_update_suffix((!suffix.is_empty()).then_some(suffix));
+        }
+        if let Some(level) = file.min_heading_level {
+            config.with_min_heading_level(level);
+        }
+        if let Some(columns) = file.max_table_columns {
+            config.with_max_table_columns(Some(columns));
+        }
+        if let Some(length) = file.max_description_line_length {
+            config.with_max_description_line_length(Some(length));
+        }
+        if let Some(max) = file.max_anonymous_types {
+            config.with_max_anonymous_types(Some(max));
+        }
+        if let Some(max) = file.max_bytes_per_operation {
+            // 0 turns the check off, as TOML has no null.
+            config.with_max_bytes_per_operation((max > 0).then_some(max));
+        }
+        if let Some(max) = file.max_title_length {
+            config.with_max_title_length((max > 0).then_some(max));
+        }
+        if let Some(max) = file.max_findings {
+            config.with_max_findings((max > 0).then_some(max));
+        }
+        if let Some(statuses) = file.location_statuses {
+            config.with_location_statuses(statuses);
+        }
+        if let Some(tags) = file.tag_order {
+            config.with_tag_order(tags);
+        }
+
+        let allow = file.allow;
+        if let Some(methods) = allow.methods {
+            config.with_allowed_methods(methods);
+        }
+        if let Some(content_types) = allow.patch_content_types {
+            config.with_patch_content_types(content_types);
+        }
+        if let Some(tags) = allow.html_tags {
+            config.with_allowed_html_tags(tags);
+        }
+        if let Some(operation_ids) = allow.redirect_operations {
+            config.with_redirect_operations(operation_ids);
+        }
+        if let Some(tags) = allow.idempotent_tags {
+            config.with_idempotent_tags(tags);
+        }
+        if let Some(statuses) = allow.retry_after_statuses {
+            config.with_retry_after_statuses(statuses);
+        }
+        if let Some(statuses) = allow.status_codes {
+            config.with_allowed_status_codes(statuses);
+        }
+        config.with_allowed_names(allow.names);
+        let patterns = allow
+            .name_patterns
+            .iter()
+            .map(|pattern| Regex::new(pattern))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(ConfigError::InvalidPattern)?;
+        config.with_allowed_name_patterns(patterns);
+
+        // Unlike the programmatic settings, a policy file names codes that
+        // nobody compiled against, so a misspelled code is an error rather
+        // than silently ignored.
+        let mut lints = file.lints;
+        if let Some(all) = lints.remove("all") {
+            config.with_all_lints(all);
+        }
+        // Keys are either codes or, to apply to a family of checks at once,
+        // category names.
+        for (code, enabled) in lints {
+            if let Some(category) = Category::by_name(&code) {
+                config.with_category(category, enabled);
+                continue;
+            }
+            known_lint(&code)?;
+            config.with_lint(code, enabled);
+        }
+        for (code, severity) in file.severities {
+            let severity = match severity.as_str() {
+                "error" => Severity::Error,
+                "warning" => Severity::Warning,
+                "advice" => Severity::Advice,
+                _ => return Err(ConfigError::UnknownSeverity(severity)),
+            };
+            if let Some(category) = Category::by_name(&code) {
+                config.with_category_severity(category, severity);
+                continue;
+            }
+            known_lint(&code)?;
+            config.with_severity(code, severity);
+        }
+
+        let required = file.required_extensions;
+        for (element, names) in [
+            (Element::Operation, required.operation),
+            (Element::Tag, required.tag),
+            (Element::Schema, required.schema),
+            (Element::Parameter, required.parameter),
+        ] {
+            for name in names {
+                config.with_required_extension(element, name);
+            }
+        }
+
+        let cases = file.cases;
+        for (target, case) in [
+            (Target::Type, cases.r#type),
+            (Target::Property, cases.property),
+            (Target::Parameter, cases.parameter),
+            (Target::OperationId, cases.operation_id),
+            (Target::EnumValue, cases.enum_value),
+            (Target::PathSegment, cases.path_segment),
+        ] {
+            if let Some(case) = case {
+                let case = Case::by_name(&case).ok_or(ConfigError::UnknownCase(case))?;
+                config.with_case(target, case);
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+fn known_lint(code: &str) -> Result<(), ConfigError> {
+    match crate::explain(code) {
+        Some(_) => Ok(()),
+        None => Err(ConfigError::UnknownLint(code.to_string())),
+    }
+}
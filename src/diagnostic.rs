@@ -0,0 +1,560 @@
+// Copyright 2026 Oxide Computer Company
+
+use std::{
+    collections::BTreeMap,
+    sync::{Mutex, OnceLock},
+};
+
+use openapiv3::Operation;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{fields::escape, lints::Lint, naming::Target, source::Position};
+
+/// A problem found in a spec. Its [`Display`](std::fmt::Display) form is the
+/// message (and the uses of a shared component in which it was found)
+/// followed by a link to the documentation for the rule; the alternate form
+/// (`{:#}`) also includes the detail, if any.
+///
+/// Problems serialize as the objects that the CLI prints with `--format json`
+/// (see [`Diagnostic::to_json`]), and deserialize from them, so that they can
+/// be kept and compared between runs. A problem found by a check defined
+/// outside this crate deserializes with a check that has its code and URL but
+/// no rationale or fix.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "DiagnosticRepr", try_from = "DiagnosticRepr")]
+#[non_exhaustive]
+pub struct Diagnostic {
+    pub(crate) lint: &'static Lint,
+    pub(crate) severity: Severity,
+    pub(crate) message: String,
+    pub(crate) location: Location,
+    pub(crate) referenced_from: Vec<Location>,
+    pub(crate) detail: Option<String>,
+    pub(crate) suggestion: Option<Suggestion>,
+}
+
+/// A fix for a problem that tools can apply without judgement: renaming the
+/// element at the problem's location, or replacing the string value there.
+/// Its [`Display`](std::fmt::Display) form describes the fix, e.g. `rename the
+/// property "fooBar" to "foo_bar"`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "SuggestionRepr", try_from = "SuggestionRepr")]
+#[non_exhaustive]
+pub struct Suggestion {
+    kind: SuggestionKind,
+    target: Option<Target>,
+    from: String,
+    to: String,
+}
+
+/// What a [`Suggestion`] does; see [`Suggestion::kind`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SuggestionKind {
+    Rename,
+    Replace,
+    Move,
+}
+
+/// How serious a problem is. Severities are ordered from least to most
+/// serious, so that, for example, `severity >= Severity::Warning` selects
+/// warnings and errors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum Severity {
+    /// A suggestion that may well be declined.
+    Advice,
+    /// Something that should be fixed, but need not block publishing the
+    /// spec; new checks often start out as warnings.
+    Warning,
+    /// The spec should not be published as is.
+    Error,
+}
+
+/// Where in a spec a problem was found. Each part is present only if it is
+/// known; a problem that involves several operations, for example, has no
+/// single operation ID.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "LocationRepr", from = "LocationRepr")]
+#[non_exhaustive]
+pub struct Location {
+    pub(crate) component: Option<String>,
+    pub(crate) operation_id: Option<String>,
+    pub(crate) pointer: Option<String>,
+    pub(crate) file: Option<String>,
+    pub(crate) position: Option<Position>,
+}
+
+impl Diagnostic {
+    pub(crate) fn new(lint: &'static Lint, message: String) -> Self {
+        Self {
+            lint,
+            severity: lint.default_severity(),
+            message,
+            location: Location::default(),
+            referenced_from: Vec::new(),
+            detail: None,
+            suggestion: None,
+        }
+    }
+
+    pub(crate) fn with_suggestion(mut self, suggestion: Suggestion) -> Self {
+        self.suggestion = Some(suggestion);
+        self
+    }
+
+    pub(crate) fn with_detail(mut self, detail: String) -> Self {
+        self.detail = Some(detail);
+        self
+    }
+
+    pub(crate) fn at(mut self, location: Location) -> Self {
+        self.location = location;
+        self
+    }
+
+    /// Note an element (such as an operation) that uses the component in
+    /// which the problem was found.
+    pub(crate) fn used_by(mut self, site: Location) -> Self {
+        self.referenced_from.push(site);
+        self
+    }
+
+    /// The stable code of the check that found the problem, e.g.
+    /// "response/retry-after"; see [`crate::explain`]. Unlike the wording of
+    /// messages, codes don't change between releases, so they can be used to
+    /// filter or route problems.
+    pub fn code(&self) -> &'static str {
+        self.lint.code()
+    }
+
+    /// The rule to which the check belongs: the anchor of the section of the
+    /// README that describes it, e.g. "retry-after".
+    pub fn rule(&self) -> &'static str {
+        self.lint.rule()
+    }
+
+    /// The check that found the problem, with its rationale and how to fix
+    /// it.
+    pub fn lint(&self) -> &'static Lint {
+        self.lint
+    }
+
+    /// The severity of the problem: that of the check, unless overridden
+    /// with [`crate::Config::with_severity`].
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// A description of the problem, without the link to the documentation.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn location(&self) -> &Location {
+        &self.location
+    }
+
+    /// The elements (such as operations) whose use of the component in which
+    /// the problem was found brought it to light. A problem with a shared
+    /// component is reported once, at the component, rather than for each
+    /// use; this is empty for other problems.
+    pub fn referenced_from(&self) -> &[Location] {
+        &self.referenced_from
+    }
+
+    /// More about the problem than the message gives, such as the whole of
+    /// the schema in which it was found, for verbose output.
+    pub fn detail(&self) -> Option<&str> {
+        self.detail.as_deref()
+    }
+
+    /// A fix that tools can apply, if there is one.
+    pub fn suggestion(&self) -> Option<&Suggestion> {
+        self.suggestion.as_ref()
+    }
+
+    /// The documentation for the rule.
+    pub fn url(&self) -> String {
+        self.lint.url()
+    }
+
+    /// The problem as a JSON object, for machine-readable output, with
+    /// `code`, `severity`, `message`, `location` (as for [`Location`]),
+    /// `referenced_from`, `detail`, `suggestion` (as for
+    /// [`Suggestion::to_json`]), and `url` members.
+    pub fn to_json(&self) -> Value {
+        serde_json::to_value(self).expect("a diagnostic serializes as JSON")
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)?;
+        if !self.referenced_from.is_empty() {
+            let sites = self
+                .referenced_from
+                .iter()
+                .map(|site| {
+                    site.operation_id()
+                        .or(site.pointer())
+                        .unwrap_or("<unknown>")
+                })
+                .collect::<Vec<_>>();
+            write!(f, "\nUsed by {}", sites.join(", "))?;
+        }
+        if let Some(detail) = self.detail.as_ref().filter(|_| f.alternate()) {
+            write!(f, "\n{}", detail)?;
+        }
+        write!(f, "\nFor more info, see {}", self.url())
+    }
+}
+
+impl Suggestion {
+    pub(crate) fn rename(target: Target, from: &str, to: &str) -> Self {
+        Self {
+            kind: SuggestionKind::Rename,
+            target: Some(target),
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+
+    pub(crate) fn replace(from: &str, to: &str) -> Self {
+        Self {
+            kind: SuggestionKind::Replace,
+            target: None,
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+
+    /// Move the value of the field `from` into the map `to` of the same
+    /// object, e.g. a singular `example` into `examples`.
+    pub(crate) fn move_field(from: &str, to: &str) -> Self {
+        Self {
+            kind: SuggestionKind::Move,
+            target: None,
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+
+    /// What the fix does: "rename" an element, "replace" a value, or "move" a
+    /// field (`from`) into a map (`to`) of the same object.
+    pub fn kind(&self) -> &'static str {
+        match self.kind {
+            SuggestionKind::Rename => "rename",
+            SuggestionKind::Replace => "replace",
+            SuggestionKind::Move => "move",
+        }
+    }
+
+    /// The kind of element to rename, or `None` if the fix doesn't rename an
+    /// element. The segments of a path are renamed
+    /// together: the names are those of the whole path.
+    pub fn target(&self) -> Option<Target> {
+        self.target
+    }
+
+    /// The element's name, the value now, or the field to move.
+    pub fn from(&self) -> &str {
+        &self.from
+    }
+
+    /// The name to give the element, the value to put in place of the
+    /// current one, or the map into which to move the field.
+    pub fn to(&self) -> &str {
+        &self.to
+    }
+
+    /// The serde attribute that makes the rename in the Rust source from
+    /// which the spec was generated, for those elements named by serde (types,
+    /// their fields and variants, and parameters).
+    pub fn attribute(&self) -> Option<String> {
+        match self.target? {
+            Target::Type | Target::Property | Target::Parameter | Target::EnumValue => {
+                Some(format!("#[serde(rename = \"{}\")]", self.to))
+            }
+            _ => None,
+        }
+    }
+
+    /// The fix as a JSON object, with `kind` (as for [`Suggestion::kind`]),
+    /// `target` (as for [`Target::kind`], or null if not a rename), `from`,
+    /// `to`, and `attribute` (which may be null) members.
+    pub fn to_json(&self) -> Value {
+        serde_json::to_value(self).expect("a suggestion serializes as JSON")
+    }
+}
+
+impl std::fmt::Display for Suggestion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.target {
+            Some(Target::PathSegment) => {
+                write!(f, "rename the path \"{}\" to \"{}\"", self.from, self.to)
+            }
+            Some(target) => write!(
+                f,
+                "rename the {} \"{}\" to \"{}\"",
+                target.kind().replace('_', " "),
+                self.from,
+                self.to,
+            ),
+            None if self.kind == SuggestionKind::Move => {
+                write!(f, "move \"{}\" into \"{}\"", self.from, self.to)
+            }
+            None => write!(f, "replace \"{}\" with \"{}\"", self.from, self.to),
+        }
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Severity::Advice => "advice",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+impl Location {
+    /// An operation, at `/paths/<path>/<method>`.
+    pub fn operation(path: &str, method: &str, op: &Operation) -> Self {
+        Self {
+            operation_id: op.operation_id.clone(),
+            pointer: Some(format!("/paths/{}/{}", escape(path), method)),
+            ..Self::default()
+        }
+    }
+
+    /// The document as a whole, at the empty JSON pointer.
+    pub fn document() -> Self {
+        Self {
+            pointer: Some(String::new()),
+            ..Self::default()
+        }
+    }
+
+    /// A path item, at `/paths/<path>`.
+    pub fn path(path: &str) -> Self {
+        Self {
+            pointer: Some(format!("/paths/{}", escape(path))),
+            ..Self::default()
+        }
+    }
+
+    /// A component of the given kind (e.g. "schemas") with the given name.
+    pub fn named(kind: &str, name: &str) -> Self {
+        Self {
+            component: Some(name.to_string()),
+            pointer: Some(format!("/components/{}/{}", kind, escape(name))),
+            ..Self::default()
+        }
+    }
+
+    /// An arbitrary JSON pointer, which may be within a component.
+    pub fn from_pointer(pointer: &str) -> Self {
+        let component = pointer
+            .strip_prefix("/components/")
+            .and_then(|rest| rest.split('/').nth(1))
+            .map(|name| name.replace("~1", "/").replace("~0", "~"));
+        Self {
+            component,
+            pointer: Some(pointer.to_string()),
+            ..Self::default()
+        }
+    }
+
+    /// The same location, narrowed to the element at `pointer` relative to
+    /// it (e.g. "/properties/time_created").
+    pub(crate) fn within(&self, pointer: &str) -> Self {
+        Self {
+            pointer: self
+                .pointer
+                .as_ref()
+                .map(|base| format!("{}{}", base, pointer)),
+            ..self.clone()
+        }
+    }
+
+    /// The name of the component (e.g. the schema) in which the problem was
+    /// found.
+    pub fn component(&self) -> Option<&str> {
+        self.component.as_deref()
+    }
+
+    /// The ID of the operation in which the problem was found.
+    pub fn operation_id(&self) -> Option<&str> {
+        self.operation_id.as_deref()
+    }
+
+    /// A JSON pointer to the element in which the problem was found, which
+    /// may contain the problem rather than be the problem itself.
+    pub fn pointer(&self) -> Option<&str> {
+        self.pointer.as_deref()
+    }
+
+    /// The file in which the problem was found, if the problem has been
+    /// placed in the text of the spec with [`crate::SourceMap::annotate`].
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// The line and column at which the problem was found, if it has been
+    /// placed in the text of the spec with [`crate::SourceMap::annotate`].
+    pub fn position(&self) -> Option<Position> {
+        self.position
+    }
+}
+
+/// The serialized form of a [`Diagnostic`].
+#[derive(Serialize, Deserialize)]
+struct DiagnosticRepr {
+    code: String,
+    severity: Severity,
+    message: String,
+    location: Location,
+    #[serde(default)]
+    referenced_from: Vec<Location>,
+    #[serde(default)]
+    detail: Option<String>,
+    #[serde(default)]
+    suggestion: Option<Suggestion>,
+    #[serde(default)]
+    url: String,
+}
+
+impl From<Diagnostic> for DiagnosticRepr {
+    fn from(diagnostic: Diagnostic) -> Self {
+        Self {
+            code: diagnostic.code().to_string(),
+            url: diagnostic.url(),
+            severity: diagnostic.severity,
+            message: diagnostic.message,
+            location: diagnostic.location,
+            referenced_from: diagnostic.referenced_from,
+            detail: diagnostic.detail,
+            suggestion: diagnostic.suggestion,
+        }
+    }
+}
+
+impl TryFrom<DiagnosticRepr> for Diagnostic {
+    type Error = String;
+
+    fn try_from(repr: DiagnosticRepr) -> Result<Self, Self::Error> {
+        let lint = match crate::explain(&repr.code) {
+            Some(lint) => lint,
+            None if repr.url.is_empty() => {
+                return Err(format!("there is no check with the code {}", repr.code))
+            }
+            None => custom_lint(&repr.code, &repr.url, repr.severity),
+        };
+        Ok(Self {
+            lint,
+            severity: repr.severity,
+            message: repr.message,
+            location: repr.location,
+            referenced_from: repr.referenced_from,
+            detail: repr.detail,
+            suggestion: repr.suggestion,
+        })
+    }
+}
+
+/// The check with the given code defined outside this crate, created the
+/// first time a problem that it found is deserialized. Checks live as long as
+/// the program, so each is created once.
+fn custom_lint(code: &str, url: &str, severity: Severity) -> &'static Lint {
+    static CUSTOM: OnceLock<Mutex<BTreeMap<(String, String), &'static Lint>>> = OnceLock::new();
+    let mut custom = CUSTOM.get_or_init(Default::default).lock().unwrap();
+    custom
+        .entry((code.to_string(), url.to_string()))
+        .or_insert_with(|| {
+            let code = Box::leak(code.to_string().into_boxed_str());
+            let url = Box::leak(url.to_string().into_boxed_str());
+            Box::leak(Box::new(Lint::custom(code, url, severity, "", "")))
+        })
+}
+
+/// The serialized form of a [`Suggestion`].
+#[derive(Serialize, Deserialize)]
+struct SuggestionRepr {
+    kind: String,
+    target: Option<Target>,
+    from: String,
+    to: String,
+    #[serde(default)]
+    attribute: Option<String>,
+}
+
+impl From<Suggestion> for SuggestionRepr {
+    fn from(suggestion: Suggestion) -> Self {
+        Self {
+            kind: suggestion.kind().to_string(),
+            attribute: suggestion.attribute(),
+            target: suggestion.target,
+            from: suggestion.from,
+            to: suggestion.to,
+        }
+    }
+}
+
+impl TryFrom<SuggestionRepr> for Suggestion {
+    type Error = String;
+
+    fn try_from(repr: SuggestionRepr) -> Result<Self, Self::Error> {
+        match (repr.kind.as_str(), repr.target) {
+            ("rename", Some(target)) => Ok(Suggestion::rename(target, &repr.from, &repr.to)),
+            ("replace", None) => Ok(Suggestion::replace(&repr.from, &repr.to)),
+            ("move", None) => Ok(Suggestion::move_field(&repr.from, &repr.to)),
+            (kind, _) => Err(format!("invalid suggestion of kind \"{}\"", kind)),
+        }
+    }
+}
+
+/// The serialized form of a [`Location`], with the line and column of its
+/// position alongside the rest.
+#[derive(Serialize, Deserialize)]
+struct LocationRepr {
+    component: Option<String>,
+    operation_id: Option<String>,
+    pointer: Option<String>,
+    #[serde(default)]
+    file: Option<String>,
+    #[serde(default)]
+    line: Option<usize>,
+    #[serde(default)]
+    column: Option<usize>,
+}
+
+impl From<Location> for LocationRepr {
+    fn from(location: Location) -> Self {
+        Self {
+            component: location.component,
+            operation_id: location.operation_id,
+            pointer: location.pointer,
+            file: location.file,
+            line: location.position.map(|position| position.line()),
+            column: location.position.map(|position| position.column()),
+        }
+    }
+}
+
+impl From<LocationRepr> for Location {
+    fn from(repr: LocationRepr) -> Self {
+        Self {
+            component: repr.component,
+            operation_id: repr.operation_id,
+            pointer: repr.pointer,
+            file: repr.file,
+            position: repr
+                .line
+                .zip(repr.column)
+                .map(|(line, column)| Position::new(line, column)),
+        }
+    }
+}
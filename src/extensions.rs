@@ -0,0 +1,190 @@
+// Copyright 2026 Oxide Computer Company
+
+//! Checking the values of extensions against the schemas registered for them.
+//! Tools that consume an extension typically ignore a value they can't make
+//! sense of, so a malformed payload otherwise goes unnoticed.
+
+use std::collections::BTreeMap;
+
+use openapiv3::{AdditionalProperties, ReferenceOr, Schema, SchemaKind, Type};
+use serde_json::{json, Value};
+
+use crate::fields::escape;
+
+/// The schemas for the extensions that our own tools produce and consume.
+pub(crate) fn default_schemas() -> BTreeMap<String, Schema> {
+    [
+        (
+            "x-dropshot-pagination",
+            json!({
+                "type": "object",
+                "properties": {
+                    "required": {
+                        "type": "array",
+                        "items": { "type": "string" }
+                    }
+                },
+                "required": ["required"]
+            }),
+        ),
+        ("x-idempotent", json!({ "type": "boolean" })),
+        ("x-location", json!({ "type": "boolean" })),
+        (
+            "x-mutually-exclusive",
+            json!({
+                "type": "array",
+                "items": {
+                    "type": "array",
+                    "items": { "type": "string" }
+                }
+            }),
+        ),
+        (
+            "x-openapi-lint",
+            json!({
+                "type": "object",
+                "properties": {
+                    "ignore": {
+                        "type": "array",
+                        "items": { "type": "string" }
+                    }
+                },
+                "required": ["ignore"]
+            }),
+        ),
+        ("x-path-template", json!({ "type": "string" })),
+    ]
+    .into_iter()
+    .map(|(name, schema)| {
+        (
+            name.to_string(),
+            serde_json::from_value(schema).expect("invalid extension schema"),
+        )
+    })
+    .collect()
+}
+
+/// Describe each way in which `value`, found at `pointer`, fails to match
+/// `schema`. References within the schema can't be resolved and so are
+/// assumed to match.
+pub(crate) fn mismatches(value: &Value, schema: &Schema, pointer: &str) -> Vec<String> {
+    let mut ret = Vec::new();
+    check(&mut ret, value, schema, pointer);
+    ret
+}
+
+fn check(ret: &mut Vec<String>, value: &Value, schema: &Schema, pointer: &str) {
+    if value.is_null() && schema.schema_data.nullable {
+        return;
+    }
+
+    let expected = match &schema.schema_kind {
+        SchemaKind::Type(Type::String(string)) => {
+            let Some(s) = value.as_str() else {
+                return mismatch(ret, pointer, "a string", value);
+            };
+            if !string.enumeration.is_empty()
+                && !string.enumeration.iter().flatten().any(|e| e == s)
+            {
+                ret.push(format!(
+                    "at {} expected one of {} but found \"{}\"",
+                    pointer,
+                    string
+                        .enumeration
+                        .iter()
+                        .flatten()
+                        .map(|e| format!("\"{}\"", e))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    s,
+                ));
+            }
+            return;
+        }
+        SchemaKind::Type(Type::Number(_)) => value.is_number().then_some(()).ok_or("a number"),
+        SchemaKind::Type(Type::Integer(_)) => (value.is_i64() || value.is_u64())
+            .then_some(())
+            .ok_or("an integer"),
+        SchemaKind::Type(Type::Boolean(_)) => value.is_boolean().then_some(()).ok_or("a boolean"),
+        SchemaKind::Type(Type::Object(object)) => {
+            let Some(map) = value.as_object() else {
+                return mismatch(ret, pointer, "an object", value);
+            };
+            for name in &object.required {
+                if !map.contains_key(name) {
+                    ret.push(format!(
+                        "at {} the required property \"{}\" is missing",
+                        pointer, name,
+                    ));
+                }
+            }
+            for (name, value) in map {
+                let property_pointer = format!("{}/{}", pointer, escape(name));
+                match (object.properties.get(name), &object.additional_properties) {
+                    (Some(property), _) => {
+                        if let Some(property) = property.as_item() {
+                            check(ret, value, property, &property_pointer);
+                        }
+                    }
+                    (None, Some(AdditionalProperties::Any(false))) => ret.push(format!(
+                        "at {} the property \"{}\" is not expected",
+                        pointer, name,
+                    )),
+                    (None, Some(AdditionalProperties::Schema(schema))) => {
+                        if let ReferenceOr::Item(schema) = schema.as_ref() {
+                            check(ret, value, schema, &property_pointer);
+                        }
+                    }
+                    (None, _) => {}
+                }
+            }
+            return;
+        }
+        SchemaKind::Type(Type::Array(array)) => {
+            let Some(items) = value.as_array() else {
+                return mismatch(ret, pointer, "an array", value);
+            };
+            if let Some(ReferenceOr::Item(item_schema)) = &array.items {
+                for (index, item) in items.iter().enumerate() {
+                    check(ret, item, item_schema, &format!("{}/{}", pointer, index));
+                }
+            }
+            return;
+        }
+        SchemaKind::AllOf { all_of } => {
+            for subschema in all_of.iter().filter_map(ReferenceOr::as_item) {
+                check(ret, value, subschema, pointer);
+            }
+            return;
+        }
+        SchemaKind::OneOf { one_of: subschemas } | SchemaKind::AnyOf { any_of: subschemas } => {
+            let matches_any = subschemas.iter().any(|subschema| match subschema {
+                ReferenceOr::Item(subschema) => mismatches(value, subschema, pointer).is_empty(),
+                ReferenceOr::Reference { .. } => true,
+            });
+            matches_any
+                .then_some(())
+                .ok_or("a value matching one of the alternatives")
+        }
+        SchemaKind::Not { .. } | SchemaKind::Any(_) => Ok(()),
+    };
+
+    if let Err(expected) = expected {
+        mismatch(ret, pointer, expected, value);
+    }
+}
+
+fn mismatch(ret: &mut Vec<String>, pointer: &str, expected: &str, value: &Value) {
+    let found = match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => format!("\"{}\"", s),
+        Value::Array(_) => "an array".to_string(),
+        Value::Object(_) => "an object".to_string(),
+    };
+    ret.push(format!(
+        "at {} expected {} but found {}",
+        pointer, expected, found
+    ));
+}
@@ -0,0 +1,665 @@
+// Copyright 2026 Oxide Computer Company
+
+//! A description of the fields of each OpenAPI 3.0.3 object, used to find
+//! fields in a raw document that deserialization would otherwise silently
+//! drop.
+
+use serde_json::{Map, Value};
+
+/// The OpenAPI object types whose fields we know about.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    OpenApi,
+    Info,
+    Contact,
+    License,
+    Server,
+    ServerVariable,
+    Components,
+    Paths,
+    PathItem,
+    Operation,
+    ExternalDocs,
+    Parameter,
+    RequestBody,
+    MediaType,
+    Encoding,
+    Responses,
+    Response,
+    Callback,
+    Example,
+    Link,
+    Header,
+    Tag,
+    Schema,
+    Discriminator,
+    Xml,
+    SecurityScheme,
+    OAuthFlows,
+    OAuthFlow,
+}
+
+/// The shape of the value of a field.
+#[derive(Clone, Copy)]
+enum Field {
+    /// Any value; we don't look inside.
+    Any,
+    /// An object of the given kind.
+    Object(Kind),
+    /// An array of objects of the given kind.
+    Array(Kind),
+    /// A map whose values are objects of the given kind.
+    Map(Kind),
+}
+
+impl Kind {
+    /// The fields defined for this kind of object.
+    fn fields(self) -> &'static [(&'static str, Field)] {
+        use Field::*;
+
+        match self {
+            Kind::OpenApi => &[
+                ("openapi", Any),
+                ("info", Object(Kind::Info)),
+                ("servers", Array(Kind::Server)),
+                ("paths", Object(Kind::Paths)),
+                ("components", Object(Kind::Components)),
+                ("security", Any),
+                ("tags", Array(Kind::Tag)),
+                ("externalDocs", Object(Kind::ExternalDocs)),
+            ],
+            Kind::Info => &[
+                ("title", Any),
+                ("description", Any),
+                ("termsOfService", Any),
+                ("contact", Object(Kind::Contact)),
+                ("license", Object(Kind::License)),
+                ("version", Any),
+            ],
+            Kind::Contact => &[("name", Any), ("url", Any), ("email", Any)],
+            Kind::License => &[("name", Any), ("url", Any)],
+            Kind::Server => &[
+                ("url", Any),
+                ("description", Any),
+                ("variables", Map(Kind::ServerVariable)),
+            ],
+            Kind::ServerVariable => &[("enum", Any), ("default", Any), ("description", Any)],
+            Kind::Components => &[
+                ("schemas", Map(Kind::Schema)),
+                ("responses", Map(Kind::Response)),
+                ("parameters", Map(Kind::Parameter)),
+                ("examples", Map(Kind::Example)),
+                ("requestBodies", Map(Kind::RequestBody)),
+                ("headers", Map(Kind::Header)),
+                ("securitySchemes", Map(Kind::SecurityScheme)),
+                ("links", Map(Kind::Link)),
+                ("callbacks", Map(Kind::Callback)),
+            ],
+            // Maps handled by `map_values`.
+            Kind::Paths | Kind::Responses | Kind::Callback => &[],
+            Kind::PathItem => &[
+                ("$ref", Any),
+                ("summary", Any),
+                ("description", Any),
+                ("get", Object(Kind::Operation)),
+                ("put", Object(Kind::Operation)),
+                ("post", Object(Kind::Operation)),
+                ("delete", Object(Kind::Operation)),
+                ("options", Object(Kind::Operation)),
+                ("head", Object(Kind::Operation)),
+                ("patch", Object(Kind::Operation)),
+                ("trace", Object(Kind::Operation)),
+                ("servers", Array(Kind::Server)),
+                ("parameters", Array(Kind::Parameter)),
+            ],
+            Kind::Operation => &[
+                ("tags", Any),
+                ("summary", Any),
+                ("description", Any),
+                ("externalDocs", Object(Kind::ExternalDocs)),
+                ("operationId", Any),
+                ("parameters", Array(Kind::Parameter)),
+                ("requestBody", Object(Kind::RequestBody)),
+                ("responses", Object(Kind::Responses)),
+                ("callbacks", Map(Kind::Callback)),
+                ("deprecated", Any),
+                ("security", Any),
+                ("servers", Array(Kind::Server)),
+            ],
+            Kind::ExternalDocs => &[("description", Any), ("url", Any)],
+            Kind::Parameter => &[
+                ("name", Any),
+                ("in", Any),
+                ("description", Any),
+                ("required", Any),
+                ("deprecated", Any),
+                ("allowEmptyValue", Any),
+                ("style", Any),
+                ("explode", Any),
+                ("allowReserved", Any),
+                ("schema", Object(Kind::Schema)),
+                ("example", Any),
+                ("examples", Map(Kind::Example)),
+                ("content", Map(Kind::MediaType)),
+            ],
+            Kind::RequestBody => &[
+                ("description", Any),
+                ("content", Map(Kind::MediaType)),
+                ("required", Any),
+            ],
+            Kind::MediaType => &[
+                ("schema", Object(Kind::Schema)),
+                ("example", Any),
+                ("examples", Map(Kind::Example)),
+                ("encoding", Map(Kind::Encoding)),
+            ],
+            Kind::Encoding => &[
+                ("contentType", Any),
+                ("headers", Map(Kind::Header)),
+                ("style", Any),
+                ("explode", Any),
+                ("allowReserved", Any),
+            ],
+            Kind::Response => &[
+                ("description", Any),
+                ("headers", Map(Kind::Header)),
+                ("content", Map(Kind::MediaType)),
+                ("links", Map(Kind::Link)),
+            ],
+            Kind::Example => &[
+                ("summary", Any),
+                ("description", Any),
+                ("value", Any),
+                ("externalValue", Any),
+            ],
+            Kind::Link => &[
+                ("operationRef", Any),
+                ("operationId", Any),
+                ("parameters", Any),
+                ("requestBody", Any),
+                ("description", Any),
+                ("server", Object(Kind::Server)),
+            ],
+            Kind::Header => &[
+                ("description", Any),
+                ("required", Any),
+                ("deprecated", Any),
+                ("allowEmptyValue", Any),
+                ("style", Any),
+                ("explode", Any),
+                ("allowReserved", Any),
+                ("schema", Object(Kind::Schema)),
+                ("example", Any),
+                ("examples", Map(Kind::Example)),
+                ("content", Map(Kind::MediaType)),
+            ],
+            Kind::Tag => &[
+                ("name", Any),
+                ("description", Any),
+                ("externalDocs", Object(Kind::ExternalDocs)),
+            ],
+            Kind::Schema => &[
+                ("title", Any),
+                ("multipleOf", Any),
+                ("maximum", Any),
+                ("exclusiveMaximum", Any),
+                ("minimum", Any),
+                ("exclusiveMinimum", Any),
+                ("maxLength", Any),
+                ("minLength", Any),
+                ("pattern", Any),
+                ("maxItems", Any),
+                ("minItems", Any),
+                ("uniqueItems", Any),
+                ("maxProperties", Any),
+                ("minProperties", Any),
+                ("required", Any),
+                ("enum", Any),
+                ("type", Any),
+                ("allOf", Array(Kind::Schema)),
+                ("oneOf", Array(Kind::Schema)),
+                ("anyOf", Array(Kind::Schema)),
+                ("not", Object(Kind::Schema)),
+                ("items", Object(Kind::Schema)),
+                ("properties", Map(Kind::Schema)),
+                // Either a boolean or a schema; only the latter is an object.
+                ("additionalProperties", Object(Kind::Schema)),
+                ("description", Any),
+                ("format", Any),
+                ("default", Any),
+                ("nullable", Any),
+                ("discriminator", Object(Kind::Discriminator)),
+                ("readOnly", Any),
+                ("writeOnly", Any),
+                ("xml", Object(Kind::Xml)),
+                ("externalDocs", Object(Kind::ExternalDocs)),
+                ("example", Any),
+                ("deprecated", Any),
+            ],
+            Kind::Discriminator => &[("propertyName", Any), ("mapping", Any)],
+            Kind::Xml => &[
+                ("name", Any),
+                ("namespace", Any),
+                ("prefix", Any),
+                ("attribute", Any),
+                ("wrapped", Any),
+            ],
+            Kind::SecurityScheme => &[
+                ("type", Any),
+                ("description", Any),
+                ("name", Any),
+                ("in", Any),
+                ("scheme", Any),
+                ("bearerFormat", Any),
+                ("flows", Object(Kind::OAuthFlows)),
+                ("openIdConnectUrl", Any),
+            ],
+            Kind::OAuthFlows => &[
+                ("implicit", Object(Kind::OAuthFlow)),
+                ("password", Object(Kind::OAuthFlow)),
+                ("clientCredentials", Object(Kind::OAuthFlow)),
+                ("authorizationCode", Object(Kind::OAuthFlow)),
+            ],
+            Kind::OAuthFlow => &[
+                ("authorizationUrl", Any),
+                ("tokenUrl", Any),
+                ("refreshUrl", Any),
+                ("scopes", Any),
+            ],
+        }
+    }
+
+    /// Some objects are maps with arbitrary keys; this is the kind of their
+    /// values.
+    fn map_values(self) -> Option<Kind> {
+        match self {
+            Kind::Paths | Kind::Callback => Some(Kind::PathItem),
+            Kind::Responses => Some(Kind::Response),
+            _ => None,
+        }
+    }
+}
+
+/// Extensions consumed by the tools we generate clients and documentation
+/// with; misspellings of these are silently ignored by those tools.
+const KNOWN_EXTENSIONS: &[&str] = &[
+    "x-dropshot-pagination",
+    "x-dropshot-websocket",
+    "x-openapi-lint",
+    "x-rust-type",
+];
+
+/// A JSON Schema keyword from a draft newer than the one OpenAPI 3.0 is based
+/// on.
+struct NewerKeyword {
+    name: &'static str,
+    /// Where the keyword's value may contain subschemas so that we can keep
+    /// looking inside them.
+    subschemas: Option<Field>,
+    /// What to use instead in OpenAPI 3.0.
+    alternative: &'static str,
+    /// Whether our conversion of draft 2020-12 schemas to OpenAPI 3.0
+    /// rewrites the keyword into its 3.0 equivalent.
+    converted: bool,
+}
+
+/// Keywords that 3.0 consumers ignore, along with what to use instead.
+const NEWER_KEYWORDS: &[NewerKeyword] = &{
+    const fn keyword(
+        name: &'static str,
+        subschemas: Option<Field>,
+        alternative: &'static str,
+        converted: bool,
+    ) -> NewerKeyword {
+        NewerKeyword {
+            name,
+            subschemas,
+            alternative,
+            converted,
+        }
+    }
+
+    const SCHEMA: Option<Field> = Some(Field::Object(Kind::Schema));
+    const SCHEMAS: Option<Field> = Some(Field::Array(Kind::Schema));
+    const SCHEMA_MAP: Option<Field> = Some(Field::Map(Kind::Schema));
+    const COMPONENTS: &str = "named schemas under #/components/schemas";
+    const ONE_OF: &str = "\"oneOf\" with a discriminator";
+    const ADDITIONAL: &str = "\"additionalProperties\"";
+
+    [
+        keyword("$defs", SCHEMA_MAP, COMPONENTS, true),
+        keyword("definitions", SCHEMA_MAP, COMPONENTS, true),
+        keyword("const", None, "a single-valued \"enum\"", true),
+        keyword("examples", None, "\"example\"", true),
+        keyword("if", SCHEMA, ONE_OF, false),
+        keyword("then", SCHEMA, ONE_OF, false),
+        keyword("else", SCHEMA, ONE_OF, false),
+        keyword("dependentSchemas", SCHEMA_MAP, ONE_OF, false),
+        keyword("dependentRequired", None, ONE_OF, false),
+        keyword("patternProperties", SCHEMA_MAP, ADDITIONAL, false),
+        keyword("propertyNames", SCHEMA, ADDITIONAL, false),
+        keyword("unevaluatedProperties", SCHEMA, ADDITIONAL, false),
+        keyword(
+            "prefixItems",
+            SCHEMAS,
+            "an object with named properties",
+            false,
+        ),
+        keyword("contains", SCHEMA, "\"items\"", false),
+    ]
+};
+
+/// A problem with the raw document that deserialization would hide.
+pub(crate) enum Problem {
+    /// A field that either isn't part of the OpenAPI object in which it
+    /// appears or is an extension that looks like a misspelling of a known
+    /// extension.
+    UnknownField {
+        pointer: String,
+        name: String,
+        extension: bool,
+        suggestion: Option<&'static str>,
+    },
+    /// A schema keyword from a newer JSON Schema draft that OpenAPI 3.0 does
+    /// not support.
+    UnsupportedKeyword {
+        pointer: String,
+        name: &'static str,
+        alternative: &'static str,
+        converted: bool,
+    },
+    /// A schema whose `type` is an array, as newer JSON Schema drafts allow.
+    /// Our conversion handles a single type paired with `"null"` by making
+    /// the schema nullable.
+    TypeArray { pointer: String, converted: bool },
+    /// A `content` map that is present but empty (which deserializes the same
+    /// as one that is absent).
+    EmptyContent { pointer: String },
+    /// A key of a responses map that isn't a registered HTTP status code, a
+    /// range of them (e.g. `4XX`), or `default`; keys that aren't status
+    /// codes at all are dropped by deserialization.
+    StatusCode { pointer: String, key: String },
+}
+
+impl Problem {
+    /// A JSON pointer to where the problem was found.
+    pub(crate) fn pointer(&self) -> &str {
+        match self {
+            Problem::UnknownField { pointer, .. }
+            | Problem::UnsupportedKeyword { pointer, .. }
+            | Problem::TypeArray { pointer, .. }
+            | Problem::EmptyContent { pointer }
+            | Problem::StatusCode { pointer, .. } => pointer,
+        }
+    }
+}
+
+/// An extension (i.e. `x-` field) of an OpenAPI object.
+pub(crate) struct Extension<'a> {
+    pub pointer: String,
+    pub name: &'a str,
+    pub value: &'a Value,
+}
+
+/// What a scan of the raw document found.
+#[derive(Default)]
+pub(crate) struct Document<'a> {
+    pub problems: Vec<Problem>,
+    pub extensions: Vec<Extension<'a>>,
+    /// Pointers to the `example` and `examples` fields of parameters,
+    /// headers, and media types. (Schemas have only `example` in OpenAPI 3.0.)
+    pub examples: Vec<String>,
+}
+
+pub(crate) fn scan(value: &Value) -> Document<'_> {
+    let mut doc = Document::default();
+    check_object(&mut doc, String::new(), value, Kind::OpenApi);
+    doc
+}
+
+fn check_object<'a>(doc: &mut Document<'a>, pointer: String, value: &'a Value, kind: Kind) {
+    let Some(obj) = value.as_object() else {
+        return;
+    };
+
+    // Reference objects (other than path items which may have siblings)
+    // ignore all other fields.
+    if kind != Kind::PathItem && obj.contains_key("$ref") {
+        return;
+    }
+
+    for (name, value) in obj {
+        let field_pointer = format!("{}/{}", pointer, escape(name));
+
+        if name.starts_with("x-") {
+            if !KNOWN_EXTENSIONS.contains(&name.as_str()) {
+                if let Some(suggestion) = closest(name, KNOWN_EXTENSIONS.iter().copied()) {
+                    doc.problems.push(Problem::UnknownField {
+                        pointer: field_pointer.clone(),
+                        name: name.clone(),
+                        extension: true,
+                        suggestion: Some(suggestion),
+                    });
+                }
+            }
+            doc.extensions.push(Extension {
+                pointer: field_pointer,
+                name,
+                value,
+            });
+            continue;
+        }
+
+        if let Some(value_kind) = kind.map_values() {
+            if kind == Kind::Responses && name != "default" && !is_status_code(name) {
+                doc.problems.push(Problem::StatusCode {
+                    pointer: field_pointer.clone(),
+                    key: name.clone(),
+                });
+            }
+            check_object(doc, field_pointer, value, value_kind);
+            continue;
+        }
+
+        if kind == Kind::Schema {
+            if let Some(keyword) = NEWER_KEYWORDS.iter().find(|keyword| keyword.name == name) {
+                doc.problems.push(Problem::UnsupportedKeyword {
+                    pointer: field_pointer.clone(),
+                    name: keyword.name,
+                    alternative: keyword.alternative,
+                    converted: keyword.converted,
+                });
+                if let Some(field) = &keyword.subschemas {
+                    check_field(doc, field_pointer, name, value, field);
+                }
+                continue;
+            }
+
+            if let ("type", Value::Array(types)) = (name.as_str(), value) {
+                let converted = types.len() == 2 && types.contains(&Value::from("null"));
+                doc.problems.push(Problem::TypeArray {
+                    pointer: field_pointer,
+                    converted,
+                });
+                continue;
+            }
+        }
+
+        if matches!(kind, Kind::Parameter | Kind::Header | Kind::MediaType)
+            && matches!(name.as_str(), "example" | "examples")
+        {
+            doc.examples.push(field_pointer.clone());
+        }
+
+        match kind.fields().iter().find(|(field, _)| field == name) {
+            None => doc.problems.push(Problem::UnknownField {
+                pointer: field_pointer,
+                name: name.clone(),
+                extension: false,
+                suggestion: closest(name, kind.fields().iter().map(|(field, _)| *field)),
+            }),
+            Some((_, field)) => check_field(doc, field_pointer, name, value, field),
+        }
+    }
+}
+
+fn check_field<'a>(
+    doc: &mut Document<'a>,
+    field_pointer: String,
+    name: &str,
+    value: &'a Value,
+    field: &Field,
+) {
+    match field {
+        Field::Any => {}
+        Field::Object(kind) => check_object(doc, field_pointer, value, *kind),
+        Field::Array(kind) => {
+            for (index, item) in value.as_array().into_iter().flatten().enumerate() {
+                check_object(doc, format!("{}/{}", field_pointer, index), item, *kind);
+            }
+        }
+        Field::Map(kind) => {
+            if name == "content" && value.as_object().is_some_and(|map| map.is_empty()) {
+                doc.problems.push(Problem::EmptyContent {
+                    pointer: field_pointer.clone(),
+                });
+            }
+            for (key, item) in value.as_object().into_iter().flatten() {
+                check_object(
+                    doc,
+                    format!("{}/{}", field_pointer, escape(key)),
+                    item,
+                    *kind,
+                );
+            }
+        }
+    }
+}
+
+/// Apply `f` to every schema in a raw document, along with the JSON pointer
+/// to it, each before those within it, so that what `f` leaves of a schema
+/// determines which are visited next.
+pub(crate) fn for_each_schema(value: &mut Value, f: &mut dyn FnMut(&str, &mut Map<String, Value>)) {
+    visit_object(value, String::new(), Kind::OpenApi, f);
+}
+
+fn visit_object(
+    value: &mut Value,
+    pointer: String,
+    kind: Kind,
+    f: &mut dyn FnMut(&str, &mut Map<String, Value>),
+) {
+    let Some(obj) = value.as_object_mut() else {
+        return;
+    };
+    if kind == Kind::Schema {
+        f(&pointer, obj);
+    }
+    for (name, value) in obj.iter_mut() {
+        if name.starts_with("x-") {
+            continue;
+        }
+        let field_pointer = format!("{}/{}", pointer, escape(name));
+        if let Some(value_kind) = kind.map_values() {
+            visit_object(value, field_pointer, value_kind, f);
+            continue;
+        }
+        let newer = (kind == Kind::Schema)
+            .then(|| NEWER_KEYWORDS.iter().find(|keyword| keyword.name == name))
+            .flatten()
+            .and_then(|keyword| keyword.subschemas.as_ref());
+        let field = kind
+            .fields()
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, field)| field);
+        match newer.or(field) {
+            Some(Field::Object(kind)) => visit_object(value, field_pointer, *kind, f),
+            Some(Field::Array(kind)) => {
+                for (index, item) in value.as_array_mut().into_iter().flatten().enumerate() {
+                    visit_object(item, format!("{}/{}", field_pointer, index), *kind, f);
+                }
+            }
+            Some(Field::Map(kind)) => {
+                for (key, item) in value.as_object_mut().into_iter().flatten() {
+                    let item_pointer = format!("{}/{}", field_pointer, escape(key));
+                    visit_object(item, item_pointer, *kind, f);
+                }
+            }
+            Some(Field::Any) | None => {}
+        }
+    }
+}
+
+/// The status codes in the IANA HTTP Status Code Registry.
+const REGISTERED_STATUS_CODES: &[u16] = &[
+    100, 101, 102, 103, 200, 201, 202, 203, 204, 205, 206, 207, 208, 226, 300, 301, 302, 303, 304,
+    305, 307, 308, 400, 401, 402, 403, 404, 405, 406, 407, 408, 409, 410, 411, 412, 413, 414, 415,
+    416, 417, 421, 422, 423, 424, 425, 426, 428, 429, 431, 451, 500, 501, 502, 503, 504, 505, 506,
+    507, 508, 510, 511,
+];
+
+/// Whether a key of a responses map is a registered status code or a range
+/// of codes from 1XX to 5XX.
+fn is_status_code(key: &str) -> bool {
+    match key.as_bytes() {
+        [b'1'..=b'5', b'X', b'X'] => true,
+        [a, b, c] if [a, b, c].iter().all(|digit| digit.is_ascii_digit()) => key
+            .parse()
+            .is_ok_and(|code| REGISTERED_STATUS_CODES.contains(&code)),
+        _ => false,
+    }
+}
+
+/// Find the candidate that `name` is most plausibly a misspelling of, if any.
+fn closest<'a>(name: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let name = name.to_lowercase();
+    candidates
+        .map(|candidate| (edit_distance(&name, &candidate.to_lowercase()), candidate))
+        // Allow more edits for longer names, but never more than two.
+        .filter(|(distance, _)| *distance <= 2 && *distance * 3 <= name.len())
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// The number of insertions, deletions, substitutions, and transpositions of
+/// adjacent characters needed to turn `a` into `b` (i.e. the optimal string
+/// alignment distance).
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+
+    // d[i][j] is the distance between the first i characters of a and the
+    // first j characters of b.
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    d[a.len()][b.len()]
+}
+
+/// Escape a key for use as a JSON Pointer component (RFC 6901).
+pub(crate) fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Unescape a JSON Pointer component (RFC 6901).
+pub(crate) fn unescape(key: &str) -> String {
+    key.replace("~1", "/").replace("~0", "~")
+}
@@ -0,0 +1,234 @@
+// Copyright 2026 Oxide Computer Company
+
+//! Applying the fixes that findings suggest to the document in which they
+//! were found, for adopting the linter on a spec with many mechanical
+//! problems (e.g. miscased names) without fixing each by hand.
+
+use serde_json::{json, Value};
+
+use crate::{
+    fields::{escape, unescape},
+    naming::Target,
+    Diagnostic, Suggestion,
+};
+
+/// The findings whose fixes [`apply_fixes`] applied, and those it couldn't.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct Fixes {
+    fixed: Vec<Diagnostic>,
+    unfixed: Vec<Diagnostic>,
+}
+
+impl Fixes {
+    /// The findings whose fixes were applied.
+    pub fn fixed(&self) -> &[Diagnostic] {
+        &self.fixed
+    }
+
+    /// The findings without a suggested fix, or whose fix couldn't be applied
+    /// (e.g. because the new name is taken), in the order in which they were
+    /// given.
+    pub fn unfixed(&self) -> &[Diagnostic] {
+        &self.unfixed
+    }
+
+    pub fn into_unfixed(self) -> Vec<Diagnostic> {
+        self.unfixed
+    }
+}
+
+/// Apply the [suggestions](Diagnostic::suggestion) of the given findings to
+/// the document in which they were found (e.g. by [`crate::validate_document`]
+/// and [`crate::validate_with_config`]), placing each by its JSON pointer.
+///
+/// Renaming an element updates what refers to it: renaming a component schema
+/// updates references to it, a property the `required` list of its object, and
+/// an enumerated value its schema's default. A singular `example` becomes the
+/// entry "example" of an `examples` map. The parameters of an operation
+/// are renamed where they are defined, in the operation or among the
+/// components; path parameters, which are also named in the path, aren't
+/// renamed. A fix whose element isn't where the finding places it, or whose
+/// new name is already taken, is not applied.
+///
+/// ```
+/// let mut document = serde_json::json!({
+///     "openapi": "3.0.3",
+///     "info": { "title": "widgets", "version": "1.0.0" },
+///     "paths": {},
+///     "components": { "schemas": { "Widget": {
+///         "type": "object",
+///         "properties": { "timeCreated": { "type": "string" } },
+///         "required": ["timeCreated"],
+///     } } },
+/// });
+/// let spec = serde_json::from_value(document.clone()).unwrap();
+/// let fixes = openapi_lint::apply_fixes(&mut document, openapi_lint::validate(&spec));
+/// assert_eq!(fixes.fixed().len(), 1);
+/// assert_eq!(
+///     document["components"]["schemas"]["Widget"]["required"],
+///     serde_json::json!(["time_created"]),
+/// );
+/// ```
+pub fn apply_fixes(document: &mut Value, findings: Vec<Diagnostic>) -> Fixes {
+    // Fixes within an element are applied before the element itself is
+    // renamed, so that the pointers of the former still lead somewhere.
+    let mut order = (0..findings.len()).collect::<Vec<_>>();
+    let depth = |index: usize| {
+        let pointer = findings[index].location().pointer().unwrap_or_default();
+        pointer.matches('/').count()
+    };
+    order.sort_by_key(|index| std::cmp::Reverse(depth(*index)));
+
+    let mut fixed = vec![false; findings.len()];
+    let mut applied = Vec::<(&str, &Suggestion)>::new();
+    for index in order {
+        let finding = &findings[index];
+        let (Some(pointer), Some(suggestion)) =
+            (finding.location().pointer(), finding.suggestion())
+        else {
+            continue;
+        };
+        // The same problem with a shared element may be found more than once.
+        if applied.contains(&(pointer, suggestion)) {
+            fixed[index] = true;
+            continue;
+        }
+        if apply(document, pointer, suggestion).is_some() {
+            fixed[index] = true;
+            applied.push((pointer, suggestion));
+        }
+    }
+
+    let mut fixes = Fixes::default();
+    for (finding, fixed) in findings.into_iter().zip(fixed) {
+        match fixed {
+            true => fixes.fixed.push(finding),
+            false => fixes.unfixed.push(finding),
+        }
+    }
+    fixes
+}
+
+fn apply(document: &mut Value, pointer: &str, suggestion: &Suggestion) -> Option<()> {
+    let (from, to) = (suggestion.from(), suggestion.to());
+    if suggestion.kind() == "move" {
+        return move_field(document, pointer, from, to);
+    }
+    match suggestion.target() {
+        None => replace(document.pointer_mut(pointer)?, from, to),
+        Some(Target::EnumValue) => {
+            replace(document.pointer_mut(pointer)?, from, to)?;
+            let (schema, _) = pointer.rsplit_once("/enum/")?;
+            if let Some(default) = document.pointer_mut(&format!("{}/default", schema)) {
+                // The default may be another value.
+                let _ = replace(default, from, to);
+            }
+            Some(())
+        }
+        Some(Target::Property) => {
+            rename_key(document, pointer, from, to)?;
+            let (schema, _) = pointer.rsplit_once("/properties/")?;
+            let required = document.pointer_mut(&format!("{}/required", schema));
+            for name in required.and_then(Value::as_array_mut).into_iter().flatten() {
+                let _ = replace(name, from, to);
+            }
+            Some(())
+        }
+        Some(Target::Type) => {
+            rename_key(document, pointer, from, to)?;
+            let prefix = pointer.strip_suffix(&escape(from))?;
+            let from = format!("#{}{}", prefix, escape(from));
+            let to = format!("#{}{}", prefix, escape(to));
+            rename_references(document, &from, &to);
+            Some(())
+        }
+        Some(Target::PathSegment) => rename_key(document, pointer, from, to),
+        Some(Target::OperationId) => replace(
+            document.pointer_mut(pointer)?.get_mut("operationId")?,
+            from,
+            to,
+        ),
+        Some(Target::Parameter) => rename_parameter(document, pointer, from, to),
+    }
+}
+
+/// Replace the string `from` with `to`.
+fn replace(value: &mut Value, from: &str, to: &str) -> Option<()> {
+    (value.as_str()? == from).then(|| *value = Value::from(to))
+}
+
+/// Rename the member of an object at `pointer`, named `from`, to `to`.
+fn rename_key(document: &mut Value, pointer: &str, from: &str, to: &str) -> Option<()> {
+    let (parent, key) = pointer.rsplit_once('/')?;
+    if unescape(key) != from {
+        return None;
+    }
+    let object = document.pointer_mut(parent)?.as_object_mut()?;
+    if object.contains_key(to) {
+        return None;
+    }
+    let value = object.remove(from)?;
+    object.insert(to.to_string(), value);
+    Some(())
+}
+
+/// Move the field at `pointer`, named `from`, into the map `to` of the same
+/// object, as the entry "example" with the field's value as its `value`.
+fn move_field(document: &mut Value, pointer: &str, from: &str, to: &str) -> Option<()> {
+    let (parent, key) = pointer.rsplit_once('/')?;
+    if unescape(key) != from {
+        return None;
+    }
+    let object = document.pointer_mut(parent)?.as_object_mut()?;
+    if object.contains_key(to) {
+        return None;
+    }
+    let value = object.remove(from)?;
+    object.insert(to.to_string(), json!({ "example": { "value": value } }));
+    Some(())
+}
+
+/// Point every reference to `from` at `to` instead.
+fn rename_references(value: &mut Value, from: &str, to: &str) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                if key == "$ref" {
+                    let _ = replace(value, from, to);
+                } else {
+                    rename_references(value, from, to);
+                }
+            }
+        }
+        Value::Array(array) => {
+            for value in array {
+                rename_references(value, from, to);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Rename a parameter of the operation at `pointer` where it is defined, unless
+/// it is a path parameter.
+fn rename_parameter(document: &mut Value, pointer: &str, from: &str, to: &str) -> Option<()> {
+    let parameters = document
+        .pointer(&format!("{}/parameters", pointer))?
+        .as_array()?;
+    let index = parameters.iter().position(|parameter| {
+        let parameter = match parameter.get("$ref").and_then(Value::as_str) {
+            Some(reference) => reference
+                .strip_prefix('#')
+                .and_then(|pointer| document.pointer(pointer)),
+            None => Some(parameter),
+        };
+        parameter.is_some_and(|parameter| parameter["name"] == from && parameter["in"] != "path")
+    })?;
+    let parameter = format!("{}/parameters/{}", pointer, index);
+    let parameter = match document.pointer(&parameter)?.get("$ref") {
+        Some(reference) => reference.as_str()?.strip_prefix('#')?.to_string(),
+        None => parameter,
+    };
+    replace(document.pointer_mut(&parameter)?.get_mut("name")?, from, to)
+}
@@ -0,0 +1,207 @@
+// Copyright 2026 Oxide Computer Company
+
+//! The graph of references between the operations and components of a spec,
+//! for questions such as which operations a change to a schema affects.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use openapiv3::OpenAPI;
+
+use crate::fields::{escape, unescape};
+
+/// An operation or component of a spec, as found in a [`SpecGraph`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Node {
+    /// A component, by its kind (e.g. "schemas") and its name.
+    Component { kind: String, name: String },
+    /// An operation, by its method (in lowercase) and path.
+    Operation { method: String, path: String },
+}
+
+impl Node {
+    /// A component schema.
+    pub fn schema<S: Into<String>>(name: S) -> Self {
+        Node::Component {
+            kind: "schemas".to_string(),
+            name: name.into(),
+        }
+    }
+
+    /// The JSON pointer to the operation or component within the spec.
+    pub fn pointer(&self) -> String {
+        match self {
+            Node::Component { kind, name } => format!("/components/{}/{}", kind, escape(name)),
+            Node::Operation { method, path } => format!("/paths/{}/{}", escape(path), method),
+        }
+    }
+}
+
+impl std::fmt::Display for Node {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Node::Component { kind, name } => write!(f, "{}/{}", kind, name),
+            Node::Operation { method, path } => {
+                write!(f, "{} {}", method.to_uppercase(), path)
+            }
+        }
+    }
+}
+
+/// Which operations and components of a spec refer to which components,
+/// directly (with `$ref`) or by way of others.
+///
+/// ```
+/// use openapi_lint::{Node, SpecGraph};
+///
+/// # let spec: openapiv3::OpenAPI = serde_json::from_str(r##"{
+/// #     "openapi": "3.0.3", "info": {"title": "", "version": ""},
+/// #     "paths": {"/disks": {"get": {"operationId": "disk_list", "responses": {
+/// #         "200": {"description": "", "content": {"application/json": {
+/// #             "schema": {"$ref": "#/components/schemas/DiskResultsPage"}}}}}}}},
+/// #     "components": {"schemas": {
+/// #         "DiskResultsPage": {"type": "object", "properties": {
+/// #             "items": {"type": "array", "items": {"$ref": "#/components/schemas/Disk"}}}},
+/// #         "Disk": {"type": "object"}}}
+/// # }"##).unwrap();
+/// let graph = SpecGraph::new(&spec);
+/// assert_eq!(graph.who_references("Disk"), [&Node::schema("DiskResultsPage")]);
+/// assert_eq!(graph.operations_using_schema("Disk").len(), 1);
+/// assert_eq!(
+///     graph.schemas_reachable_from("disk_list"),
+///     ["Disk", "DiskResultsPage"],
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SpecGraph {
+    /// The components to which each operation or component refers directly.
+    references: BTreeMap<Node, BTreeSet<Node>>,
+    /// The operations and components that refer directly to each component.
+    referrers: BTreeMap<Node, BTreeSet<Node>>,
+    operation_ids: BTreeMap<String, Node>,
+}
+
+impl SpecGraph {
+    /// Find the references in a spec. Only references to components of the
+    /// spec itself (`#/components/<kind>/<name>`) are followed; path-level
+    /// parameters count as references from each operation of the path.
+    pub fn new(spec: &OpenAPI) -> Self {
+        let mut graph = SpecGraph::default();
+        let mut operations = BTreeMap::<&str, Vec<Node>>::new();
+        for (path, method, op) in spec.operations() {
+            let node = Node::Operation {
+                method: method.to_string(),
+                path: path.to_string(),
+            };
+            if let Some(operation_id) = &op.operation_id {
+                graph
+                    .operation_ids
+                    .insert(operation_id.clone(), node.clone());
+            }
+            operations.entry(path).or_default().push(node);
+        }
+
+        let Ok(document) = serde_json::to_value(spec) else {
+            return graph;
+        };
+        let mut references = Vec::new();
+        crate::find_references(&document, String::new(), &mut references);
+        for (pointer, reference) in references {
+            let Some(target) = component(reference.strip_prefix('#').unwrap_or_default()) else {
+                continue;
+            };
+            let segments = pointer.split('/').skip(1).collect::<Vec<_>>();
+            let referrers = match segments.as_slice() {
+                ["components", kind, name, ..] => vec![Node::Component {
+                    kind: kind.to_string(),
+                    name: unescape(name),
+                }],
+                ["paths", path, "parameters", ..] => operations
+                    .get(unescape(path).as_str())
+                    .cloned()
+                    .unwrap_or_default(),
+                ["paths", path, method, ..] => vec![Node::Operation {
+                    method: method.to_string(),
+                    path: unescape(path),
+                }],
+                _ => continue,
+            };
+            for referrer in referrers {
+                graph
+                    .referrers
+                    .entry(target.clone())
+                    .or_default()
+                    .insert(referrer.clone());
+                graph
+                    .references
+                    .entry(referrer)
+                    .or_default()
+                    .insert(target.clone());
+            }
+        }
+        graph
+    }
+
+    /// The operations and components that refer directly to the component
+    /// schema with the given name.
+    pub fn who_references(&self, schema: &str) -> Vec<&Node> {
+        self.referrers
+            .get(&Node::schema(schema))
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// The operations that use the component schema with the given name,
+    /// directly or by way of other components, and so are affected by a
+    /// change to it.
+    pub fn operations_using_schema(&self, schema: &str) -> Vec<&Node> {
+        let mut seen = BTreeSet::new();
+        let mut pending = vec![Node::schema(schema)];
+        while let Some(node) = pending.pop() {
+            for referrer in self.referrers.get(&node).into_iter().flatten() {
+                if seen.insert(referrer) {
+                    pending.push(referrer.clone());
+                }
+            }
+        }
+        seen.into_iter()
+            .filter(|node| matches!(node, Node::Operation { .. }))
+            .collect()
+    }
+
+    /// The names of the component schemas that the operation with the given
+    /// ID uses, directly or by way of other components, ordered by name.
+    pub fn schemas_reachable_from(&self, operation_id: &str) -> Vec<&str> {
+        let Some(operation) = self.operation_ids.get(operation_id) else {
+            return Vec::new();
+        };
+        let mut seen = BTreeSet::new();
+        let mut pending = vec![operation];
+        while let Some(node) = pending.pop() {
+            for reference in self.references.get(node).into_iter().flatten() {
+                if seen.insert(reference) {
+                    pending.push(reference);
+                }
+            }
+        }
+        seen.into_iter()
+            .filter_map(|node| match node {
+                Node::Component { kind, name } if kind == "schemas" => Some(name.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// The component to which a local reference (less its leading `#`) refers.
+fn component(target: &str) -> Option<Node> {
+    match target.split('/').skip(1).collect::<Vec<_>>().as_slice() {
+        ["components", kind, name] => Some(Node::Component {
+            kind: kind.to_string(),
+            name: unescape(name),
+        }),
+        _ => None,
+    }
+}
@@ -0,0 +1,189 @@
+// Copyright 2026 Oxide Computer Company
+
+//! Parsing the text of a spec, with specific errors for text that holds
+//! several documents (e.g. specs concatenated into one file) rather than
+//! serde's, which don't say what went wrong.
+
+use serde_json::Value;
+
+/// A problem with the text of a spec, as returned by [`parse_document`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum InputError {
+    /// The text is neither JSON nor YAML.
+    #[cfg(feature = "yaml")]
+    Parse(serde_yaml::Error),
+    /// The text isn't JSON, and YAML isn't read without the `yaml` feature.
+    Json(serde_json::Error),
+    /// The text is a stream of several YAML documents (separated by `---`) or
+    /// JSON values, rather than a single spec; see [`parse_stream`].
+    MultipleDocuments { count: usize },
+    /// The text is an array (e.g. of specs) rather than a single spec.
+    Array { len: usize },
+}
+
+impl std::fmt::Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            #[cfg(feature = "yaml")]
+            InputError::Parse(_) => f.write_str("the text is neither JSON nor YAML"),
+            InputError::Json(_) => f.write_str("the text isn't JSON"),
+            InputError::MultipleDocuments { count } => write!(
+                f,
+                "the text holds {} documents rather than a single spec; were specs \
+                concatenated?",
+                count,
+            ),
+            InputError::Array { len } => write!(
+                f,
+                "the text is an array of {} elements rather than a single spec; were \
+                specs concatenated?",
+                len,
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "yaml")]
+            InputError::Parse(err) => Some(err),
+            InputError::Json(err) => Some(err),
+            InputError::MultipleDocuments { .. } | InputError::Array { .. } => None,
+        }
+    }
+}
+
+/// One of the documents of a YAML stream, as returned by [`parse_stream`].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct StreamDocument<'a> {
+    text: &'a str,
+    line: usize,
+    value: Value,
+}
+
+impl<'a> StreamDocument<'a> {
+    /// The text of the document, without the markers that separate it from
+    /// the others.
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// The line of the stream on which the document's text starts, counted
+    /// from 1; see [`crate::SourceMap::with_first_line`].
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+
+    pub fn into_value(self) -> Value {
+        self.value
+    }
+}
+
+/// Parse the text of a single spec, in JSON or (with the `yaml` feature)
+/// YAML.
+///
+/// ```
+/// use openapi_lint::{parse_document, InputError};
+///
+/// let text = "openapi: 3.0.3\n---\nopenapi: 3.0.3\n";
+/// assert!(matches!(
+///     parse_document(text),
+///     Err(InputError::MultipleDocuments { count: 2 }),
+/// ));
+/// ```
+pub fn parse_document(text: &str) -> Result<Value, InputError> {
+    let value = match serde_json::from_str::<Value>(text) {
+        Ok(value) => value,
+        Err(err) => {
+            let json = serde_json::Deserializer::from_str(text)
+                .into_iter::<Value>()
+                .take_while(Result::is_ok)
+                .count();
+            if json > 1 {
+                return Err(InputError::MultipleDocuments { count: json });
+            }
+            let documents = split_stream(text);
+            if documents.len() > 1 {
+                return Err(InputError::MultipleDocuments {
+                    count: documents.len(),
+                });
+            }
+            parse_yaml(text, err)?
+        }
+    };
+    match value {
+        Value::Array(array) => Err(InputError::Array { len: array.len() }),
+        value => Ok(value),
+    }
+}
+
+/// Parse text that isn't JSON (as `err` says) as YAML.
+#[cfg(feature = "yaml")]
+fn parse_yaml(text: &str, _err: serde_json::Error) -> Result<Value, InputError> {
+    serde_yaml::from_str(text).map_err(InputError::Parse)
+}
+
+/// Without the `yaml` feature, text that isn't JSON is an error.
+#[cfg(not(feature = "yaml"))]
+fn parse_yaml(_text: &str, err: serde_json::Error) -> Result<Value, InputError> {
+    Err(InputError::Json(err))
+}
+
+/// Parse each of the documents of a YAML stream (one holding a single document
+/// is a stream too), for linting specs that have been concatenated into one
+/// file. Each document must be a spec in its own right, as for
+/// [`parse_document`].
+pub fn parse_stream(text: &str) -> Result<Vec<StreamDocument<'_>>, InputError> {
+    split_stream(text)
+        .into_iter()
+        .map(|(line, text)| {
+            Ok(StreamDocument {
+                text,
+                line,
+                value: parse_document(text)?,
+            })
+        })
+        .collect()
+}
+
+/// Split a YAML stream into the text of each of its documents, with the line
+/// on which each starts. A document starts after a `---` marker (or at the
+/// start of the stream) and ends at the next marker or at a `...` marker;
+/// documents with no content other than comments are dropped.
+fn split_stream(text: &str) -> Vec<(usize, &str)> {
+    let mut documents = Vec::new();
+    let mut start = Some((1, 0));
+    let mut offset = 0;
+    for (index, line) in text.split_inclusive('\n').enumerate() {
+        let end = offset;
+        offset += line.len();
+        let marker = line.trim_end();
+        if marker == "---" || marker.starts_with("--- ") {
+            if let Some((line, start)) = start {
+                documents.push((line, &text[start..end]));
+            }
+            start = Some((index + 2, offset));
+        } else if marker == "..." {
+            if let Some((line, start)) = start.take() {
+                documents.push((line, &text[start..end]));
+            }
+        }
+    }
+    if let Some((line, start)) = start {
+        documents.push((line, &text[start..]));
+    }
+    documents.retain(|(_, text)| {
+        text.lines().any(|line| {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with('#') && !line.starts_with('%')
+        })
+    });
+    documents
+}
@@ -6,103 +6,998 @@
 //! generators would have a hard time turning into easy-to-use native
 //! constructs.
 
-use heck::{ToKebabCase, ToPascalCase, ToShoutySnakeCase, ToSnakeCase};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
+
+use heck::{ToPascalCase, ToSnakeCase};
 use indexmap::IndexMap;
 use openapiv3::{
-    AnySchema, Components, OpenAPI, Operation, Parameter, ReferenceOr, Response, Schema,
-    SchemaKind, StringType, Type, VariantOrUnknownOrEmpty,
+    AdditionalProperties, AnySchema, ArrayType, BooleanType, Components, Header, IntegerType,
+    NumberType, ObjectType, OpenAPI, Operation, Parameter, ParameterSchemaOrContent, PathItem,
+    PathStyle, ReferenceOr, RequestBody, Response, Schema, SchemaKind, StatusCode, StringFormat,
+    StringType, Type, VariantOrUnknownOrEmpty,
 };
 
+mod attributes;
+mod baseline;
+mod check;
+mod config;
+mod config_file;
+mod diagnostic;
+mod extensions;
+mod fields;
+mod fix;
+mod graph;
+mod input;
+mod lints;
+pub mod naming;
+mod openapi31;
+mod profile;
+#[cfg(feature = "miette")]
+mod render;
+mod report;
+mod resolver;
+mod source;
+mod tags;
+mod validator;
+mod waivers;
 mod walker;
 
+pub use attributes::{attribute_plan, AttributeFix, AttributePlan};
+pub use baseline::Baseline;
+pub use check::{Check, Context};
+pub use config::{Config, Element};
+pub use config_file::{ConfigError, CONFIG_FILE};
+pub use diagnostic::{Diagnostic, Location, Severity, Suggestion};
+pub use fix::{apply_fixes, Fixes};
+pub use graph::{Node, SpecGraph};
+pub use input::{parse_document, parse_stream, InputError, StreamDocument};
+pub use lints::{explain, lints, Category, Lint};
+pub use openapi31::{convert_3_1, is_3_1};
+pub use profile::Profile;
+#[cfg(feature = "miette")]
+pub use render::Snippet;
+pub use report::{Report, Skipped, Waived};
+pub use resolver::{ResolveError, Resolver};
+pub use source::{Position, SourceMap};
+pub use tags::{TagReport, TaggedOperation};
+pub use validator::{Validator, ValidatorBuilder};
+pub use walker::{SchemaWalker, Walk, Walked};
+
+use naming::{Case, Target};
 use regex::Regex;
-use walker::SchemaWalker;
+use waivers::Waivers;
+
+/// Validate a spec with [`Profile::internal`], i.e. the default settings.
+pub fn validate(spec: &OpenAPI) -> Vec<Diagnostic> {
+    validate_with_config(spec, &Config::default())
+}
+
+/// Validate a spec with [`Profile::external`]. For other profiles, see
+/// [`ValidatorBuilder::profile`].
+pub fn validate_external(spec: &OpenAPI) -> Vec<Diagnostic> {
+    validate_with_config(spec, Config::default().with_profile(&Profile::external()))
+}
+
+pub fn validate_with_config(spec: &OpenAPI, config: &Config) -> Vec<Diagnostic> {
+    validate_with_report(spec, config).into_findings()
+}
+
+/// Like [`validate_with_config`], but also report the constructs that rules
+/// skipped rather than checked, so that gaps in coverage aren't silent.
+pub fn validate_with_report(spec: &OpenAPI, config: &Config) -> Report {
+    let mut findings = Findings::new(spec, config);
+    #[cfg(feature = "parallel")]
+    findings.validate_schemas_in_parallel();
+    // One more than the maximum is looked for, to tell whether there are more.
+    let limit = config
+        .max_findings
+        .map_or(usize::MAX, |max| max.saturating_add(1));
+    let mut found = findings.by_ref().take(limit).collect::<Vec<_>>();
+    let truncated = config.max_findings.is_some_and(|max| found.len() > max);
+    found.truncate(config.max_findings.unwrap_or(usize::MAX));
+    Report {
+        findings: found,
+        skipped: findings.rules.skipped.into_inner(),
+        waived: findings.rules.waivers.applied(),
+        config: config.to_json(),
+        truncated,
+    }
+}
+
+/// Like [`validate`], but find problems only as they are asked for, so that a
+/// caller that only needs to know whether there are any (e.g. to gate CI) can
+/// stop at the first.
+pub fn validate_iter(spec: &OpenAPI) -> impl Iterator<Item = Diagnostic> + '_ {
+    // The default configuration lives as long as the program.
+    static DEFAULT: std::sync::OnceLock<Config> = std::sync::OnceLock::new();
+    validate_iter_with_config(spec, DEFAULT.get_or_init(Config::default))
+}
+
+/// Like [`validate_with_config`], but find problems only as they are asked
+/// for; see [`validate_iter`].
+pub fn validate_iter_with_config<'a>(
+    spec: &'a OpenAPI,
+    config: &'a Config,
+) -> impl Iterator<Item = Diagnostic> + 'a {
+    Findings::new(spec, config).take(config.max_findings.unwrap_or(usize::MAX))
+}
 
-pub fn validate(spec: &OpenAPI) -> Vec<String> {
-    Validator::default().validate_impl(spec, false)
+/// Collect the renames that the naming rules call for into a single map, for
+/// tools that patch the code from which the spec is generated. Names are
+/// checked as they are by the [naming](https://github.com/oxidecomputer/openapi-lint#naming)
+/// and [paths](https://github.com/oxidecomputer/openapi-lint#paths) rules.
+pub fn rename_map(spec: &OpenAPI) -> naming::RenameMap {
+    let mut map = naming::RenameMap::default();
+
+    for type_name in spec.components.iter().flat_map(|c| c.schemas.keys()) {
+        map.insert(type_name, Target::Type);
+    }
+    for walked in spec.walk() {
+        match &walked.schema.schema_kind {
+            SchemaKind::Type(Type::Object(obj)) => {
+                for prop_name in obj.properties.keys() {
+                    map.insert(prop_name, Target::Property);
+                }
+            }
+            SchemaKind::Type(Type::String(StringType { enumeration, .. })) => {
+                for value in enumeration.iter().flatten() {
+                    map.insert(value, Target::EnumValue);
+                }
+            }
+            _ => {}
+        }
+    }
+    for (path, _, op) in spec.operations() {
+        if let Some(operation_id) = &op.operation_id {
+            map.insert(operation_id, Target::OperationId);
+        }
+        for param in op.parameters.iter() {
+            match param.item(&spec.components) {
+                None | Some(Parameter::Header { .. }) => {}
+                Some(param) => map.insert(&param.parameter_data_ref().name, Target::Parameter),
+            }
+        }
+        for segment in path.split('/').filter(|segment| !segment.starts_with('{')) {
+            map.insert(segment, Target::PathSegment);
+        }
+    }
+
+    map
 }
 
-pub fn validate_external(spec: &OpenAPI) -> Vec<String> {
-    Validator::default().validate_impl(spec, true)
+/// Group the operations of a spec by tag, with their methods and success
+/// types, for planning SDKs that generate a module per tag.
+pub fn tag_report(spec: &OpenAPI) -> TagReport {
+    let mut report = TagReport::default();
+    for (path, method, op) in spec.operations() {
+        let success_type = success_schema(spec, op).map(|schema| match schema {
+            ReferenceOr::Reference { reference } => {
+                reference_name(reference).unwrap_or(reference).to_string()
+            }
+            ReferenceOr::Item(_) => "inline".to_string(),
+        });
+        report.insert(TaggedOperation {
+            operation_id: op.operation_id.clone(),
+            method: method.to_string(),
+            path: path.to_string(),
+            success_type,
+            tags: op.tags.clone(),
+        });
+    }
+    report
 }
 
-struct Validator;
+/// Sort the entries of each section of a spec's components (schemas,
+/// responses, and so on) by name, as the [sorted
+/// components](https://github.com/oxidecomputer/openapi-lint#sorted-components)
+/// rule expects. The order of everything else, such as the properties of
+/// schemas, is left alone.
+pub fn sort_components(spec: &mut OpenAPI) {
+    let Some(components) = &mut spec.components else {
+        return;
+    };
+    components.security_schemes.sort_keys();
+    components.responses.sort_keys();
+    components.parameters.sort_keys();
+    components.examples.sort_keys();
+    components.request_bodies.sort_keys();
+    components.headers.sort_keys();
+    components.schemas.sort_keys();
+    components.links.sort_keys();
+    components.callbacks.sort_keys();
+}
 
-impl Default for Validator {
-    fn default() -> Self {
-        Self
+/// The names of the entries of each section of a spec's components, in
+/// order, by the section's key in the document.
+fn component_names(components: &Components) -> [(&'static str, Vec<&str>); 9] {
+    fn names<T>(section: &IndexMap<String, T>) -> Vec<&str> {
+        section.keys().map(String::as_str).collect()
     }
+    [
+        ("securitySchemes", names(&components.security_schemes)),
+        ("responses", names(&components.responses)),
+        ("parameters", names(&components.parameters)),
+        ("examples", names(&components.examples)),
+        ("requestBodies", names(&components.request_bodies)),
+        ("headers", names(&components.headers)),
+        ("schemas", names(&components.schemas)),
+        ("links", names(&components.links)),
+        ("callbacks", names(&components.callbacks)),
+    ]
 }
 
-impl Validator {
-    fn validate_impl(&self, spec: &OpenAPI, external: bool) -> Vec<String> {
-        let schema = spec.walk().flat_map(|(name, schema)| {
-            let subs = self.validate_subschemas(spec, schema).map(|msg| {
+/// Check a raw OpenAPI document for problems that deserialization hides:
+/// fields that look like misspellings of OpenAPI keywords or of known
+/// extensions, JSON Schema keywords that OpenAPI 3.0 doesn't support, and
+/// `content` maps that are present but empty. With
+/// [`Config::with_strict_parse`], any field that is not part of OpenAPI 3.0.3
+/// is reported. Examples given in the way that
+/// [`Config::with_singular_examples`] doesn't choose are reported too.
+pub fn validate_document(value: &serde_json::Value, config: &Config) -> Vec<Diagnostic> {
+    let document = fields::scan(value);
+
+    let problems = document.problems.into_iter().filter_map(|problem| {
+        let location = Location::from_pointer(problem.pointer());
+        let diagnostic = match problem {
+            fields::Problem::UnknownField {
+                pointer,
+                name,
+                extension,
+                suggestion,
+            } => {
+                let problem = if extension {
+                    format!(
+                        "The extension \"{}\" at {} is not a known extension",
+                        name, pointer,
+                    )
+                } else {
+                    format!(
+                        "The field \"{}\" at {} is not part of OpenAPI 3.0.3 and \
+                        will be ignored",
+                        name, pointer,
+                    )
+                };
+
+                match suggestion {
+                    Some(suggestion) => Some(Diagnostic::new(
+                        &lints::UNKNOWN_FIELD,
+                        format!("{}; did you mean \"{}\"?", problem, suggestion),
+                    )),
+                    None if config.strict_parse => {
+                        Some(Diagnostic::new(&lints::UNKNOWN_FIELD, problem))
+                    }
+                    None => None,
+                }
+            }
+            fields::Problem::UnsupportedKeyword {
+                pointer,
+                name,
+                alternative,
+                converted,
+            } => {
+                if !config.draft_2020_12 {
+                    Some(Diagnostic::new(
+                        &lints::UNSUPPORTED_KEYWORD,
+                        format!(
+                            "The schema keyword \"{}\" at {} is not supported by OpenAPI \
+                            3.0 and will be ignored by most tools; use {} instead.",
+                            name, pointer, alternative,
+                        ),
+                    ))
+                } else if !converted {
+                    Some(Diagnostic::new(
+                        &lints::UNCONVERTED_KEYWORD,
+                        format!(
+                            "{} uses the draft 2020-12 keyword \"{}\" (at {}), which is \
+                            not converted to OpenAPI 3.0; use {} instead.",
+                            containing_schema(&pointer),
+                            name,
+                            pointer,
+                            alternative,
+                        ),
+                    ))
+                } else {
+                    None
+                }
+            }
+            fields::Problem::TypeArray { pointer, converted } => {
+                if !config.draft_2020_12 {
+                    Some(Diagnostic::new(
+                        &lints::TYPE_ARRAY,
+                        format!(
+                            "The schema type at {} is an array, which is not supported by \
+                            OpenAPI 3.0; use a single type (with \"nullable\" if needed) \
+                            or \"oneOf\" instead.",
+                            pointer,
+                        ),
+                    ))
+                } else if !converted {
+                    Some(Diagnostic::new(
+                        &lints::UNCONVERTED_TYPE_ARRAY,
+                        format!(
+                            "{} uses an array of types (at {}), which is not converted to \
+                            OpenAPI 3.0 unless it pairs a single type with \"null\"; use \
+                            \"oneOf\" instead.",
+                            containing_schema(&pointer),
+                            pointer,
+                        ),
+                    ))
+                } else {
+                    None
+                }
+            }
+            fields::Problem::StatusCode { pointer, key } => {
+                let code = key.parse::<u16>().ok().filter(|_| key.len() == 3);
+                let problem = match code {
+                    Some(code) if config.allowed_status_codes.contains(&code) => return None,
+                    Some(100..=599) => "is not a registered HTTP status code",
+                    Some(_) => "is outside the range of HTTP status codes, 100 to 599",
+                    None => {
+                        "is neither an HTTP status code, a range of them (e.g. \"4XX\"), nor \
+                        \"default\""
+                    }
+                };
+                Some(Diagnostic::new(
+                    &lints::STATUS_CODE,
+                    format!("The response key \"{}\" at {} {}.", key, pointer, problem),
+                ))
+            }
+            fields::Problem::EmptyContent { pointer } => Some(Diagnostic::new(
+                &lints::EMPTY_CONTENT,
                 format!(
-                    "Problem with type {}: {}",
-                    name.unwrap_or_else(|| "<unknown>".to_string()),
-                    msg
+                    "The content map at {} is empty; omit it if there is no \
+                    content.",
+                    pointer,
+                ),
+            )),
+        };
+        diagnostic.map(|diagnostic| diagnostic.at(location))
+    });
+
+    let extensions = document.extensions.into_iter().flat_map(|extension| {
+        let Some(schema) = config.extension_schemas.get(extension.name) else {
+            return Vec::new();
+        };
+        extensions::mismatches(extension.value, schema, &extension.pointer)
+            .into_iter()
+            .map(|mismatch| {
+                Diagnostic::new(
+                    &lints::EXTENSION_VALUE,
+                    format!(
+                        "The value of the extension \"{}\" does not match its \
+                        expected schema: {}.",
+                        extension.name, mismatch,
+                    ),
                 )
-            });
-            let properties = self.validate_object(schema);
-            let enum_values = self.validate_enumeration_value(schema);
-            let docs = if external {
-                self.validate_schema_docs(schema)
-            } else {
-                Vec::new()
-            };
-            subs.into_iter()
-                .chain(properties)
-                .chain(enum_values)
-                .chain(docs)
+                .at(Location::from_pointer(&extension.pointer))
+            })
+            .collect()
+    });
+
+    let (flagged, preferred) = match config.singular_examples {
+        false => ("example", "examples"),
+        true => ("examples", "example"),
+    };
+    let examples = document
+        .examples
+        .into_iter()
+        .filter(|pointer| pointer.ends_with(&format!("/{}", flagged)))
+        .map(|pointer| {
+            let diagnostic = Diagnostic::new(
+                &lints::EXAMPLE_STYLE,
+                format!(
+                    "The \"{}\" field at {} should be given as \"{}\", so that \
+                    documentation has a single source of examples.",
+                    flagged, pointer, preferred,
+                ),
+            )
+            .at(Location::from_pointer(&pointer));
+            // Only a singular example can be migrated mechanically.
+            match config.singular_examples {
+                false => diagnostic.with_suggestion(Suggestion::move_field(flagged, preferred)),
+                true => diagnostic,
+            }
         });
 
-        let paths = spec
-            .paths
+    config.apply(problems.chain(extensions).chain(examples).collect())
+}
+
+/// Parse the text of a spec as JSON and validate it, as with
+/// [`validate_document`] and [`validate_with_config`] together; a document
+/// that isn't JSON, or isn't an OpenAPI 3.0 (or 3.1) document, is reported as
+/// a problem rather than as an error.
+///
+/// ```
+/// let findings = openapi_lint::validate_json_str("{\"openapi\": ", &Default::default());
+/// assert_eq!(findings[0].code(), "document/invalid");
+/// ```
+pub fn validate_json_str(text: &str, config: &Config) -> Vec<Diagnostic> {
+    match serde_json::from_str(text) {
+        Ok(value) => validate_parsed(value, config, |_| serde_json::from_str(text)),
+        Err(err) => {
+            let message = format!("The document is not valid JSON: {}.", err);
+            let position = Position::new(err.line(), err.column());
+            config.apply(vec![invalid_document(message, Some(position))])
+        }
+    }
+}
+
+/// Like [`validate_json_str`], but for a spec in YAML (or JSON, which is also
+/// YAML); text that holds several documents is reported as a problem too.
+/// Requires the `yaml` feature.
+#[cfg(feature = "yaml")]
+pub fn validate_yaml_str(text: &str, config: &Config) -> Vec<Diagnostic> {
+    match parse_document(text) {
+        Ok(value) => validate_parsed(value, config, |_| serde_yaml::from_str(text)),
+        Err(err) => {
+            let position = match &err {
+                InputError::Parse(err) => err
+                    .location()
+                    .map(|location| Position::new(location.line(), location.column())),
+                _ => None,
+            };
+            let message = match &err {
+                InputError::Parse(source) => {
+                    format!("The document is invalid: {}: {}.", err, source)
+                }
+                _ => format!("The document is invalid: {}.", err),
+            };
+            config.apply(vec![invalid_document(message, position)])
+        }
+    }
+}
+
+/// Like [`validate_json_str`], but for a spec (in JSON or, with the `yaml`
+/// feature, YAML) read from `reader`; only a failure to read it is an error.
+pub fn validate_reader<R: std::io::Read>(
+    mut reader: R,
+    config: &Config,
+) -> std::io::Result<Vec<Diagnostic>> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    #[cfg(feature = "yaml")]
+    return Ok(validate_yaml_str(&text, config));
+    #[cfg(not(feature = "yaml"))]
+    Ok(validate_json_str(&text, config))
+}
+
+/// Validate a spec held as a JSON value (e.g. one that a test harness has just
+/// generated) with the default settings, as [`validate_json_str`] validates
+/// its text.
+///
+/// ```
+/// let value = serde_json::json!({
+///     "openapi": "3.0.3",
+///     "info": { "title": "widgets", "version": "1.0.0" },
+///     "paths": {},
+/// });
+/// assert!(openapi_lint::validate_value(&value).is_empty());
+/// ```
+pub fn validate_value(value: &serde_json::Value) -> Vec<Diagnostic> {
+    validate_value_with_config(value, &Config::default())
+}
+
+/// Like [`validate_value`], with the given settings.
+pub fn validate_value_with_config(value: &serde_json::Value, config: &Config) -> Vec<Diagnostic> {
+    validate_parsed(value.clone(), config, |value| {
+        <OpenAPI as serde::Deserialize>::deserialize(value)
+    })
+}
+
+/// Validate a parsed document: the checks of the raw document, and, if it
+/// is (or converts to) an OpenAPI 3.0 document, the rest. The spec is
+/// deserialized with `deserialize` (e.g. from the text, which, unlike a
+/// `serde_json::Value`, keeps the order of its maps) unless it was converted.
+fn validate_parsed<F, E>(
+    mut value: serde_json::Value,
+    config: &Config,
+    deserialize: F,
+) -> Vec<Diagnostic>
+where
+    F: FnOnce(&serde_json::Value) -> Result<OpenAPI, E>,
+    E: std::fmt::Display,
+{
+    let converted = convert_3_1(&mut value);
+    let mut findings = validate_document(&value, config);
+    let spec = match converted {
+        true => serde_json::from_value::<OpenAPI>(value).map_err(|err| err.to_string()),
+        false => deserialize(&value).map_err(|err| err.to_string()),
+    };
+    match spec {
+        Ok(spec) => findings.extend(validate_with_config(&spec, config)),
+        // As with the CLI, a document whose schemas have yet to be converted
+        // gets only the document checks.
+        Err(_) if converted || config.draft_2020_12 => {}
+        Err(err) => findings.extend(config.apply(vec![invalid_document(
+            format!("The document is not a valid OpenAPI document: {}.", err),
+            None,
+        )])),
+    }
+    findings.truncate(config.max_findings.unwrap_or(usize::MAX));
+    findings
+}
+
+fn invalid_document(message: String, position: Option<Position>) -> Diagnostic {
+    let mut location = Location::document();
+    location.position = position;
+    Diagnostic::new(&lints::INVALID_DOCUMENT, message).at(location)
+}
+
+/// Describe the schema containing the given JSON pointer for use at the start
+/// of a message, naming it if it is (within) a component schema.
+fn containing_schema(pointer: &str) -> String {
+    pointer
+        .strip_prefix("/components/schemas/")
+        .and_then(|rest| rest.split('/').next())
+        .map(|name| format!("The schema {}", name.replace("~1", "/").replace("~0", "~")))
+        .unwrap_or_else(|| "A schema".to_string())
+}
+
+/// Every schema in the spec, attributed to the operation within which it
+/// appears or to the component (if it's a named schema).
+pub(crate) fn located_schemas(
+    spec: &OpenAPI,
+) -> impl Iterator<Item = (Option<&str>, &Schema, Location)> + '_ {
+    let path_schemas = spec.paths.iter().flat_map(|(path, path_item)| {
+        path_item.as_item().into_iter().flat_map(move |path_item| {
+            let operations = path_item.iter().flat_map(move |(method, op)| {
+                let location = Location::operation(path, method, op);
+                SchemaWalker::walk(op).map(move |walked| {
+                    let location = location.within(&walked.pointer);
+                    (walked.component, walked.schema, location)
+                })
+            });
+            let parameters = path_item
+                .parameters
+                .iter()
+                .enumerate()
+                .flat_map(move |(i, param)| {
+                    let location = Location::path(path).within(&format!("/parameters/{}", i));
+                    param.walk().map(move |walked| {
+                        let location = location.within(&walked.pointer);
+                        (walked.component, walked.schema, location)
+                    })
+                });
+            operations.chain(parameters)
+        })
+    });
+    let component_schemas = spec.components.walk().map(|walked| {
+        let location = Location::from_pointer(&format!("/components{}", walked.pointer));
+        (walked.component, walked.schema, location)
+    });
+
+    path_schemas.chain(component_schemas)
+}
+
+/// A rule (or a group of related rules) applied to the spec as a whole. The
+/// rules for individual paths, operations, schemas, and component schemas are
+/// applied separately, in a single pass over the spec; see [`Findings`]. Each
+/// of these instead makes its own way through the spec (some, such as
+/// [`Rules::validate_references`], by serializing it), as they compare parts
+/// of it with one another; only the single pass is shared.
+type Rule = fn(&Rules, &OpenAPI) -> Vec<Diagnostic>;
+
+/// A rule applied to each path, given its template; what it finds is
+/// attributed to the path.
+type PathRule = fn(&Rules, &str) -> Vec<Diagnostic>;
+
+/// A rule applied to each operation; what it finds is attributed to the
+/// operation.
+type OperationRule =
+    for<'s> fn(&Rules, &'s OpenAPI, (&'s str, &'s str, &'s Operation)) -> Vec<Diagnostic>;
+
+/// A rule applied to each component schema, given its name; what it finds is
+/// attributed to the component.
+type ComponentRule = fn(&Rules, &str, &ReferenceOr<Schema>) -> Option<Diagnostic>;
+
+/// The rules for the spec as a whole, in the order in which they are applied,
+/// after those for its parts.
+const RULES: &[Rule] = &[
+    |rules, spec| rules.validate_operation_id_collisions(spec),
+    |rules, spec| rules.validate_tags(spec),
+    |rules, spec| rules.validate_resource_tags(spec),
+    |rules, spec| rules.validate_components_order(spec),
+    |rules, spec| {
+        rules
+            .validate_anonymous_types(spec)
+            .map(|diagnostic| diagnostic.at(Location::document()))
+            .into_iter()
+            .collect()
+    },
+    |rules, spec| {
+        rules
+            .validate_spec_size(spec)
+            .map(|diagnostic| diagnostic.at(Location::document()))
+            .into_iter()
+            .collect()
+    },
+    |rules, spec| rules.validate_references(spec),
+    |rules, spec| rules.validate_required_extensions(spec),
+    |rules, spec| rules.validate_shared_request_bodies(spec),
+    |rules, spec| rules.validate_nullable_required(spec),
+    |rules, spec| rules.validate_duplicate_enums(spec),
+    |rules, spec| rules.validate_response_urls(spec),
+];
+
+/// The rules for each path, in the order in which they are applied.
+const PATH_RULES: &[PathRule] = &[
+    |rules, path| rules.validate_path(path).into_iter().collect(),
+    |rules, path| rules.validate_path_prefix(path).into_iter().collect(),
+    |rules, path| rules.validate_path_parameter_names(path),
+];
+
+/// The rules for each operation, in the order in which they are applied.
+const OPERATION_RULES: &[OperationRule] = &[
+    |rules, _, path_method_op| {
+        rules
+            .validate_operation_id(path_method_op)
+            .into_iter()
+            .collect()
+    },
+    |rules, _, path_method_op| {
+        rules
+            .validate_operation_id_path(path_method_op)
+            .into_iter()
+            .collect()
+    },
+    |rules, _, path_method_op| rules.validate_method(path_method_op).into_iter().collect(),
+    |rules, spec, path_method_op| rules.validate_update(spec, path_method_op),
+    |rules, spec, (_, _, op)| rules.validate_operation_parameters(spec, op),
+    |rules, spec, (_, _, op)| rules.validate_operation_response(spec, op),
+    |rules, spec, (path, _, op)| rules.validate_mutually_exclusive(spec, path, op),
+    |rules, spec, (path, _, op)| rules.validate_unique_parameters(spec, path, op),
+    |rules, spec, (path, _, op)| rules.validate_body_parameters(spec, path, op),
+    |rules, spec, (path, _, op)| rules.validate_query_arrays(spec, path, op),
+    |rules, spec, (path, _, op)| rules.validate_path_parameter_style(spec, path, op),
+    |rules, spec, (_, _, op)| rules.validate_request_body_union(spec, op),
+    |rules, spec, (_, _, op)| rules.validate_nullable_request_body(spec, op),
+    |rules, spec, path_method_op| rules.validate_success_content(spec, path_method_op),
+    |rules, spec, (_, _, op)| rules.validate_binary_encoding(spec, op),
+    |rules, _, (_, _, op)| rules.validate_redirects(op),
+    |rules, spec, (_, _, op)| rules.validate_retry_after(spec, op),
+    |rules, spec, path_method_op| rules.validate_location(spec, path_method_op),
+    |rules, spec, path_method_op| {
+        rules
+            .validate_idempotency_key(spec, path_method_op)
+            .into_iter()
+            .collect()
+    },
+    |rules, _, (_, _, op)| {
+        if !rules.config.external {
+            return Vec::new();
+        }
+        op.description
             .iter()
-            .filter_map(|(path, _)| self.validate_path(path));
-        let operations = spec
-            .operations()
-            .filter_map(|path_method_op| self.validate_operation_id(path_method_op));
-        let parameters = spec
-            .operations()
-            .flat_map(|(_, _, op)| self.validate_operation_parameters(spec, op));
-        let responses = spec
-            .operations()
-            .flat_map(|(_, _, op)| self.validate_operation_response(spec, op));
-        let op_docs = if external {
-            spec.operations()
-                .flat_map(|(_, _, op)| op.description.as_ref().and_then(|s| check_doc_string(s)))
-                .collect()
+            .flat_map(|s| {
+                check_doc_string(s)
+                    .into_iter()
+                    .chain(rules.check_html(s))
+                    .chain(rules.check_description_style(s))
+            })
+            .collect()
+    },
+];
+
+/// The rules for each component schema, in the order in which they are
+/// applied.
+const COMPONENT_RULES: &[ComponentRule] = &[
+    |rules, type_name, _| rules.validate_named_schema(type_name),
+    |rules, type_name, schema| rules.validate_schema_title(type_name, schema.as_item()?),
+    |rules, type_name, schema| rules.validate_schema_title_text(type_name, schema.as_item()?),
+    |rules, type_name, _| rules.validate_singular_name(type_name),
+];
+
+/// The problems in a spec, found as they are asked for: in a single pass over
+/// the spec, schema by schema, path by path (with each operation of the path
+/// in turn), and component by component, applying every rule for each; then
+/// rule by rule for the spec as a whole, each of which looks through the spec
+/// on its own; and then by the checks defined outside this crate, so that a
+/// caller that stops early doesn't pay for the rest.
+struct Findings<'a> {
+    rules: Rules<'a>,
+    spec: &'a OpenAPI,
+    schemas: Box<dyn Iterator<Item = (Option<&'a str>, &'a Schema, Location)> + 'a>,
+    paths: indexmap::map::Iter<'a, String, ReferenceOr<PathItem>>,
+    /// The problems that operations found with the components that they use,
+    /// by code, pointer, and message, which are reported once the paths have
+    /// been checked, each with every operation that found it.
+    shared: IndexMap<(&'static str, Option<String>, String), Diagnostic>,
+    components: Box<dyn Iterator<Item = (&'a String, &'a ReferenceOr<Schema>)> + 'a>,
+    builtin: std::slice::Iter<'static, Rule>,
+    custom: std::slice::Iter<'a, Arc<dyn Check>>,
+    unused_waivers: bool,
+    pending: std::vec::IntoIter<Diagnostic>,
+}
+
+impl<'a> Findings<'a> {
+    fn new(spec: &'a OpenAPI, config: &'a Config) -> Self {
+        Findings {
+            rules: Rules {
+                config,
+                skipped: RefCell::default(),
+                waivers: Arc::new(Waivers::collect(spec)),
+                resolved: RefCell::default(),
+            },
+            spec,
+            schemas: Box::new(located_schemas(spec)),
+            paths: spec.paths.iter(),
+            shared: IndexMap::new(),
+            components: Box::new(spec.components.iter().flat_map(|c| &c.schemas)),
+            builtin: RULES.iter(),
+            custom: config.checks.0.iter(),
+            unused_waivers: false,
+            pending: Vec::new().into_iter(),
+        }
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl Findings<'_> {
+    /// Apply the rules for individual schemas to every schema at once, across
+    /// threads, leaving what they find to be reported in the same order (and
+    /// with the same skipped constructs noted) as if they had been applied
+    /// one schema at a time.
+    fn validate_schemas_in_parallel(&mut self) {
+        use rayon::prelude::*;
+
+        let schemas = self.schemas.by_ref().collect::<Vec<_>>();
+        let (spec, config, waivers) = (self.spec, self.rules.config, &self.rules.waivers);
+        let results = schemas
+            .into_par_iter()
+            .map(|(name, schema, location)| {
+                // Each schema notes what it skips separately, but the uses of
+                // waivers are shared.
+                let rules = Rules {
+                    config,
+                    skipped: RefCell::default(),
+                    waivers: Arc::clone(waivers),
+                    resolved: RefCell::default(),
+                };
+                let found = rules.validate_schema(spec, name, schema, &location);
+                (found, rules.skipped.into_inner())
+            })
+            .collect::<Vec<_>>();
+
+        let mut pending = std::mem::take(&mut self.pending).collect::<Vec<_>>();
+        for (found, skipped) in results {
+            pending.extend(found);
+            for Skipped { location, reason } in skipped {
+                self.rules.skip(location, reason);
+            }
+        }
+        self.pending = pending.into_iter();
+    }
+}
+
+impl Iterator for Findings<'_> {
+    type Item = Diagnostic;
+
+    fn next(&mut self) -> Option<Diagnostic> {
+        loop {
+            for finding in self.pending.by_ref() {
+                // Unused waivers are found last, and can't themselves be
+                // waived.
+                if self.unused_waivers || !self.rules.waivers.waived(&finding) {
+                    if let Some(finding) = self.rules.config.adjust(finding) {
+                        return Some(finding);
+                    }
+                }
+            }
+
+            let rules = &self.rules;
+            self.pending = if let Some((name, schema, location)) = self.schemas.next() {
+                rules.validate_schema(self.spec, name, schema, &location)
+            } else if let Some((path, path_item)) = self.paths.next() {
+                let (shared, found) = rules
+                    .validate_path_item(self.spec, path, path_item)
+                    .into_iter()
+                    .partition::<Vec<_>, _>(|finding| !finding.referenced_from.is_empty());
+                for finding in shared {
+                    let key = (
+                        finding.code(),
+                        finding.location.pointer.clone(),
+                        finding.message.clone(),
+                    );
+                    match self.shared.entry(key) {
+                        indexmap::map::Entry::Occupied(mut entry) => entry
+                            .get_mut()
+                            .referenced_from
+                            .extend(finding.referenced_from),
+                        indexmap::map::Entry::Vacant(entry) => {
+                            entry.insert(finding);
+                        }
+                    }
+                }
+                found
+            } else if !self.shared.is_empty() {
+                std::mem::take(&mut self.shared).into_values().collect()
+            } else if let Some((type_name, schema)) = self.components.next() {
+                let location = Location::named("schemas", type_name);
+                COMPONENT_RULES
+                    .iter()
+                    .filter_map(|rule| rule(rules, type_name, schema))
+                    .map(|diagnostic| diagnostic.at(location.clone()))
+                    .collect()
+            } else if let Some(rule) = self.builtin.next() {
+                rule(rules, self.spec)
+            } else if let Some(check) = self.custom.next() {
+                let mut cx = Context::new(self.spec, check.lint());
+                check.check(&mut cx);
+                cx.into_findings()
+            } else if !self.unused_waivers {
+                self.unused_waivers = true;
+                rules.waivers.unused(rules.config)
+            } else {
+                return None;
+            }
+            .into_iter();
+        }
+    }
+}
+
+/// The rules, applied with a particular configuration.
+struct Rules<'a> {
+    config: &'a Config,
+    skipped: RefCell<Vec<Skipped>>,
+    waivers: Arc<Waivers<'a>>,
+    /// Where each reference that has been followed led, by section of the
+    /// components: the index of the component, if there is one.
+    resolved: RefCell<BTreeMap<&'static str, BTreeMap<String, Option<usize>>>>,
+}
+
+impl Rules<'_> {
+    /// The item or the component to which it refers, as for
+    /// [`ReferenceOrExt::item`], following each reference only once however
+    /// often the rules come across it.
+    fn item<'s, T: ComponentLookup>(
+        &self,
+        components: &'s Option<Components>,
+        item: &'s impl ReferenceOrExt<T>,
+    ) -> Option<&'s T> {
+        let reference = match item.split() {
+            Ok(item) => return Some(item),
+            Err(reference) => reference,
+        };
+        let items = T::get_components(components.as_ref()?);
+        let mut resolved = self.resolved.borrow_mut();
+        let section = resolved.entry(T::SECTION).or_default();
+        let index = match section.get(reference) {
+            Some(index) => *index,
+            None => *section
+                .entry(reference.to_string())
+                .or_insert(resolve_index(reference, items)),
+        };
+        items.get_index(index?)?.1.as_item()
+    }
+
+    /// The name that `name` should be renamed to, unless it follows the
+    /// naming convention for the given target or is exempt from it.
+    fn suggest_rename(&self, name: &str, target: Target) -> Option<String> {
+        if self.config.allows_name(name) {
+            return None;
+        }
+        match self.config.cases.get(&target) {
+            Some(case) => (!case.is(name)).then(|| case.convert(name)),
+            None => naming::suggest_rename(name, target),
+        }
+    }
+
+    /// The name that `name` should be renamed to if it has leading, trailing,
+    /// or repeated underscores, unless it is exempt from naming conventions.
+    fn suggest_trim(&self, name: &str, target: Target) -> Option<String> {
+        if self.config.allows_name(name) {
+            return None;
+        }
+        let trimmed = naming::trim_underscores(name)?;
+        Some(self.suggest_rename(&trimmed, target).unwrap_or(trimmed))
+    }
+
+    /// The case convention for names of the given kind.
+    fn case(&self, target: Target) -> Case {
+        self.config.case(target)
+    }
+
+    /// Note that a rule bailed on a construct at the given location.
+    fn skip(&self, location: String, reason: String) {
+        let skipped = Skipped { location, reason };
+        let mut all = self.skipped.borrow_mut();
+        // Nested schemas are visited both on their own and as part of their
+        // parents; only note each once.
+        if !all.contains(&skipped) {
+            all.push(skipped);
+        }
+    }
+
+    /// Apply the rules for individual schemas to one schema.
+    fn validate_schema(
+        &self,
+        spec: &OpenAPI,
+        name: Option<&str>,
+        schema: &Schema,
+        location: &Location,
+    ) -> Vec<Diagnostic> {
+        let tags = self.validate_enum_tags(spec, name, schema);
+        let unique_tags = self.validate_enum_tag_uniqueness(spec, name, schema);
+        let enum_type = self.validate_enumeration_type(name, schema);
+        let nullable_union = self.validate_nullable_union(name, schema);
+        let subs = self.validate_subschemas(spec, name, schema);
+        let properties = self.validate_object(schema);
+        let enum_values = self.validate_enumeration_value(schema);
+        let format = self.validate_format_case(schema);
+        let discriminator = self.validate_discriminator(schema);
+        let docs = if self.config.external {
+            self.validate_schema_docs(schema)
         } else {
             Vec::new()
         };
-        let named_schemas = spec.components.iter().flat_map(|components| {
-            components
-                .schemas
-                .keys()
-                .filter_map(|type_name| self.validate_named_schema(type_name))
-        });
-
-        schema
-            .chain(paths)
-            .chain(operations)
-            .chain(parameters)
-            .chain(responses)
-            .chain(named_schemas)
-            .chain(op_docs)
+        // Most problems are with the schema as a whole; those with a property
+        // or a value are placed there.
+        subs.into_iter()
+            .chain(tags)
+            .chain(unique_tags)
+            .chain(enum_type)
+            .chain(nullable_union)
+            .chain(docs)
+            .map(|diagnostic| (String::new(), diagnostic))
+            .chain(properties)
+            .chain(enum_values)
+            .chain(format)
+            .chain(discriminator)
+            .filter(|(_, diagnostic)| {
+                !self
+                    .waivers
+                    .waives(&schema.schema_data.extensions, diagnostic)
+            })
+            .map(|(pointer, diagnostic)| diagnostic.at(location.within(&pointer)))
             .collect()
     }
 
-    fn validate_subschemas(&self, spec: &OpenAPI, schema: &Schema) -> Option<String> {
-        let subschemas = self.subschemas(spec, schema);
+    /// Apply the rules for a path, and for each of its operations.
+    fn validate_path_item(
+        &self,
+        spec: &OpenAPI,
+        path: &str,
+        path_item: &ReferenceOr<PathItem>,
+    ) -> Vec<Diagnostic> {
+        let location = Location::path(path);
+        let mut ret = PATH_RULES
+            .iter()
+            .flat_map(|rule| rule(self, path))
+            .map(|diagnostic| diagnostic.at(location.clone()))
+            .collect::<Vec<_>>();
+        for (method, op) in path_item.as_item().into_iter().flat_map(PathItem::iter) {
+            let location = Location::operation(path, method, op);
+            ret.extend(
+                OPERATION_RULES
+                    .iter()
+                    .flat_map(|rule| rule(self, spec, (path, method, op)))
+                    .map(|diagnostic| match diagnostic.location.component {
+                        // A problem with a component that the operation uses
+                        // is placed at the component.
+                        Some(_) => diagnostic.used_by(location.clone()),
+                        None => diagnostic.at(location.clone()),
+                    }),
+            );
+        }
+        ret
+    }
+
+    fn validate_subschemas(
+        &self,
+        spec: &OpenAPI,
+        name: Option<&str>,
+        schema: &Schema,
+    ) -> Option<Diagnostic> {
+        let subschemas = self.subschemas(spec, name, schema, &mut Vec::new());
         let mut iter = subschemas.into_iter();
 
         const PRE: &str = "Mismatched types between subschemas; this is often \
             due to enums with different data payloads and can be resolved \
             using serde adjacent tagging.";
-        const POST: &str = "For more info, see \
-            https://github.com/oxidecomputer/openapi-lint#type-mismatch";
 
         if let Some(first) = iter.next() {
             for ty in iter {
@@ -114,10 +1009,24 @@ impl Validator {
                     | (Type::Array(_), Type::Array(_))
                     | (Type::Boolean(_), Type::Boolean(_)) => {}
                     (a, b) => {
-                        return Some(format!(
-                            "{}\nthis schema's type\n{:#?}\ndiffers from this\n{:#?}\n\n{}",
-                            PRE, a, b, POST,
-                        ))
+                        return Some(
+                            Diagnostic::new(
+                                &lints::TYPE_MISMATCH,
+                                format!(
+                                    "Problem with type {}: {}\nthis schema's type, {}, \
+                                    differs from this, {}",
+                                    name.unwrap_or("<unknown>"),
+                                    PRE,
+                                    excerpt(a),
+                                    excerpt(b),
+                                ),
+                            )
+                            .with_detail(format!(
+                                "this schema's type\n{}\ndiffers from this\n{}",
+                                pretty(a),
+                                pretty(b),
+                            )),
+                        )
                     }
                 }
             }
@@ -126,38 +1035,140 @@ impl Validator {
         None
     }
 
-    fn subschemas<'a>(&self, spec: &'a OpenAPI, schema: &'a Schema) -> Vec<&'a Type> {
+    /// The types of the subschemas of a schema, flattening compositions.
+    /// `expanding` holds the references being followed, so that a
+    /// composition that refers back to itself ends rather than recursing
+    /// forever.
+    fn subschemas<'a>(
+        &self,
+        spec: &'a OpenAPI,
+        name: Option<&str>,
+        schema: &'a Schema,
+        expanding: &mut Vec<&'a str>,
+    ) -> Vec<&'a Type> {
+        let location = || format!("schema {}", name.unwrap_or("<unknown>"));
+
         match &schema.schema_kind {
             openapiv3::SchemaKind::OneOf { one_of: ofs }
             | openapiv3::SchemaKind::AllOf { all_of: ofs }
             | openapiv3::SchemaKind::AnyOf { any_of: ofs } => ofs
                 .iter()
                 .flat_map(|subschema| {
-                    self.subschemas(spec, subschema.item(&spec.components).unwrap())
+                    let reference = subschema.split().err();
+                    if let Some(reference) = reference {
+                        if expanding.contains(&reference) {
+                            self.skip(
+                                location(),
+                                format!(
+                                    "a subschema refers back to {} through its own \
+                                    subschemas, so the types of that cycle weren't \
+                                    compared",
+                                    reference,
+                                ),
+                            );
+                            return Vec::new();
+                        }
+                    }
+                    // Unresolved references are reported on their own.
+                    let Some(subschema) = self.item(&spec.components, subschema) else {
+                        return Vec::new();
+                    };
+                    match &subschema.schema_kind {
+                        SchemaKind::Any(any) if is_permissive(any) => self.skip(
+                            location(),
+                            "a subschema accepts any value, so its type wasn't \
+                            compared with those of the other subschemas"
+                                .to_string(),
+                        ),
+                        SchemaKind::Not { .. } => self.skip(
+                            location(),
+                            "a subschema is a 'not' schema, which has no type of its \
+                            own, so it wasn't compared with the other subschemas"
+                                .to_string(),
+                        ),
+                        _ => {}
+                    }
+                    expanding.extend(reference);
+                    let types = self.subschemas(spec, name, subschema, expanding);
+                    if reference.is_some() {
+                        expanding.pop();
+                    }
+                    types
                 })
                 .collect(),
-            openapiv3::SchemaKind::Not { .. } => todo!("'not' subschemas aren't handled"),
+            // A 'not' schema excludes a type rather than having one.
+            openapiv3::SchemaKind::Not { .. } => vec![],
             openapiv3::SchemaKind::Type(t) => vec![t],
             openapiv3::SchemaKind::Any(any) if is_permissive(any) => vec![],
-            openapiv3::SchemaKind::Any(any) => todo!("complex 'any' schema not handled {:#?}", any),
+            // A schema that didn't deserialize as a well-formed type (e.g. an
+            // integer with string enum values) but that has no subschemas
+            // doesn't have a type we can compare.
+            openapiv3::SchemaKind::Any(AnySchema {
+                one_of,
+                all_of,
+                any_of,
+                not: None,
+                ..
+            }) if one_of.is_empty() && all_of.is_empty() && any_of.is_empty() => vec![],
+            openapiv3::SchemaKind::Any(_) => {
+                self.skip(
+                    location(),
+                    "a schema that combines its own type or constraints with \
+                    subschemas isn't handled, so its subschemas' types weren't \
+                    compared"
+                        .to_string(),
+                );
+                vec![]
+            }
         }
     }
 
-    fn validate_object(&self, schema: &Schema) -> Vec<String> {
+    /// The problems with an object's properties, each with a JSON pointer to
+    /// the property relative to the object (or an empty one, for a problem
+    /// with the object as a whole).
+    fn validate_object(&self, schema: &Schema) -> Vec<(String, Diagnostic)> {
         let mut ret = Vec::new();
 
         if let openapiv3::SchemaKind::Type(Type::Object(obj)) = &schema.schema_kind {
             for prop_name in obj.properties.keys() {
-                let snake = prop_name.to_snake_case();
-                if prop_name.clone() != snake {
-                    ret.push(format!(
-                        "An object contains a property '{}' which is not \
-                        snake_case:\n{:#?}\n\
-                        Add #[serde(rename = \"{}\")] to the member or \
-                        #[serde(rename_all = \"snake_case\")] to the struct.\n\
-                        For more info see \
-                        https://github.com/oxidecomputer/openapi-lint#naming",
-                        prop_name, schema, snake
+                if let Some(trimmed) = self.suggest_trim(prop_name, Target::Property) {
+                    ret.push((
+                        prop_name,
+                        Diagnostic::new(
+                            &lints::STRAY_UNDERSCORE,
+                            format!(
+                                "An object contains a property '{}' with stray \
+                                underscores; add #[serde(rename = \"{}\")] to the \
+                                member.",
+                                prop_name, trimmed,
+                            ),
+                        )
+                        .with_suggestion(Suggestion::rename(
+                            Target::Property,
+                            prop_name,
+                            &trimmed,
+                        )),
+                    ));
+                } else if let Some(snake) = self.suggest_rename(prop_name, Target::Property) {
+                    let case = self.case(Target::Property);
+                    ret.push((
+                        prop_name,
+                        Diagnostic::new(
+                            &lints::PROPERTY_NAME,
+                            format!(
+                                "An object contains a property '{}' which is not \
+                                {}: {}\n\
+                                Add #[serde(rename = \"{}\")] to the member or \
+                                #[serde(rename_all = \"{}\")] to the struct.",
+                                prop_name,
+                                case,
+                                excerpt(schema),
+                                snake,
+                                case,
+                            ),
+                        )
+                        .with_detail(pretty(schema))
+                        .with_suggestion(Suggestion::rename(Target::Property, prop_name, &snake)),
                     ))
                 }
             }
@@ -175,280 +1186,4737 @@ impl Validator {
                                     max_length: None,
                                 })),
                             ..
-                        }) if format == "uuid" && enumeration.is_empty() => ret.push(format!(
-                            "An object contains a property '{}' that is a \
-                            uuid and redundantly ends with `_uuid`'; rename \
-                            this property to `{}_id`.\n\
-                            For more info see \
-                            https://github.com/oxidecomputer/openapi-lint#uuids",
-                            prop_name,
-                            prop_name.trim_end_matches("_uuid"),
-                        )),
+                        }) if format == "uuid" && enumeration.is_empty() => {
+                            let renamed = format!("{}_id", prop_name.trim_end_matches("_uuid"));
+                            ret.push((
+                                prop_name,
+                                Diagnostic::new(
+                                    &lints::UUID_SUFFIX,
+                                    format!(
+                                        "An object contains a property '{}' that is a \
+                                        uuid and redundantly ends with `_uuid`'; rename \
+                                        this property to `{}`.",
+                                        prop_name, renamed,
+                                    ),
+                                )
+                                .with_suggestion(
+                                    Suggestion::rename(Target::Property, prop_name, &renamed),
+                                ),
+                            ))
+                        }
                         _ => (),
                     }
                 }
             }
+
+            for (prop_name, prop_schema) in obj.properties.iter() {
+                let Some(prop) = prop_schema
+                    .as_item()
+                    .filter(|prop| is_free_form_string(prop))
+                else {
+                    continue;
+                };
+                let format = string_format(prop);
+                let problem = if prop_name == "name" && format == Some("uuid") {
+                    "is a uuid; a name is human-readable, so name the property `id` \
+                    (or `*_id`) or make it a Name"
+                } else if (prop_name == "id" || prop_name.ends_with("_id")) && format.is_none() {
+                    "may be any string; an identifier should be a uuid (with format \
+                    uuid) or a Name, or the property named for what it is"
+                } else {
+                    continue;
+                };
+                ret.push((
+                    prop_name,
+                    Diagnostic::new(
+                        &lints::IDENTIFIER_FORMAT,
+                        format!(
+                            "An object contains a property '{}' that {}.",
+                            prop_name, problem,
+                        ),
+                    ),
+                ));
+            }
+
+            if !self.config.nullable_collections {
+                for (prop_name, prop_schema) in obj.properties.iter() {
+                    let Some(prop) = prop_schema.as_item() else {
+                        continue;
+                    };
+                    let kind = match &prop.schema_kind {
+                        _ if !prop.schema_data.nullable => continue,
+                        SchemaKind::Type(Type::Array(_)) => "an array",
+                        SchemaKind::Type(Type::Object(ObjectType {
+                            properties,
+                            additional_properties: Some(_),
+                            ..
+                        })) if properties.is_empty() => "a map",
+                        _ => continue,
+                    };
+                    ret.push((
+                        prop_name,
+                        Diagnostic::new(
+                            &lints::NULLABLE_COLLECTION,
+                            format!(
+                                "The property '{}' is {} that is nullable; make it \
+                                non-nullable and use an empty collection instead.",
+                                prop_name, kind,
+                            ),
+                        ),
+                    ));
+                }
+            }
+
+            for prop_name in &obj.required {
+                let has_default = obj
+                    .properties
+                    .get(prop_name)
+                    .and_then(ReferenceOr::as_item)
+                    .is_some_and(|prop| prop.schema_data.default.is_some());
+                if has_default {
+                    ret.push((
+                        prop_name,
+                        Diagnostic::new(
+                            &lints::REQUIRED_DEFAULT,
+                            format!(
+                                "The property '{}' is required but has a default; \
+                                make it optional or remove the default.",
+                                prop_name,
+                            ),
+                        ),
+                    ));
+                }
+            }
+
+            // A property may waive the checks that concern it.
+            ret.retain(|(prop_name, diagnostic)| {
+                !obj.properties[*prop_name].as_item().is_some_and(|prop| {
+                    self.waivers
+                        .waives(&prop.schema_data.extensions, diagnostic)
+                })
+            });
+        }
+
+        let mut ret = ret
+            .into_iter()
+            .map(|(prop_name, diagnostic)| {
+                (
+                    format!("/properties/{}", fields::escape(prop_name)),
+                    diagnostic,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        if let SchemaKind::Type(Type::Object(ObjectType {
+            properties,
+            additional_properties: Some(AdditionalProperties::Schema(_)),
+            ..
+        })) = &schema.schema_kind
+        {
+            if !properties.is_empty() {
+                ret.push((
+                    String::new(),
+                    Diagnostic::new(
+                        &lints::MIXED_OBJECT,
+                        format!(
+                            "An object has both properties ({}) and an \
+                            additionalProperties schema; make the map a property \
+                            of its own.",
+                            properties.keys().cloned().collect::<Vec<_>>().join(", "),
+                        ),
+                    ),
+                ));
+            }
         }
 
         ret
     }
 
-    fn validate_enumeration_value(&self, schema: &Schema) -> Vec<String> {
+    /// The problems with the values of an enumerated string, each with a JSON
+    /// pointer to the value relative to the schema.
+    fn validate_enumeration_value(&self, schema: &Schema) -> Vec<(String, Diagnostic)> {
         let mut ret = Vec::new();
 
         if let openapiv3::SchemaKind::Type(Type::String(StringType { enumeration, .. })) =
             &schema.schema_kind
         {
-            enumeration.iter().for_each(|enum_value| {
-                if let Some(label) = enum_value {
-                    let lower = label.to_snake_case();
-                    let upper = label.to_shouty_snake_case();
-                    if label != &lower && label != &upper {
-                        ret.push(format!(
-                            "An enumerated string contains a value '{}' that \
-                            is neither snake_case nor \
-                            SCREAMING_SNAKE_CASE:\n{:#?}\n\
-                            Add #[serde(rename = \"{}\")] to the variant or \
-                            #[serde(rename_all = \"snake_case\")] to the enum.\n\
-                            For more info see \
-                            https://github.com/oxidecomputer/openapi-lint#naming",
-                            label, schema, lower
-                        ));
+            enumeration
+                .iter()
+                .enumerate()
+                .for_each(|(index, enum_value)| {
+                    if let Some(label) = enum_value {
+                        if let Some(lower) = self.suggest_rename(label, Target::EnumValue) {
+                            let case = self.case(Target::EnumValue);
+                            let convention = match self.config.cases.get(&Target::EnumValue) {
+                                Some(case) => format!("is not {}", case),
+                                None => {
+                                    "is neither snake_case nor SCREAMING_SNAKE_CASE".to_string()
+                                }
+                            };
+                            ret.push((
+                                format!("/enum/{}", index),
+                                Diagnostic::new(
+                                    &lints::ENUM_VALUE_NAME,
+                                    format!(
+                                        "An enumerated string contains a value '{}' that \
+                                        {}: {}\n\
+                                        Add #[serde(rename = \"{}\")] to the variant or \
+                                        #[serde(rename_all = \"{}\")] to the enum.",
+                                        label,
+                                        convention,
+                                        excerpt(schema),
+                                        lower,
+                                        case,
+                                    ),
+                                )
+                                .with_detail(pretty(schema))
+                                .with_suggestion(
+                                    Suggestion::rename(Target::EnumValue, label, &lower),
+                                ),
+                            ));
+                        }
                     }
-                }
-            });
+                });
         }
         ret
     }
 
-    fn validate_path(&self, path: &str) -> Option<String> {
-        const INFO: &str = "For more info, see \
-            https://github.com/oxidecomputer/openapi-lint#paths";
+    /// The problem with the format of a schema if it is a miscased or
+    /// misspelled form of a well-known format, with a JSON pointer to the
+    /// format relative to the schema.
+    fn validate_format_case(&self, schema: &Schema) -> Option<(String, Diagnostic)> {
+        let format = unrecognized_format(schema)?;
+        let canonical = canonical_format(format)?;
+        Some((
+            "/format".to_string(),
+            Diagnostic::new(
+                &lints::FORMAT_CASE,
+                format!(
+                    "The format \"{}\" is not spelled as the well-known format \
+                    \"{}\", so generators that match formats exactly will ignore \
+                    it; use \"{}\" instead.",
+                    format, canonical, canonical,
+                ),
+            )
+            .with_suggestion(Suggestion::replace(format, canonical)),
+        ))
+    }
 
-        path.split('/')
-            .any(|component| {
-                if component.starts_with('{') {
-                    false
-                } else {
-                    component != component.to_kebab_case()
-                }
-            })
-            .then(|| format!("The path {} doesn't use kebab-case\n{}", path, INFO,))
+    fn validate_discriminator(&self, schema: &Schema) -> Option<(String, Diagnostic)> {
+        let discriminator = schema.schema_data.discriminator.as_ref()?;
+        let kind = match &schema.schema_kind {
+            SchemaKind::OneOf { .. } | SchemaKind::AnyOf { .. } => return None,
+            SchemaKind::AllOf { .. } => "an allOf hierarchy",
+            SchemaKind::Type(Type::Object(_)) => "a plain object",
+            _ => "a schema without oneOf or anyOf",
+        };
+        Some((
+            "/discriminator".to_string(),
+            Diagnostic::new(
+                &lints::DISCRIMINATOR,
+                format!(
+                    "The discriminator on the property \"{}\" is declared on {}, \
+                    which generators don't support; use an internally tagged enum \
+                    (#[serde(tag = \"{}\")]), which produces a oneOf of variants: {}",
+                    discriminator.property_name,
+                    kind,
+                    discriminator.property_name,
+                    excerpt(schema),
+                ),
+            )
+            .with_detail(pretty(schema)),
+        ))
     }
 
-    fn validate_operation_id(&self, path_method_op: (&str, &str, &Operation)) -> Option<String> {
-        let (path, method, op) = path_method_op;
+    fn validate_enum_tags(
+        &self,
+        spec: &OpenAPI,
+        name: Option<&str>,
+        schema: &Schema,
+    ) -> Option<Diagnostic> {
+        let tags = enum_variant_tags(spec, schema)?;
 
-        const INFO: &str = "For more info, see \
-            https://github.com/oxidecomputer/openapi-lint#naming";
+        let snake = tags
+            .iter()
+            .filter(|tag| tag.to_snake_case() == **tag && tag.to_pascal_case() != **tag)
+            .copied()
+            .collect::<Vec<_>>();
+        let pascal = tags
+            .iter()
+            .filter(|tag| tag.to_pascal_case() == **tag && tag.to_snake_case() != **tag)
+            .copied()
+            .collect::<Vec<_>>();
 
-        if let Some(operation_id) = &op.operation_id {
-            let snake = operation_id.to_snake_case();
-            if operation_id.as_str() == snake {
-                return None;
+        (!snake.is_empty() && !pascal.is_empty()).then(|| {
+            Diagnostic::new(
+                &lints::ENUM_TAG_CONSISTENCY,
+                format!(
+                    "The enum {} has variants with snake_case tags ({}) and \
+                    variants with PascalCase tags ({}); this is usually due to a \
+                    missing #[serde(rename_all = \"snake_case\")] on the enum or \
+                    on one of the types in its family.",
+                    name.unwrap_or("<unknown>"),
+                    snake.join(", "),
+                    pascal.join(", "),
+                ),
+            )
+        })
+    }
+
+    fn validate_enum_tag_uniqueness(
+        &self,
+        spec: &OpenAPI,
+        name: Option<&str>,
+        schema: &Schema,
+    ) -> Vec<Diagnostic> {
+        let Some(tags) = enum_variant_tags(spec, schema) else {
+            return vec![];
+        };
+        let name = name.unwrap_or("<unknown>");
+
+        let mut seen = BTreeMap::<String, &str>::new();
+        let mut ret = Vec::new();
+        for tag in tags {
+            match seen.insert(tag.to_snake_case(), tag) {
+                None => {}
+                Some(previous) if previous == tag => ret.push(Diagnostic::new(
+                    &lints::ENUM_TAG_UNIQUENESS,
+                    format!(
+                        "The enum {} has multiple variants with the tag \"{}\"; \
+                        clients can't tell these variants apart.",
+                        name, tag,
+                    ),
+                )),
+                Some(previous) => ret.push(Diagnostic::new(
+                    &lints::ENUM_TAG_UNIQUENESS,
+                    format!(
+                        "The enum {} has variants with the tags \"{}\" and \"{}\" \
+                        which are the same when converted to snake_case; clients \
+                        that normalize tags can't tell these variants apart.",
+                        name, previous, tag,
+                    ),
+                )),
             }
-            Some(format!(
-                "The operation for {} {} is named \"{}\" which is not snake_case\n{}",
-                path, method, operation_id, INFO,
-            ))
-        } else {
-            Some(format!(
-                "The operation for {} {} does not have an operation_id\n{}",
-                path, method, INFO,
-            ))
         }
+        ret
     }
 
-    fn validate_operation_parameters(&self, spec: &OpenAPI, op: &Operation) -> Vec<String> {
-        const INFO: &str = "For more info, see \
-            https://github.com/oxidecomputer/openapi-lint#naming";
+    fn validate_nullable_union(&self, name: Option<&str>, schema: &Schema) -> Option<Diagnostic> {
+        if !schema.schema_data.nullable {
+            return None;
+        }
+        let union = match &schema.schema_kind {
+            SchemaKind::OneOf { .. } => "oneOf",
+            SchemaKind::AnyOf { .. } => "anyOf",
+            SchemaKind::Any(AnySchema { one_of, .. }) if !one_of.is_empty() => "oneOf",
+            SchemaKind::Any(AnySchema { any_of, .. }) if !any_of.is_empty() => "anyOf",
+            _ => return None,
+        };
+        Some(Diagnostic::new(
+            &lints::NULLABLE_UNION,
+            format!(
+                "The type {} is a nullable {} union, which SDK generators read \
+                differently: null may be another variant or may wrap the union. \
+                Add a variant for the null case, or make the property that uses \
+                the type optional instead.",
+                name.unwrap_or("<unknown>"),
+                union,
+            ),
+        ))
+    }
 
-        let operation_id = op.operation_id.as_deref().unwrap_or("<unknown>");
-        op.parameters
-            .iter()
-            .filter_map(|ref_or_param| {
-                let param = ref_or_param.item(&spec.components)?;
+    fn validate_enumeration_type(&self, name: Option<&str>, schema: &Schema) -> Option<Diagnostic> {
+        let name = name.unwrap_or("<unknown>");
 
-                let name = &param.parameter_data_ref().name;
-                let snake = name.to_snake_case();
+        let non_string = |typ: &str, has_values: bool| {
+            has_values.then(|| {
+                Diagnostic::new(
+                    &lints::NON_STRING_ENUM,
+                    format!(
+                        "The type {} is an enumeration of {} values; SDK generators \
+                        can't represent these as native enums. Use a string \
+                        enumeration or document the allowed values instead.",
+                        name, typ,
+                    ),
+                )
+            })
+        };
 
-                if name.as_str() != snake {
-                    Some(format!(
-                        "The parameter \"{}\" to {} should be snake_case.\n{}",
-                        name, operation_id, INFO,
-                    ))
-                } else {
-                    None
+        match &schema.schema_kind {
+            SchemaKind::Type(Type::Number(NumberType { enumeration, .. })) => {
+                non_string("number", enumeration.iter().any(Option::is_some))
+            }
+            SchemaKind::Type(Type::Integer(IntegerType { enumeration, .. })) => {
+                non_string("integer", enumeration.iter().any(Option::is_some))
+            }
+            SchemaKind::Type(Type::Boolean(BooleanType { enumeration })) => {
+                non_string("boolean", enumeration.iter().any(Option::is_some))
+            }
+            SchemaKind::Any(AnySchema {
+                typ, enumeration, ..
+            }) if !enumeration.is_empty() => {
+                let value_types = enumeration
+                    .iter()
+                    .filter_map(json_type)
+                    .collect::<BTreeSet<_>>();
+
+                match typ.as_deref() {
+                    Some(typ)
+                        if value_types.iter().any(|value_type| {
+                            *value_type != typ && !(typ == "number" && *value_type == "integer")
+                        }) =>
+                    {
+                        Some(Diagnostic::new(
+                            &lints::NON_STRING_ENUM,
+                            format!(
+                                "The type {} is declared as {} but has enumerated \
+                                values of type {}; use a string enumeration \
+                                instead.",
+                                name,
+                                typ,
+                                value_types.into_iter().collect::<Vec<_>>().join(", "),
+                            ),
+                        ))
+                    }
+                    None if value_types.len() > 1 => Some(Diagnostic::new(
+                        &lints::NON_STRING_ENUM,
+                        format!(
+                            "The type {} has enumerated values of mixed types ({}); \
+                            use a string enumeration instead.",
+                            name,
+                            value_types.into_iter().collect::<Vec<_>>().join(", "),
+                        ),
+                    )),
+                    Some(typ) if typ != "string" => non_string(typ, true),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn validate_path(&self, path: &str) -> Option<Diagnostic> {
+        let renamed = path
+            .split('/')
+            .map(|component| match component.starts_with('{') {
+                true => None,
+                false => self.suggest_rename(component, Target::PathSegment),
+            })
+            .collect::<Vec<_>>();
+        if renamed.iter().all(Option::is_none) {
+            return None;
+        }
+        let renamed = path
+            .split('/')
+            .zip(renamed)
+            .map(|(component, renamed)| renamed.unwrap_or_else(|| component.to_string()))
+            .collect::<Vec<_>>()
+            .join("/");
+        Some(
+            Diagnostic::new(
+                &lints::PATH_NAME,
+                format!(
+                    "The path {} doesn't use {}",
+                    path,
+                    self.case(Target::PathSegment),
+                ),
+            )
+            .with_suggestion(Suggestion::rename(Target::PathSegment, path, &renamed)),
+        )
+    }
+
+    fn validate_path_prefix(&self, path: &str) -> Option<Diagnostic> {
+        if !self.config.literal_path_prefixes {
+            return None;
+        }
+        let mut segments = path.split('/').filter(|segment| !segment.is_empty());
+        let first = segments.next()?;
+        if !first.starts_with('{') {
+            return None;
+        }
+        let problem = if segments.all(|segment| segment.starts_with('{')) {
+            "consists only of parameters".to_string()
+        } else {
+            format!("starts with the parameter {}", first)
+        };
+        Some(Diagnostic::new(
+            &lints::PATH_PREFIX,
+            format!(
+                "The path {} {}, so it matches every prefix; start it with a \
+                literal segment instead.",
+                path, problem,
+            ),
+        ))
+    }
+
+    fn validate_operation_id(
+        &self,
+        path_method_op: (&str, &str, &Operation),
+    ) -> Option<Diagnostic> {
+        let (path, method, op) = path_method_op;
+
+        if let Some(operation_id) = &op.operation_id {
+            if let Some(trimmed) = self.suggest_trim(operation_id, Target::OperationId) {
+                return Some(
+                    Diagnostic::new(
+                        &lints::STRAY_UNDERSCORE,
+                        format!(
+                            "The operation for {} {} is named \"{}\" which has stray \
+                            underscores; rename it \"{}\"",
+                            path, method, operation_id, trimmed,
+                        ),
+                    )
+                    .with_suggestion(Suggestion::rename(
+                        Target::OperationId,
+                        operation_id,
+                        &trimmed,
+                    )),
+                );
+            }
+            let renamed = self.suggest_rename(operation_id, Target::OperationId)?;
+            Some(
+                Diagnostic::new(
+                    &lints::OPERATION_ID_NAME,
+                    format!(
+                        "The operation for {} {} is named \"{}\" which is not {}",
+                        path,
+                        method,
+                        operation_id,
+                        self.case(Target::OperationId),
+                    ),
+                )
+                .with_suggestion(Suggestion::rename(
+                    Target::OperationId,
+                    operation_id,
+                    &renamed,
+                )),
+            )
+        } else {
+            Some(Diagnostic::new(
+                &lints::MISSING_OPERATION_ID,
+                format!(
+                    "The operation for {} {} does not have an operation_id",
+                    path, method,
+                ),
+            ))
+        }
+    }
+
+    fn validate_tags(&self, spec: &OpenAPI) -> Vec<Diagnostic> {
+        // Without a top-level list, tags only group operations, and the
+        // documentation has no introductions to show.
+        if spec.tags.is_empty() {
+            return Vec::new();
+        }
+        let mut ret = Vec::new();
+
+        for (index, tag) in spec.tags.iter().enumerate() {
+            if !is_trivial_description(&tag.name, tag.description.as_deref()) {
+                continue;
+            }
+            let message = match tag.description.as_deref().map(str::trim) {
+                None | Some("") => format!("The tag {} has no description.", tag.name),
+                Some(_) => format!(
+                    "The description of the tag {} only restates its name.",
+                    tag.name
+                ),
+            };
+            ret.push(
+                Diagnostic::new(&lints::TAG_DESCRIPTION, message)
+                    .at(Location::from_pointer(&format!("/tags/{}", index))),
+            );
+        }
+
+        let declared = spec
+            .tags
+            .iter()
+            .map(|tag| tag.name.as_str())
+            .collect::<BTreeSet<_>>();
+        for (path, method, op) in spec.operations() {
+            let undeclared = op
+                .tags
+                .iter()
+                .filter(|tag| !declared.contains(tag.as_str()))
+                .collect::<BTreeSet<_>>();
+            for tag in undeclared {
+                ret.push(
+                    Diagnostic::new(
+                        &lints::TAG_DESCRIPTION,
+                        format!(
+                            "The operation {} has the tag {}, which isn't declared in the \
+                            top-level tags and so has no description.",
+                            op.operation_id.as_deref().unwrap_or("<unknown>"),
+                            tag,
+                        ),
+                    )
+                    .at(Location::operation(path, method, op)),
+                );
+            }
+        }
+
+        // Tags in the configured order come first, and the rest follow
+        // alphabetically.
+        let names = spec
+            .tags
+            .iter()
+            .map(|tag| tag.name.as_str())
+            .collect::<Vec<_>>();
+        let mut canonical = names.clone();
+        canonical.sort_by_key(|name| {
+            let position = self.config.tag_order.iter().position(|tag| tag == name);
+            (position.unwrap_or(self.config.tag_order.len()), *name)
+        });
+        if canonical != names {
+            ret.push(
+                Diagnostic::new(
+                    &lints::TAG_ORDER,
+                    format!(
+                        "The top-level tags aren't in the canonical order; reorder them \
+                        as {}.",
+                        serde_json::to_string(&canonical).unwrap_or_default(),
+                    ),
+                )
+                .at(Location::from_pointer("/tags")),
+            );
+        }
+
+        ret
+    }
+
+    /// Operations on the same resource, i.e. under the same first literal
+    /// segment of their paths after any version (e.g. `disks` in
+    /// `/v1/disks/{disk}`), should carry the tag that most of them share.
+    fn validate_resource_tags(&self, spec: &OpenAPI) -> Vec<Diagnostic> {
+        let mut resources = BTreeMap::<&str, Vec<_>>::new();
+        for (path, method, op) in spec.operations() {
+            if let Some(resource) = path_resource(path) {
+                resources
+                    .entry(resource)
+                    .or_default()
+                    .push((path, method, op));
+            }
+        }
+
+        let mut ret = Vec::new();
+        for (resource, operations) in resources {
+            let tagged = operations
+                .iter()
+                .filter(|(_, _, op)| !op.tags.is_empty())
+                .collect::<Vec<_>>();
+            let mut counts = BTreeMap::<&str, usize>::new();
+            for (_, _, op) in &tagged {
+                for tag in op.tags.iter().collect::<BTreeSet<_>>() {
+                    *counts.entry(tag.as_str()).or_default() += 1;
+                }
+            }
+            // Without a tag shared by most of the operations, there is no
+            // telling which are the strays.
+            let Some((tag, _)) = counts
+                .into_iter()
+                .filter(|(_, count)| 2 * count > tagged.len())
+                .max_by_key(|(_, count)| *count)
+            else {
+                continue;
+            };
+            for (path, method, op) in tagged {
+                if op.tags.iter().any(|other| other == tag) {
+                    continue;
                 }
+                ret.push(
+                    Diagnostic::new(
+                        &lints::TAG_RESOURCE,
+                        format!(
+                            "The operation {} on the resource {} has the tags {}, but \
+                            the resource's other operations have the tag {}.",
+                            op.operation_id.as_deref().unwrap_or("<unknown>"),
+                            resource,
+                            op.tags.join(", "),
+                            tag,
+                        ),
+                    )
+                    .at(Location::operation(path, method, op)),
+                );
+            }
+        }
+        ret
+    }
+
+    fn validate_components_order(&self, spec: &OpenAPI) -> Vec<Diagnostic> {
+        if !self.config.sorted_components {
+            return Vec::new();
+        }
+        let Some(components) = &spec.components else {
+            return Vec::new();
+        };
+
+        // One finding per section, at the first entry out of place, is enough
+        // to prompt sorting the lot.
+        component_names(components)
+            .into_iter()
+            .filter_map(|(section, names)| {
+                let (previous, name) = names
+                    .windows(2)
+                    .map(|pair| (pair[0], pair[1]))
+                    .find(|(previous, name)| previous > name)?;
+                Some(
+                    Diagnostic::new(
+                        &lints::UNSORTED_COMPONENTS,
+                        format!(
+                            "The entries of components.{} aren't sorted by name: \"{}\" \
+                            comes after \"{}\".",
+                            section, name, previous,
+                        ),
+                    )
+                    .at(Location::from_pointer(&format!(
+                        "/components/{}/{}",
+                        section,
+                        fields::escape(name),
+                    ))),
+                )
             })
             .collect()
     }
 
-    fn validate_operation_response(&self, spec: &OpenAPI, op: &Operation) -> Vec<String> {
-        const INFO: &str = "For more info, see \
-            https://github.com/oxidecomputer/openapi-lint#trivial-null-response";
+    fn validate_operation_id_collisions(&self, spec: &OpenAPI) -> Vec<Diagnostic> {
+        // SDK generators derive method and type names from operation IDs by
+        // converting their case; IDs that differ only in where (or whether)
+        // words are split produce names that collide, at least in languages
+        // or on filesystems that ignore case.
+        let mut by_name = BTreeMap::<String, Vec<(&str, Location)>>::new();
+        for (path, method, op) in spec.operations() {
+            if let Some(operation_id) = &op.operation_id {
+                let ids = by_name
+                    .entry(operation_id.to_pascal_case().to_lowercase())
+                    .or_default();
+                if !ids.iter().any(|(id, _)| id == operation_id) {
+                    ids.push((operation_id, Location::operation(path, method, op)));
+                }
+            }
+        }
+
+        by_name
+            .into_values()
+            .filter(|ids| ids.len() > 1)
+            .map(|ids| {
+                // The collision is placed at the operation that introduced it.
+                let location = ids[ids.len() - 1].1.clone();
+                let ids = ids.into_iter().map(|(id, _)| id).collect::<Vec<_>>();
+                Diagnostic::new(
+                    &lints::OPERATION_ID_COLLISION,
+                    format!(
+                        "The operation IDs {} differ only in how they are split into \
+                        words, so the names generated from them ({}) collide where \
+                        case is ignored.",
+                        ids.join(", "),
+                        ids.iter()
+                            .map(|id| id.to_pascal_case())
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    ),
+                )
+                .at(location)
+            })
+            .collect()
+    }
 
-        let operation_id = op.operation_id.as_deref().unwrap_or("<unknown>");
+    fn validate_anonymous_types(&self, spec: &OpenAPI) -> Option<Diagnostic> {
+        let max = self.config.max_anonymous_types?;
 
-        op.responses
-            .responses
+        let named = spec
+            .components
             .iter()
-            .filter_map(|(_, x)| x.item(&spec.components))
-            .flat_map(|response| response.content.iter())
-            .filter_map(|(_, media_type)| media_type.schema.as_ref())
-            .filter_map(|schema| match schema.item(&spec.components)? {
-                Schema {
-                    schema_kind: SchemaKind::Type(Type::String(StringType { enumeration, .. })),
-                    ..
-                } if enumeration.len() == 1 && enumeration.first() == Some(&None) => Some(format!(
-                    "The return type for {} was a trivial null.\n{}",
-                    operation_id, INFO,
-                )),
+            .flat_map(|components| components.schemas.values())
+            .filter_map(ReferenceOr::as_item)
+            .collect::<Vec<_>>();
 
-                _ => None,
+        let operations = spec.operations().flat_map(|(_, _, op)| {
+            let location = format!(
+                "operation {}",
+                op.operation_id.as_deref().unwrap_or("<unknown>")
+            );
+            SchemaWalker::walk(op).map(move |walked| (location.clone(), walked.schema))
+        });
+        let components = spec.components.walk().map(|walked| {
+            let location = match walked.component {
+                Some(name) => format!("schema {}", name),
+                None => "shared components".to_string(),
+            };
+            (location, walked.schema)
+        });
+
+        let mut counts = IndexMap::<String, usize>::new();
+        for (location, schema) in operations.chain(components) {
+            if is_anonymous_type(schema) && !named.iter().any(|n| std::ptr::eq(*n, schema)) {
+                *counts.entry(location).or_default() += 1;
+            }
+        }
+
+        let total = counts.values().sum::<usize>();
+        (total > max).then(|| {
+            Diagnostic::new(
+                &lints::ANONYMOUS_TYPES,
+                format!(
+                    "The spec has {} inline types, more than the budget of {}; SDK \
+                    generators must synthesize a name for each. Consider making some \
+                    of these named components:\n{}",
+                    total,
+                    max,
+                    counts
+                        .iter()
+                        .map(|(location, count)| format!("  {}: {}", location, count))
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                ),
+            )
+        })
+    }
+
+    fn validate_references(&self, spec: &OpenAPI) -> Vec<Diagnostic> {
+        // References are found in the serialized spec, where each has a JSON
+        // pointer and can be looked up as one.
+        let Ok(document) = serde_json::to_value(spec) else {
+            return Vec::new();
+        };
+        let mut references = Vec::new();
+        find_references(&document, String::new(), &mut references);
+
+        let operations = spec
+            .operations()
+            .map(|(path, method, op)| Location::operation(path, method, op))
+            .collect::<Vec<_>>();
+        references
+            .into_iter()
+            .filter_map(|(pointer, reference)| {
+                let problem = match reference.strip_prefix('#') {
+                    None => "refers to another document, which isn't read",
+                    Some(target)
+                        if !target.starts_with("/components/")
+                            || target.split('/').count() != 4 =>
+                    {
+                        "doesn't refer to a component"
+                    }
+                    Some(target) if document.pointer(target).is_none() => {
+                        "refers to a component that doesn't exist"
+                    }
+                    Some(_) => return None,
+                };
+                // A reference within an operation is attributed to it.
+                let location = operations
+                    .iter()
+                    .find(|location| {
+                        location.pointer().is_some_and(|prefix| {
+                            pointer
+                                .strip_prefix(prefix)
+                                .is_some_and(|rest| rest.starts_with('/'))
+                        })
+                    })
+                    .map_or_else(
+                        || Location::from_pointer(&pointer),
+                        |location| Location {
+                            pointer: Some(pointer.clone()),
+                            ..location.clone()
+                        },
+                    );
+                Some(
+                    Diagnostic::new(
+                        &lints::UNRESOLVED_REFERENCE,
+                        format!("The reference {} {}.", reference, problem),
+                    )
+                    .at(location),
+                )
             })
             .collect()
     }
 
-    fn validate_named_schema(&self, type_name: &str) -> Option<String> {
-        const INFO: &str = "For more info, see \
-            https://github.com/oxidecomputer/openapi-lint#naming";
+    fn validate_spec_size(&self, spec: &OpenAPI) -> Option<Diagnostic> {
+        let budget = self.config.max_bytes_per_operation?;
 
-        let pascal = type_name.to_pascal_case();
-        if type_name == pascal {
+        let size = serde_json::to_vec(spec).ok()?.len();
+        let operations = spec.operations().count();
+        if size <= budget * operations.max(1) {
             return None;
         }
 
-        Some(format!(
-            "The type \"{}\" has a name that is not PascalCase; to rename it add \
-            #[serde(rename = \"{}\")]\n{}",
-            type_name, pascal, INFO,
-        ))
-    }
+        // Bloat usually comes from a large schema inlined over and over, so
+        // identical inline schemas are counted together.
+        let mut schemas = IndexMap::<String, (String, usize)>::new();
+        for (_, _, op) in spec.operations() {
+            let operation_id = op.operation_id.as_deref().unwrap_or("<unknown>");
+            for schema in operation_schemas(spec, op) {
+                let Ok(json) = serde_json::to_string(schema) else {
+                    continue;
+                };
+                schemas
+                    .entry(json)
+                    .or_insert_with(|| (format!("an inline schema in {}", operation_id), 0))
+                    .1 += 1;
+            }
+        }
+        let inline = schemas
+            .into_iter()
+            .map(|(json, (what, count))| (what, json.len(), count));
+        let named = spec
+            .components
+            .iter()
+            .flat_map(|components| components.schemas.iter())
+            .filter_map(|(name, schema)| {
+                let size = serde_json::to_vec(schema).ok()?.len();
+                Some((format!("the schema {}", name), size, 1))
+            });
+        let mut largest = inline.chain(named).collect::<Vec<_>>();
+        largest.sort_by_key(|(_, size, count)| std::cmp::Reverse(size * count));
 
-    fn validate_schema_docs(&self, schema: &Schema) -> Vec<String> {
-        let title = schema
-            .schema_data
-            .title
-            .as_ref()
-            .and_then(|s| check_doc_string(s));
-        let description = schema
-            .schema_data
-            .description
-            .as_ref()
-            .and_then(|s| check_doc_string(s));
-        [title, description].iter().flatten().cloned().collect()
+        Some(Diagnostic::new(
+            &lints::SPEC_SIZE,
+            format!(
+                "The spec is {} for {} operations, more than the budget of {} per \
+                    operation; this usually means that a large schema has been \
+                    inlined many times. The largest schemas are:\n{}",
+                kib(size),
+                operations,
+                kib(budget),
+                largest
+                    .iter()
+                    .take(5)
+                    .map(|(what, size, count)| match count {
+                        1 => format!("  {}: {}", what, kib(*size)),
+                        _ => format!(
+                            "  {}, {} times: {} each, {} in all",
+                            what,
+                            count,
+                            kib(*size),
+                            kib(size * count),
+                        ),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+        ))
     }
-}
 
-fn check_doc_string(s: &str) -> Option<String> {
-    const INFO: &str = "For more info, see \
-            https://github.com/oxidecomputer/openapi-lint#rust-documentation";
+    fn validate_required_extensions(&self, spec: &OpenAPI) -> Vec<Diagnostic> {
+        let mut ret = Vec::new();
+        for (element, extension) in &self.config.required_extensions {
+            let mut missing =
+                |what: String,
+                 location: Location,
+                 extensions: &IndexMap<String, serde_json::Value>| {
+                    if !extensions.contains_key(extension) {
+                        ret.push(
+                            Diagnostic::new(
+                                &lints::REQUIRED_EXTENSION,
+                                format!(
+                                    "The {} lacks the extension \"{}\", which is required \
+                                    on every {}.",
+                                    what,
+                                    extension,
+                                    element_name(*element),
+                                ),
+                            )
+                            .at(location),
+                        );
+                    }
+                };
 
-    lazy_static::lazy_static! {
-        static ref PATH: Regex = Regex::new(r#"[[:alnum:]]::[[:alpha:]]"#).unwrap();
-        static ref LINK: Regex = Regex::new(r#"\][^(]"#).unwrap();
+            match element {
+                Element::Operation => {
+                    for (path, method, op) in spec.operations() {
+                        let what = match &op.operation_id {
+                            Some(operation_id) => format!("operation {}", operation_id),
+                            None => format!("operation for {} {}", path, method),
+                        };
+                        missing(what, Location::operation(path, method, op), &op.extensions);
+                    }
+                }
+                Element::Tag => {
+                    for (index, tag) in spec.tags.iter().enumerate() {
+                        missing(
+                            format!("tag {}", tag.name),
+                            Location::from_pointer(&format!("/tags/{}", index)),
+                            &tag.extensions,
+                        );
+                    }
+                }
+                Element::Schema => {
+                    let schemas = spec.components.iter().flat_map(|c| c.schemas.iter());
+                    for (name, schema) in schemas {
+                        if let ReferenceOr::Item(schema) = schema {
+                            missing(
+                                format!("schema {}", name),
+                                Location::named("schemas", name),
+                                &schema.schema_data.extensions,
+                            );
+                        }
+                    }
+                }
+                Element::Parameter => {
+                    for (path, method, op) in spec.operations() {
+                        let operation_id = op.operation_id.as_deref().unwrap_or("<unknown>");
+                        for param in operation_parameters(spec, path, op) {
+                            let data = param.parameter_data_ref();
+                            missing(
+                                format!("parameter \"{}\" to {}", data.name, operation_id),
+                                Location::operation(path, method, op),
+                                &data.extensions,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        ret
     }
 
-    (PATH.is_match(s) || LINK.is_match(s)).then(|| {
-        format!(
-            "Rust documentation found in external interface: {}\n{}",
-            s, INFO
-        )
-    })
-}
+    fn validate_duplicate_enums(&self, spec: &OpenAPI) -> Vec<Diagnostic> {
+        // Component schemas come first, so that a repetition of one is
+        // pointed at it rather than the other way around.
+        let mut schemas = located_schemas(spec)
+            .filter_map(|(component, schema, location)| {
+                let SchemaKind::Type(Type::String(StringType { enumeration, .. })) =
+                    &schema.schema_kind
+                else {
+                    return None;
+                };
+                let values = enumeration
+                    .iter()
+                    .flatten()
+                    .map(String::as_str)
+                    .collect::<BTreeSet<_>>();
+                let named = component.filter(|name| {
+                    location.pointer()
+                        == Some(&format!("/components/schemas/{}", fields::escape(name)))
+                });
+                Some((named, values, location))
+            })
+            // A single value is a constant (such as the tag of a variant)
+            // rather than a type.
+            .filter(|(_, values, _)| values.len() > 1)
+            .collect::<Vec<_>>();
+        schemas.sort_by_key(|(named, ..)| named.is_none());
 
-fn is_permissive(any: &AnySchema) -> bool {
-    match any {
-        AnySchema {
-            typ: None,
-            pattern: None,
-            multiple_of: None,
-            exclusive_minimum: None,
-            exclusive_maximum: None,
-            minimum: None,
-            maximum: None,
-            properties,
-            required,
-            additional_properties: None,
-            min_properties: None,
-            max_properties: None,
-            items: None,
-            min_items: None,
-            max_items: None,
-            unique_items: None,
-            enumeration,
-            format: None,
-            min_length: None,
-            max_length: None,
-            one_of,
-            all_of,
-            any_of,
-            not: None,
-        } if properties.is_empty()
-            && required.is_empty()
-            && enumeration.is_empty()
-            && one_of.is_empty()
-            && all_of.is_empty()
-            && any_of.is_empty() =>
-        {
-            true
+        let mut first = BTreeMap::<BTreeSet<&str>, (Option<&str>, Location)>::new();
+        let mut ret = Vec::new();
+        for (named, values, location) in schemas {
+            let Some((first_named, first_location)) = first.get(&values) else {
+                first.insert(values, (named, location));
+                continue;
+            };
+            let pointer = location.pointer().unwrap_or_default();
+            let message = match (named, first_named) {
+                (Some(name), Some(first_name)) => format!(
+                    "The schemas {} and {} have the same values; merge them, so that \
+                    SDKs generate a single type.",
+                    first_name, name,
+                ),
+                (None, Some(first_name)) => format!(
+                    "The string enumeration at {} has the same values as the schema {}; \
+                    refer to {} instead, so that SDKs generate a single type.",
+                    pointer, first_name, first_name,
+                ),
+                (_, None) => format!(
+                    "The string enumeration at {} has the same values as the one at {}; \
+                    define them once, as a component schema that both refer to, so that \
+                    SDKs generate a single type.",
+                    pointer,
+                    first_location.pointer().unwrap_or_default(),
+                ),
+            };
+            ret.push(Diagnostic::new(&lints::DUPLICATE_ENUM, message).at(location));
         }
-        _ => false,
+        ret
     }
-}
 
-trait ReferenceOrExt<T: ComponentLookup> {
-    fn item<'a>(&'a self, components: &'a Option<Components>) -> Option<&'a T>;
-}
-trait ComponentLookup: Sized {
-    fn get_components(components: &Components) -> &IndexMap<String, ReferenceOr<Self>>;
-}
+    fn validate_shared_request_bodies(&self, spec: &OpenAPI) -> Vec<Diagnostic> {
+        // The operations that use each request body component, with their
+        // method and the names of their path parameters.
+        let mut users = IndexMap::<&str, Vec<(&str, &str, BTreeSet<&str>)>>::new();
+        for (path, method, op) in spec.operations() {
+            let Some(ReferenceOr::Reference { reference }) = &op.request_body else {
+                continue;
+            };
+            let Some(name) = reference_name(reference) else {
+                continue;
+            };
+            let path_params = operation_parameters(spec, path, op)
+                .into_iter()
+                .filter(|param| matches!(param, Parameter::Path { .. }))
+                .map(|param| param.parameter_data_ref().name.as_str())
+                .collect();
+            users.entry(name).or_default().push((
+                op.operation_id.as_deref().unwrap_or("<unknown>"),
+                method,
+                path_params,
+            ));
+        }
 
-impl<T: ComponentLookup> ReferenceOrExt<T> for openapiv3::ReferenceOr<T> {
-    fn item<'a>(&'a self, components: &'a Option<Components>) -> Option<&'a T> {
-        match self {
-            ReferenceOr::Item(item) => Some(item),
-            ReferenceOr::Reference { reference } => {
-                let idx = reference.rfind('/').unwrap();
-                let key = &reference[idx + 1..];
-                let items = T::get_components(components.as_ref().unwrap());
-                items.get(key).unwrap().item(components)
+        let mut ret = Vec::new();
+        for (name, ops) in users.into_iter().filter(|(_, ops)| ops.len() > 1) {
+            let location = Location::named("requestBodies", name);
+            let using = |methods: &[&str]| {
+                ops.iter()
+                    .filter(|(_, method, _)| methods.contains(method))
+                    .map(|(operation_id, ..)| *operation_id)
+                    .collect::<Vec<_>>()
+            };
+
+            let creates = using(&["post"]);
+            let updates = using(&["put", "patch"]);
+            if !creates.is_empty() && !updates.is_empty() {
+                ret.push(
+                    Diagnostic::new(
+                        &lints::SHARED_BODY_CREATE_UPDATE,
+                        format!(
+                            "The request body {} is used both to create (by {}) and to \
+                            update (by {}); this is usually a copy-pasted reference, as \
+                            creates and updates rarely accept the same fields.",
+                            name,
+                            creates.join(", "),
+                            updates.join(", "),
+                        ),
+                    )
+                    .at(location.clone()),
+                );
+            }
+
+            let (_, _, first_params) = &ops[0];
+            if ops.iter().any(|(_, _, params)| params != first_params) {
+                ret.push(
+                    Diagnostic::new(
+                        &lints::SHARED_BODY_PATH_PARAMS,
+                        format!(
+                            "The request body {} is used by operations with different path \
+                            parameters ({}), so they likely act on different kinds of \
+                            resource.",
+                            name,
+                            ops.iter()
+                                .map(|(operation_id, _, params)| format!(
+                                    "{}: {}",
+                                    operation_id,
+                                    params.iter().copied().collect::<Vec<_>>().join(", ")
+                                ))
+                                .collect::<Vec<_>>()
+                                .join("; "),
+                        ),
+                    )
+                    .at(location.clone()),
+                );
             }
         }
+        ret
     }
-}
 
-impl ComponentLookup for Parameter {
-    fn get_components(components: &Components) -> &IndexMap<String, ReferenceOr<Self>> {
-        &components.parameters
+    fn validate_nullable_required(&self, spec: &OpenAPI) -> Vec<Diagnostic> {
+        // Named body schemas are often shared by several operations; check
+        // each once, on behalf of the first operation that uses it.
+        let mut named = IndexMap::<&str, (&str, &Schema)>::new();
+        let mut inline = Vec::new();
+        for (path, method, op) in spec.operations() {
+            let operation_id = op.operation_id.as_deref().unwrap_or("<unknown>");
+            let Some(body) = op
+                .request_body
+                .as_ref()
+                .and_then(|body| self.item(&spec.components, body))
+            else {
+                continue;
+            };
+            for schema in body.content.values().filter_map(|m| m.schema.as_ref()) {
+                match schema {
+                    ReferenceOr::Reference { reference } => {
+                        let (Some(name), Some(schema)) = (
+                            reference_name(reference),
+                            self.item(&spec.components, schema),
+                        ) else {
+                            continue;
+                        };
+                        named.entry(name).or_insert((operation_id, schema));
+                    }
+                    ReferenceOr::Item(schema) => inline.push((
+                        "the inline schema".to_string(),
+                        operation_id,
+                        schema,
+                        Location::operation(path, method, op),
+                    )),
+                }
+            }
+        }
+
+        let named = named.into_iter().map(|(name, (operation_id, schema))| {
+            (
+                format!("the schema {}", name),
+                operation_id,
+                schema,
+                Location::named("schemas", name),
+            )
+        });
+
+        named
+            .chain(inline)
+            .flat_map(|(what, operation_id, schema, location)| {
+                let SchemaKind::Type(Type::Object(obj)) = &schema.schema_kind else {
+                    return vec![];
+                };
+                obj.properties
+                    .iter()
+                    .filter(|(prop_name, _)| obj.required.contains(prop_name))
+                    .filter(|(_, prop)| {
+                        self.item(&spec.components, *prop)
+                            .is_some_and(|prop| prop.schema_data.nullable)
+                    })
+                    .map(|(prop_name, prop)| {
+                        let diagnostic = Diagnostic::new(
+                            &lints::NULLABLE_REQUIRED,
+                            format!(
+                                "The property \"{}\" of {}, the request body of {}, is \
+                                both required and nullable; make it optional if it may \
+                                be omitted, or non-nullable if it must be given.",
+                                prop_name, what, operation_id,
+                            ),
+                        );
+                        (prop, diagnostic)
+                    })
+                    .filter(|(prop, diagnostic)| {
+                        !prop.as_item().is_some_and(|prop| {
+                            self.waivers
+                                .waives(&prop.schema_data.extensions, diagnostic)
+                        })
+                    })
+                    .map(|(_, diagnostic)| diagnostic.at(location.clone()))
+                    .collect()
+            })
+            .collect()
     }
-}
 
-impl ComponentLookup for Schema {
-    fn get_components(components: &Components) -> &IndexMap<String, ReferenceOr<Self>> {
-        &components.schemas
+    /// Check that some word of an operation's ID appears in its path, to
+    /// catch IDs copied from another endpoint along with its code.
+    fn validate_operation_id_path(
+        &self,
+        path_method_op: (&str, &str, &Operation),
+    ) -> Option<Diagnostic> {
+        let (path, _, op) = path_method_op;
+        let operation_id = op.operation_id.as_deref()?;
+
+        let words = |text: &str| {
+            text.to_snake_case()
+                .split(|c: char| !c.is_ascii_alphanumeric())
+                .filter(|word| !word.is_empty())
+                .map(singular)
+                .collect::<BTreeSet<_>>()
+        };
+        let in_path = words(path);
+        let in_id = words(operation_id);
+        if in_id.is_empty() || !in_id.is_disjoint(&in_path) {
+            return None;
+        }
+        Some(Diagnostic::new(
+            &lints::OPERATION_ID_PATH,
+            format!(
+                "The operation {} has no word in common with its path {}; it may \
+                have been copied from another endpoint. Name it after the resource \
+                in the path.",
+                operation_id, path,
+            ),
+        ))
     }
-}
 
-impl ComponentLookup for Response {
-    fn get_components(components: &Components) -> &IndexMap<String, ReferenceOr<Self>> {
-        &components.responses
+    fn validate_method(&self, path_method_op: (&str, &str, &Operation)) -> Option<Diagnostic> {
+        let (path, method, op) = path_method_op;
+        let operation_id = op.operation_id.as_deref().unwrap_or("<unknown>");
+
+        (!self.config.allowed_methods.iter().any(|m| m == method)).then(|| {
+            Diagnostic::new(
+                &lints::METHOD,
+                format!(
+                    "The operation {} uses the {} method for {}, which is not \
+                    among the allowed methods ({}).",
+                    operation_id,
+                    method.to_uppercase(),
+                    path,
+                    self.config.allowed_methods.join(", ").to_uppercase(),
+                ),
+            )
+        })
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use heck::ToSnakeCase;
+    fn validate_update(
+        &self,
+        spec: &OpenAPI,
+        path_method_op: (&str, &str, &Operation),
+    ) -> Vec<Diagnostic> {
+        let (path, method, op) = path_method_op;
+        let operation_id = op.operation_id.as_deref().unwrap_or("<unknown>");
+        let Some(body) = op
+            .request_body
+            .as_ref()
+            .and_then(|body| self.item(&spec.components, body))
+        else {
+            return vec![];
+        };
 
-    use crate::validate_external;
+        match method {
+            // Disallowed methods are reported elsewhere.
+            "patch" if self.config.allowed_methods.iter().any(|m| m == "patch") => body
+                .content
+                .keys()
+                .filter(|content_type| !self.config.patch_content_types.contains(content_type))
+                .map(|content_type| {
+                    Diagnostic::new(
+                        &lints::PATCH_CONTENT_TYPE,
+                        format!(
+                            "The PATCH operation {} accepts {}; partial updates must \
+                            use {}.",
+                            operation_id,
+                            content_type,
+                            self.config.patch_content_types.join(" or "),
+                        ),
+                    )
+                })
+                .collect(),
+            "put" => {
+                let Some(suffix) = &self.config.update_suffix else {
+                    return vec![];
+                };
+                let Some(media_type) = body.content.get("application/json") else {
+                    return vec![];
+                };
 
-    #[test]
-    fn bad_schema() {
-        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+                let body_name = match &media_type.schema {
+                    Some(ReferenceOr::Reference { reference }) => reference_name(reference),
+                    _ => None,
+                };
+                // The resource being replaced is whatever a GET on the same
+                // path returns.
+                let resource_name = spec
+                    .paths
+                    .paths
+                    .get(path)
+                    .and_then(ReferenceOr::as_item)
+                    .and_then(|path_item| path_item.get.as_ref())
+                    .and_then(|get| success_schema_name(spec, get));
 
-        let actual = validate_external(&openapi).join("\n\n");
-        expectorate::assert_contents("src/tests/errors.out", &actual);
+                let expected =
+                    resource_name.map(|resource_name| format!("{}{}", resource_name, suffix));
+                let ok = match (&body_name, &expected) {
+                    (Some(body_name), Some(expected)) => body_name == expected,
+                    (Some(body_name), None) => body_name.ends_with(suffix.as_str()),
+                    (None, _) => false,
+                };
+
+                if ok {
+                    vec![]
+                } else {
+                    vec![Diagnostic::new(
+                        &lints::PUT_BODY,
+                        format!(
+                            "The PUT operation {} accepts {} rather than {}; a PUT \
+                            replaces the full resource and its body should be the \
+                            resource's update schema.",
+                            operation_id,
+                            body_name.unwrap_or("an inline schema"),
+                            expected.unwrap_or_else(|| format!("a schema named *{}", suffix)),
+                        ),
+                    )]
+                }
+            }
+            _ => vec![],
+        }
+    }
+
+    fn validate_operation_parameters(&self, spec: &OpenAPI, op: &Operation) -> Vec<Diagnostic> {
+        let operation_id = op.operation_id.as_deref().unwrap_or("<unknown>");
+        op.parameters
+            .iter()
+            .filter_map(|ref_or_param| {
+                let param = self.item(&spec.components, ref_or_param)?;
+
+                // The header of the idempotency key convention has a name of
+                // its own, checked by validate_idempotency_key.
+                if is_idempotency_key(param) {
+                    return None;
+                }
+
+                let name = &param.parameter_data_ref().name;
+
+                if let Some(trimmed) = self.suggest_trim(name, Target::Parameter) {
+                    Some(
+                        Diagnostic::new(
+                            &lints::STRAY_UNDERSCORE,
+                            format!(
+                                "The parameter \"{}\" to {} has stray underscores; \
+                                rename it \"{}\".",
+                                name, operation_id, trimmed,
+                            ),
+                        )
+                        .with_suggestion(Suggestion::rename(
+                            Target::Parameter,
+                            name,
+                            &trimmed,
+                        )),
+                    )
+                } else {
+                    let renamed = self.suggest_rename(name, Target::Parameter)?;
+                    Some(
+                        Diagnostic::new(
+                            &lints::PARAMETER_NAME,
+                            format!(
+                                "The parameter \"{}\" to {} should be {}.",
+                                name,
+                                operation_id,
+                                self.case(Target::Parameter),
+                            ),
+                        )
+                        .with_suggestion(Suggestion::rename(
+                            Target::Parameter,
+                            name,
+                            &renamed,
+                        )),
+                    )
+                }
+            })
+            .collect()
+    }
+
+    fn validate_unique_parameters(
+        &self,
+        spec: &OpenAPI,
+        path: &str,
+        op: &Operation,
+    ) -> Vec<Diagnostic> {
+        let operation_id = op.operation_id.as_deref().unwrap_or("<unknown>");
+        let key = |param: &Parameter| {
+            (
+                param.parameter_data_ref().name.clone(),
+                parameter_location(param),
+            )
+        };
+
+        let path_params = spec
+            .paths
+            .paths
+            .get(path)
+            .and_then(ReferenceOr::as_item)
+            .into_iter()
+            .flat_map(|path_item| path_item.parameters.iter())
+            .filter_map(|param| self.item(&spec.components, param))
+            .map(key)
+            .collect::<Vec<_>>();
+        let op_params = op
+            .parameters
+            .iter()
+            .filter_map(|param| self.item(&spec.components, param))
+            .map(key)
+            .collect::<Vec<_>>();
+
+        let mut ret = Vec::new();
+        for (params, level) in [(&path_params, "path item"), (&op_params, "operation")] {
+            let mut seen = BTreeSet::new();
+            for (name, location) in params {
+                if !seen.insert((name, location)) {
+                    ret.push(Diagnostic::new(
+                        &lints::DUPLICATE_PARAMETER,
+                        format!(
+                            "The {} parameter \"{}\" is declared more than once for \
+                            the {} of {}.",
+                            location, name, level, operation_id,
+                        ),
+                    ));
+                }
+            }
+        }
+        ret
+    }
+
+    fn validate_mutually_exclusive(
+        &self,
+        spec: &OpenAPI,
+        path: &str,
+        op: &Operation,
+    ) -> Vec<Diagnostic> {
+        let operation_id = op.operation_id.as_deref().unwrap_or("<unknown>");
+        let params = operation_parameters(spec, path, op);
+        // Malformed values are reported by the check of extension values.
+        let groups = op
+            .extensions
+            .get("x-mutually-exclusive")
+            .and_then(serde_json::Value::as_array)
+            .into_iter()
+            .flatten()
+            .map(|group| {
+                group
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(serde_json::Value::as_str)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut ret = Vec::new();
+        for name in groups.iter().flatten().collect::<BTreeSet<_>>() {
+            let param = params
+                .iter()
+                .map(|param| param.parameter_data_ref())
+                .find(|data| data.name == *name);
+            let problem = match param {
+                None => "which isn't a parameter of the operation",
+                Some(data) if data.required => {
+                    "which is required and so can't be left out in favor of the others"
+                }
+                Some(_) => continue,
+            };
+            ret.push(Diagnostic::new(
+                &lints::MUTUALLY_EXCLUSIVE_PARAMETER,
+                format!(
+                    "The x-mutually-exclusive extension of {} names \"{}\", {}.",
+                    operation_id, name, problem,
+                ),
+            ));
+        }
+
+        // Optional query parameters that select the same thing by ID and by
+        // name, such as `project_id` and `project_name`, are obviously
+        // exclusive.
+        let optional = params
+            .iter()
+            .filter_map(|param| match param {
+                Parameter::Query { parameter_data, .. } if !parameter_data.required => {
+                    Some(parameter_data.name.as_str())
+                }
+                _ => None,
+            })
+            .collect::<BTreeSet<_>>();
+        for id in &optional {
+            let Some(stem) = id.strip_suffix("id") else {
+                continue;
+            };
+            let name = format!("{}name", stem);
+            if !(stem.is_empty() || stem.ends_with('_')) || !optional.contains(name.as_str()) {
+                continue;
+            }
+            let grouped = groups
+                .iter()
+                .any(|group| group.contains(id) && group.contains(&name.as_str()));
+            if !grouped {
+                ret.push(Diagnostic::new(
+                    &lints::MISSING_MUTUAL_EXCLUSION,
+                    format!(
+                        "The parameters \"{}\" and \"{}\" to {} select the same thing in \
+                        different ways, but no x-mutually-exclusive group lists both.",
+                        id, name, operation_id,
+                    ),
+                ));
+            }
+        }
+
+        ret
+    }
+
+    /// Check for required properties of the request body's object schemas
+    /// that have the same name and type as a path or query parameter.
+    fn validate_body_parameters(
+        &self,
+        spec: &OpenAPI,
+        path: &str,
+        op: &Operation,
+    ) -> Vec<Diagnostic> {
+        let operation_id = op.operation_id.as_deref().unwrap_or("<unknown>");
+        let Some(body) = op
+            .request_body
+            .as_ref()
+            .and_then(|body| self.item(&spec.components, body))
+        else {
+            return vec![];
+        };
+
+        let params = operation_parameters(spec, path, op)
+            .into_iter()
+            .filter_map(|param| {
+                if !matches!(param, Parameter::Path { .. } | Parameter::Query { .. }) {
+                    return None;
+                }
+                let parameter_data = param.parameter_data_ref();
+                let ParameterSchemaOrContent::Schema(schema) = &parameter_data.format else {
+                    return None;
+                };
+                let SchemaKind::Type(typ) = &self.item(&spec.components, schema)?.schema_kind
+                else {
+                    return None;
+                };
+                Some((param, parameter_data.name.as_str(), typ))
+            })
+            .collect::<Vec<_>>();
+
+        let mut ret = Vec::new();
+        let mut reported = BTreeSet::new();
+        for media_type in body.content.values() {
+            let Some(Schema {
+                schema_kind: SchemaKind::Type(Type::Object(object)),
+                ..
+            }) = media_type
+                .schema
+                .as_ref()
+                .and_then(|schema| self.item(&spec.components, schema))
+            else {
+                continue;
+            };
+            for (param, name, typ) in &params {
+                let duplicated = object.required.iter().any(|required| required == name)
+                    && object
+                        .properties
+                        .get(*name)
+                        .and_then(|prop| self.item(&spec.components, prop))
+                        .is_some_and(|prop| match &prop.schema_kind {
+                            SchemaKind::Type(prop_type) => {
+                                std::mem::discriminant(prop_type) == std::mem::discriminant(*typ)
+                            }
+                            _ => false,
+                        });
+                // Each content type of the body is usually the same schema.
+                if duplicated && reported.insert(*name) {
+                    ret.push(Diagnostic::new(
+                        &lints::BODY_PARAMETER,
+                        format!(
+                            "The request body of {} has a required property \"{}\" \
+                            that repeats its {} parameter of the same name and type; \
+                            clients can't tell which value the server uses. Remove \
+                            the property from the body.",
+                            operation_id,
+                            name,
+                            parameter_location(param),
+                        ),
+                    ));
+                }
+            }
+        }
+        ret
+    }
+
+    fn validate_query_arrays(&self, spec: &OpenAPI, path: &str, op: &Operation) -> Vec<Diagnostic> {
+        if !self.config.bounded_query_arrays {
+            return vec![];
+        }
+
+        let operation_id = op.operation_id.as_deref().unwrap_or("<unknown>");
+
+        operation_parameters(spec, path, op)
+            .into_iter()
+            .flat_map(|param| {
+                let Parameter::Query { parameter_data, .. } = param else {
+                    return vec![];
+                };
+                let ParameterSchemaOrContent::Schema(schema) = &parameter_data.format else {
+                    return vec![];
+                };
+                let Some(Schema {
+                    schema_kind: SchemaKind::Type(Type::Array(array)),
+                    ..
+                }) = self.item(&spec.components, schema)
+                else {
+                    return vec![];
+                };
+
+                let unbounded = array.max_items.is_none().then(|| {
+                    Diagnostic::new(
+                        &lints::UNBOUNDED_QUERY_ARRAY,
+                        format!(
+                            "The array query parameter \"{}\" to {} does not declare \
+                            maxItems; query strings must be bounded.",
+                            parameter_data.name, operation_id,
+                        ),
+                    )
+                });
+                let scalar = array
+                    .items
+                    .as_ref()
+                    .and_then(|items| self.item(&spec.components, items))
+                    .is_some_and(|items| is_scalar(spec, items));
+                let complex = (!scalar).then(|| {
+                    Diagnostic::new(
+                        &lints::COMPLEX_QUERY_ARRAY,
+                        format!(
+                            "The array query parameter \"{}\" to {} has items that \
+                            are not strings, numbers, or booleans, which can't be \
+                            represented in a query string.",
+                            parameter_data.name, operation_id,
+                        ),
+                    )
+                });
+
+                unbounded.into_iter().chain(complex).collect()
+            })
+            .collect()
+    }
+
+    fn validate_path_parameter_style(
+        &self,
+        spec: &OpenAPI,
+        path: &str,
+        op: &Operation,
+    ) -> Vec<Diagnostic> {
+        let operation_id = op.operation_id.as_deref().unwrap_or("<unknown>");
+
+        operation_parameters(spec, path, op)
+            .into_iter()
+            .flat_map(|param| {
+                let Parameter::Path {
+                    parameter_data,
+                    style,
+                } = param
+                else {
+                    return vec![];
+                };
+
+                let style = match style {
+                    PathStyle::Simple => None,
+                    PathStyle::Label => Some("label"),
+                    PathStyle::Matrix => Some("matrix"),
+                }
+                .map(|style| {
+                    Diagnostic::new(
+                        &lints::PATH_PARAMETER_STYLE,
+                        format!(
+                            "The path parameter \"{}\" to {} uses the {} style; \
+                            only the simple style is supported.",
+                            parameter_data.name, operation_id, style,
+                        ),
+                    )
+                });
+                let explode = (parameter_data.explode == Some(true)).then(|| {
+                    Diagnostic::new(
+                        &lints::PATH_PARAMETER_STYLE,
+                        format!(
+                            "The path parameter \"{}\" to {} sets explode, which \
+                            is not supported.",
+                            parameter_data.name, operation_id,
+                        ),
+                    )
+                });
+
+                style.into_iter().chain(explode).collect()
+            })
+            .collect()
+    }
+
+    fn validate_path_parameter_names(&self, path: &str) -> Vec<Diagnostic> {
+        if !self.config.resource_path_parameters {
+            return vec![];
+        }
+
+        let segments = path.split('/').collect::<Vec<_>>();
+        segments
+            .windows(2)
+            .filter_map(|pair| {
+                let (collection, param) = (pair[0], pair[1]);
+                let name = param.strip_prefix('{')?.strip_suffix('}')?;
+                // Only a parameter that selects an item from a collection
+                // (e.g. /instances/{instance}) is named for the resource.
+                if collection.starts_with('{') || !is_plural(collection) {
+                    return None;
+                }
+                let resource = singular(&collection.to_snake_case());
+                let expected = std::iter::once(resource.clone())
+                    .chain(
+                        self.config
+                            .path_parameter_suffixes
+                            .iter()
+                            .map(|suffix| format!("{}{}", resource, suffix)),
+                    )
+                    .collect::<Vec<_>>();
+                if expected.iter().any(|expected| expected == name) {
+                    return None;
+                }
+
+                let expected = expected
+                    .iter()
+                    .map(|name| format!("\"{}\"", name))
+                    .collect::<Vec<_>>();
+                Some(Diagnostic::new(
+                    &lints::PATH_PARAMETER_NAME,
+                    format!(
+                        "The path parameter \"{}\" in {} selects an item from \
+                        {}; name it for the resource (one of {}).",
+                        name,
+                        path,
+                        collection,
+                        expected.join(", "),
+                    ),
+                ))
+            })
+            .collect()
+    }
+
+    fn validate_operation_response(&self, spec: &OpenAPI, op: &Operation) -> Vec<Diagnostic> {
+        let operation_id = op.operation_id.as_deref().unwrap_or("<unknown>");
+
+        op.responses
+            .responses
+            .iter()
+            .filter_map(|(_, x)| self.item(&spec.components, x))
+            .flat_map(|response| response.content.iter())
+            .filter_map(|(_, media_type)| media_type.schema.as_ref())
+            .filter_map(|schema| match self.item(&spec.components, schema)? {
+                Schema {
+                    schema_kind: SchemaKind::Type(Type::String(StringType { enumeration, .. })),
+                    ..
+                } if enumeration.len() == 1 && enumeration.first() == Some(&None) => {
+                    Some(Diagnostic::new(
+                        &lints::TRIVIAL_NULL,
+                        format!("The return type for {} was a trivial null.", operation_id),
+                    ))
+                }
+
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Check the properties of response schemas that hold URLs (those named
+    /// `location`, `url`, or `href`), and of the schemas within them, each
+    /// component schema once.
+    fn validate_response_urls(&self, spec: &OpenAPI) -> Vec<Diagnostic> {
+        let mut ret = Vec::new();
+        let mut visited = BTreeSet::new();
+        for (path, method, op) in spec.operations() {
+            let responses = op
+                .responses
+                .default
+                .iter()
+                .map(|r| ("default".to_string(), r));
+            let responses = responses.chain(
+                op.responses
+                    .responses
+                    .iter()
+                    .map(|(status, response)| (status.to_string(), response)),
+            );
+            for (status, response) in responses {
+                let base = match response {
+                    ReferenceOr::Reference { reference } => {
+                        let Some(name) = reference_name(reference) else {
+                            continue;
+                        };
+                        format!("/components/responses/{}", fields::escape(name))
+                    }
+                    ReferenceOr::Item(_) => format!(
+                        "/paths/{}/{}/responses/{}",
+                        fields::escape(path),
+                        method,
+                        status,
+                    ),
+                };
+                let Some(response) = self.item(&spec.components, response) else {
+                    continue;
+                };
+                for (content_type, media_type) in &response.content {
+                    let Some(schema) = &media_type.schema else {
+                        continue;
+                    };
+                    let pointer =
+                        format!("{}/content/{}/schema", base, fields::escape(content_type),);
+                    self.validate_urls_within(spec, schema, pointer, &mut visited, &mut ret);
+                }
+            }
+        }
+        ret
+    }
+
+    fn validate_urls_within<'s>(
+        &self,
+        spec: &'s OpenAPI,
+        schema: &'s impl ReferenceOrExt<Schema>,
+        pointer: String,
+        visited: &mut BTreeSet<&'s str>,
+        ret: &mut Vec<Diagnostic>,
+    ) {
+        let (schema, pointer) = match schema.split() {
+            Ok(schema) => (schema, pointer),
+            Err(reference) => {
+                let Some(name) = reference_name(reference) else {
+                    return;
+                };
+                if !visited.insert(name) {
+                    return;
+                }
+                let Some(schema) = self.item(&spec.components, schema) else {
+                    return;
+                };
+                let pointer = format!("/components/schemas/{}", fields::escape(name));
+                (schema, pointer)
+            }
+        };
+
+        match &schema.schema_kind {
+            SchemaKind::Type(Type::Object(object)) => {
+                for (prop_name, prop) in &object.properties {
+                    let pointer = format!("{}/properties/{}", pointer, fields::escape(prop_name));
+                    if ["location", "url", "href"]
+                        .iter()
+                        .any(|name| prop_name.eq_ignore_ascii_case(name))
+                    {
+                        if let Some(prop) = self.item(&spec.components, prop) {
+                            ret.extend(
+                                self.validate_url(prop_name, prop)
+                                    .into_iter()
+                                    .map(|d| d.at(Location::from_pointer(&pointer))),
+                            );
+                        }
+                    }
+                    self.validate_urls_within(spec, prop, pointer, visited, ret);
+                }
+                if let Some(AdditionalProperties::Schema(schema)) = &object.additional_properties {
+                    let pointer = format!("{}/additionalProperties", pointer);
+                    self.validate_urls_within(spec, schema.as_ref(), pointer, visited, ret);
+                }
+            }
+            SchemaKind::Type(Type::Array(ArrayType {
+                items: Some(items), ..
+            })) => {
+                let pointer = format!("{}/items", pointer);
+                self.validate_urls_within(spec, items, pointer, visited, ret);
+            }
+            SchemaKind::AllOf { all_of: ofs } => {
+                for (index, sub) in ofs.iter().enumerate() {
+                    let pointer = format!("{}/allOf/{}", pointer, index);
+                    self.validate_urls_within(spec, sub, pointer, visited, ret);
+                }
+            }
+            SchemaKind::OneOf { one_of: ofs } => {
+                for (index, sub) in ofs.iter().enumerate() {
+                    let pointer = format!("{}/oneOf/{}", pointer, index);
+                    self.validate_urls_within(spec, sub, pointer, visited, ret);
+                }
+            }
+            SchemaKind::AnyOf { any_of: ofs } => {
+                for (index, sub) in ofs.iter().enumerate() {
+                    let pointer = format!("{}/anyOf/{}", pointer, index);
+                    self.validate_urls_within(spec, sub, pointer, visited, ret);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The problems with a string property of a response that holds a URL.
+    fn validate_url(&self, prop_name: &str, prop: &Schema) -> Vec<Diagnostic> {
+        let SchemaKind::Type(Type::String(StringType { format, .. })) = &prop.schema_kind else {
+            return Vec::new();
+        };
+        let mut ret = Vec::new();
+        if !matches!(format, VariantOrUnknownOrEmpty::Unknown(format) if format == "uri") {
+            ret.push(Diagnostic::new(
+                &lints::URL_FORMAT,
+                format!(
+                    "The response property '{}' is a string without format: uri, so \
+                    it may hold a URL relative to the server that clients resolve \
+                    against the wrong base; return an absolute URL with format: uri.",
+                    prop_name,
+                ),
+            ));
+        }
+        let explained = prop.schema_data.description.as_deref().is_some_and(|d| {
+            let d = d.to_lowercase();
+            d.contains("absolute") || d.contains("relative")
+        });
+        if self.config.external && !explained {
+            ret.push(Diagnostic::new(
+                &lints::URL_DESCRIPTION,
+                format!(
+                    "The response property '{}' holds a URL, but its description \
+                    doesn't say whether it is absolute or relative (and to what).",
+                    prop_name,
+                ),
+            ));
+        }
+        ret
+    }
+
+    fn validate_request_body_union(&self, spec: &OpenAPI, op: &Operation) -> Vec<Diagnostic> {
+        if self.config.request_body_unions {
+            return vec![];
+        }
+
+        let operation_id = op.operation_id.as_deref().unwrap_or("<unknown>");
+        let Some(body) = op
+            .request_body
+            .as_ref()
+            .and_then(|body| self.item(&spec.components, body))
+        else {
+            return vec![];
+        };
+
+        body.content
+            .iter()
+            .filter_map(|(content_type, media_type)| {
+                let schema = media_type.schema.as_ref()?;
+                let SchemaKind::OneOf { .. } = self.item(&spec.components, schema)?.schema_kind
+                else {
+                    return None;
+                };
+                const FIX: &str = "polymorphic request bodies are awkward to build \
+                    with SDKs, so prefer separate operations or a single object with \
+                    an explicit type field.";
+                // A shared schema is reported once, for every operation that
+                // uses it.
+                if let Some(name) = schema_reference_name(schema) {
+                    return Some(
+                        Diagnostic::new(
+                            &lints::REQUEST_BODY_UNION,
+                            format!(
+                                "The schema {} is used for {} request bodies but is a \
+                                oneOf union; {}",
+                                name, content_type, FIX,
+                            ),
+                        )
+                        .at(Location::named("schemas", name)),
+                    );
+                }
+                Some(Diagnostic::new(
+                    &lints::REQUEST_BODY_UNION,
+                    format!(
+                        "The {} request body for {} is an inline schema, a oneOf \
+                        union; {}",
+                        content_type, operation_id, FIX,
+                    ),
+                ))
+            })
+            .collect()
+    }
+
+    fn validate_nullable_request_body(&self, spec: &OpenAPI, op: &Operation) -> Vec<Diagnostic> {
+        let operation_id = op.operation_id.as_deref().unwrap_or("<unknown>");
+        let Some(body) = op
+            .request_body
+            .as_ref()
+            .and_then(|body| self.item(&spec.components, body))
+        else {
+            return vec![];
+        };
+
+        body.content
+            .iter()
+            .filter_map(|(content_type, media_type)| {
+                let schema = media_type.schema.as_ref()?;
+                if !self.item(&spec.components, schema)?.schema_data.nullable {
+                    return None;
+                }
+                const FIX: &str = "make the request body optional instead, or accept \
+                    an empty object.";
+                if let Some(name) = schema_reference_name(schema) {
+                    return Some(
+                        Diagnostic::new(
+                            &lints::NULLABLE_REQUEST_BODY,
+                            format!(
+                                "The schema {} is used for {} request bodies but is \
+                                nullable; {}",
+                                name, content_type, FIX,
+                            ),
+                        )
+                        .at(Location::named("schemas", name)),
+                    );
+                }
+                Some(Diagnostic::new(
+                    &lints::NULLABLE_REQUEST_BODY,
+                    format!(
+                        "The {} request body for {} is an inline schema, which is \
+                        nullable; {}",
+                        content_type, operation_id, FIX,
+                    ),
+                ))
+            })
+            .collect()
+    }
+
+    fn validate_success_content(
+        &self,
+        spec: &OpenAPI,
+        path_method_op: (&str, &str, &Operation),
+    ) -> Vec<Diagnostic> {
+        let (_, method, op) = path_method_op;
+        let operation_id = op.operation_id.as_deref().unwrap_or("<unknown>");
+
+        if method != "get" {
+            return vec![];
+        }
+
+        op.responses
+            .responses
+            .iter()
+            .filter(|(status, _)| match status {
+                StatusCode::Code(code) => (200..=299).contains(code) && *code != 204,
+                StatusCode::Range(range) => *range == 2,
+            })
+            .filter_map(|(status, response)| {
+                let response = self.item(&spec.components, response)?;
+                response.content.is_empty().then(|| {
+                    Diagnostic::new(
+                        &lints::MISSING_CONTENT,
+                        format!(
+                            "The {} response for the GET operation {} has no \
+                            content; use 204 if no content is intended.",
+                            status, operation_id,
+                        ),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    fn validate_binary_encoding(&self, spec: &OpenAPI, op: &Operation) -> Vec<Diagnostic> {
+        let operation_id = op.operation_id.as_deref().unwrap_or("<unknown>");
+
+        let request = op
+            .request_body
+            .as_ref()
+            .and_then(|body| self.item(&spec.components, body))
+            .map(|body| ("request body".to_string(), &body.content));
+        let responses = op
+            .responses
+            .responses
+            .iter()
+            .filter_map(|(status, response)| {
+                let response = self.item(&spec.components, response)?;
+                Some((format!("{} response", status), &response.content))
+            });
+
+        request
+            .into_iter()
+            .chain(responses)
+            .flat_map(|(what, content)| {
+                content
+                    .iter()
+                    .filter_map(move |(content_type, media_type)| {
+                        let schema = self.item(&spec.components, media_type.schema.as_ref()?)?;
+                        let json =
+                            content_type == "application/json" || content_type.ends_with("+json");
+                        let streaming = !json
+                            && !content_type.starts_with("text/")
+                            && !content_type.starts_with("multipart/")
+                            && content_type != "application/x-www-form-urlencoded";
+
+                        if json
+                            && schema
+                                .walk()
+                                .any(|walked| string_format(walked.schema) == Some("binary"))
+                        {
+                            Some(Diagnostic::new(
+                                &lints::BINARY_IN_JSON,
+                                format!(
+                                    "The {} {} for {} contains a string with format binary; \
+                                    JSON can't carry raw bytes, so use format byte (base64) \
+                                    instead.",
+                                    content_type, what, operation_id,
+                                ),
+                            ))
+                        } else if streaming && string_format(schema) == Some("byte") {
+                            Some(Diagnostic::new(
+                                &lints::BYTE_IN_RAW,
+                                format!(
+                                    "The {} {} for {} is a string with format byte (base64), \
+                                    but {} content is sent as raw bytes; use format binary \
+                                    instead.",
+                                    content_type, what, operation_id, content_type,
+                                ),
+                            ))
+                        } else {
+                            None
+                        }
+                    })
+            })
+            .collect()
+    }
+
+    fn validate_redirects(&self, op: &Operation) -> Vec<Diagnostic> {
+        let operation_id = op.operation_id.as_deref().unwrap_or("<unknown>");
+        if self
+            .config
+            .redirect_operations
+            .iter()
+            .any(|allowed| allowed == operation_id)
+        {
+            return vec![];
+        }
+
+        op.responses
+            .responses
+            .keys()
+            .filter(|status| match status {
+                StatusCode::Code(code) => (300..=399).contains(code),
+                StatusCode::Range(range) => *range == 3,
+            })
+            .map(|status| {
+                Diagnostic::new(
+                    &lints::REDIRECT,
+                    format!(
+                        "The operation {} has a {} (redirect) response; generated SDKs \
+                        follow or surface redirects inconsistently.",
+                        operation_id, status,
+                    ),
+                )
+            })
+            .collect()
+    }
+
+    fn validate_retry_after(&self, spec: &OpenAPI, op: &Operation) -> Vec<Diagnostic> {
+        let operation_id = op.operation_id.as_deref().unwrap_or("<unknown>");
+
+        op.responses
+            .responses
+            .iter()
+            .filter(|(status, _)| {
+                matches!(status, StatusCode::Code(code)
+                    if self.config.retry_after_statuses.contains(code))
+            })
+            .filter_map(|(status, response)| {
+                let response = self.item(&spec.components, response)?;
+                let declared = response
+                    .headers
+                    .keys()
+                    .any(|name| name.eq_ignore_ascii_case("Retry-After"));
+                (!declared).then(|| {
+                    Diagnostic::new(
+                        &lints::RETRY_AFTER,
+                        format!(
+                            "The {} response for {} does not declare a Retry-After \
+                            header.",
+                            status, operation_id,
+                        ),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    fn validate_location(
+        &self,
+        spec: &OpenAPI,
+        path_method_op: (&str, &str, &Operation),
+    ) -> Vec<Diagnostic> {
+        let (_, method, op) = path_method_op;
+        if method != "post" {
+            return Vec::new();
+        }
+        let operation_id = op.operation_id.as_deref().unwrap_or("<unknown>");
+
+        op.responses
+            .responses
+            .iter()
+            .filter(|(status, _)| {
+                matches!(status, StatusCode::Code(code)
+                    if self.config.location_statuses.contains(code))
+            })
+            .filter_map(|(status, response)| {
+                let response = self.item(&spec.components, response)?;
+                if response.extensions.get("x-location") == Some(&serde_json::Value::Bool(false)) {
+                    return None;
+                }
+                let Some((_, header)) = response
+                    .headers
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case("Location"))
+                else {
+                    return Some(Diagnostic::new(
+                        &lints::MISSING_LOCATION,
+                        format!(
+                            "The {} response for {} does not declare a Location \
+                            header.",
+                            status, operation_id,
+                        ),
+                    ));
+                };
+
+                // The header names the path of the created resource either
+                // as a template or by example; one that does neither can't be
+                // checked.
+                let header = self.item(&spec.components, header)?;
+                let target = match header.extensions.get("x-path-template") {
+                    Some(template) => template.as_str()?,
+                    None => url_path(header.example.as_ref()?.as_str()?),
+                };
+                let found = spec
+                    .paths
+                    .paths
+                    .keys()
+                    .any(|path| path_matches(path, target));
+                (!found).then(|| {
+                    Diagnostic::new(
+                        &lints::UNKNOWN_LOCATION,
+                        format!(
+                            "The Location header of the {} response for {} points \
+                            at \"{}\", which matches none of the spec's paths.",
+                            status, operation_id, target,
+                        ),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    fn validate_idempotency_key(
+        &self,
+        spec: &OpenAPI,
+        path_method_op: (&str, &str, &Operation),
+    ) -> Option<Diagnostic> {
+        let (path, method, op) = path_method_op;
+        let operation_id = op.operation_id.as_deref().unwrap_or("<unknown>");
+
+        let key = operation_parameters(spec, path, op)
+            .into_iter()
+            .find(|param| is_idempotency_key(param))
+            .map(Parameter::parameter_data_ref);
+
+        let idempotent = op.extensions.get("x-idempotent") == Some(&serde_json::Value::Bool(true))
+            || op
+                .tags
+                .iter()
+                .any(|tag| self.config.idempotent_tags.contains(tag));
+
+        match (method, key) {
+            ("get", Some(_)) => Some(Diagnostic::new(
+                &lints::IDEMPOTENCY_KEY_ON_GET,
+                format!(
+                    "The GET operation {} declares an Idempotency-Key header \
+                    parameter; GET operations are inherently idempotent.",
+                    operation_id,
+                ),
+            )),
+            ("post", None) if idempotent => Some(Diagnostic::new(
+                &lints::MISSING_IDEMPOTENCY_KEY,
+                format!(
+                    "The operation {} is idempotent but does not declare an \
+                    Idempotency-Key header parameter.",
+                    operation_id,
+                ),
+            )),
+            ("post", Some(key)) if idempotent => {
+                let is_uuid = match &key.format {
+                    ParameterSchemaOrContent::Schema(schema) => matches!(
+                        self.item(&spec.components, schema),
+                        Some(Schema {
+                            schema_kind: SchemaKind::Type(Type::String(StringType {
+                                format: VariantOrUnknownOrEmpty::Unknown(format),
+                                ..
+                            })),
+                            ..
+                        }) if format == "uuid"
+                    ),
+                    ParameterSchemaOrContent::Content(_) => false,
+                };
+                (!is_uuid).then(|| {
+                    Diagnostic::new(
+                        &lints::IDEMPOTENCY_KEY_FORMAT,
+                        format!(
+                            "The Idempotency-Key header parameter of {} should be a \
+                            string with format uuid.",
+                            operation_id,
+                        ),
+                    )
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn validate_named_schema(&self, type_name: &str) -> Option<Diagnostic> {
+        let pascal = self.suggest_rename(type_name, Target::Type)?;
+
+        Some(
+            Diagnostic::new(
+                &lints::TYPE_NAME,
+                format!(
+                    "The type \"{}\" has a name that is not {}; to rename it add \
+                    #[serde(rename = \"{}\")]",
+                    type_name,
+                    self.case(Target::Type),
+                    pascal,
+                ),
+            )
+            .with_suggestion(Suggestion::rename(Target::Type, type_name, &pascal)),
+        )
+    }
+
+    fn validate_schema_title(&self, type_name: &str, schema: &Schema) -> Option<Diagnostic> {
+        let title = schema.schema_data.title.as_deref()?;
+        (title.to_pascal_case() != type_name.to_pascal_case()).then(|| {
+            Diagnostic::new(
+                &lints::SCHEMA_TITLE,
+                format!(
+                    "The type \"{}\" has the title \"{}\"; generators that name \
+                    types after titles will call it something else. Remove the title \
+                    or make it match the name.",
+                    type_name, title,
+                ),
+            )
+        })
+    }
+
+    fn validate_schema_title_text(&self, type_name: &str, schema: &Schema) -> Option<Diagnostic> {
+        let title = schema.schema_data.title.as_deref()?.trim();
+        let description = schema.schema_data.description.as_deref().map(str::trim);
+        if description == Some(title) {
+            return Some(Diagnostic::new(
+                &lints::SCHEMA_TITLE_DESCRIPTION,
+                format!(
+                    "The type \"{}\" has a title identical to its description, \
+                    so documentation shows the text twice. Remove the title.",
+                    type_name,
+                ),
+            ));
+        }
+        let max = self.config.max_title_length?;
+        let length = title.chars().count();
+        (length > max).then(|| {
+            Diagnostic::new(
+                &lints::LONG_SCHEMA_TITLE,
+                format!(
+                    "The type \"{}\" has a title of {} characters, more than the \
+                    {} that make a reasonable heading: \"{}\". Shorten it, moving \
+                    the detail into the description.",
+                    type_name, length, max, title,
+                ),
+            )
+        })
+    }
+
+    fn validate_singular_name(&self, type_name: &str) -> Option<Diagnostic> {
+        if !self.config.singular_type_names {
+            return None;
+        }
+
+        // Pages of results are necessarily plural.
+        let words = type_name.trim_end_matches("ResultsPage").to_snake_case();
+        let plural = words.split('_').find(|word| is_plural(word))?;
+
+        Some(Diagnostic::new(
+            &lints::PLURAL_TYPE_NAME,
+            format!(
+                "The type \"{}\" has a name with the plural \"{}\"; name types in \
+                the singular and use arrays for collections.",
+                type_name, plural,
+            ),
+        ))
+    }
+
+    fn validate_schema_docs(&self, schema: &Schema) -> Vec<Diagnostic> {
+        let title = schema
+            .schema_data
+            .title
+            .as_ref()
+            .and_then(|s| check_doc_string(s));
+        let description = schema
+            .schema_data
+            .description
+            .as_ref()
+            .and_then(|s| check_doc_string(s));
+        let html = [&schema.schema_data.title, &schema.schema_data.description]
+            .into_iter()
+            .flatten()
+            .flat_map(|s| {
+                self.check_html(s)
+                    .into_iter()
+                    .chain(self.check_description_style(s))
+            });
+        [title, description]
+            .into_iter()
+            .flatten()
+            .chain(html)
+            .collect()
+    }
+
+    fn check_description_style(&self, s: &str) -> Vec<Diagnostic> {
+        let mut problems = Vec::new();
+
+        if s.trim() != s {
+            problems.push("it has leading or trailing whitespace".to_string());
+        }
+
+        // Headings, tables, and long lines within code blocks are just code.
+        let mut in_code = false;
+        let prose = s.lines().filter(|line| {
+            if line.trim_start().starts_with("```") {
+                in_code = !in_code;
+                return false;
+            }
+            !in_code
+        });
+
+        let mut heading = None;
+        let mut columns = 0;
+        let mut longest = 0;
+        for line in prose {
+            let trimmed = line.trim();
+            let level = trimmed.chars().take_while(|c| *c == '#').count();
+            if level > 0 && trimmed[level..].starts_with(' ') {
+                heading = Some(heading.map_or(level, |heading: usize| heading.min(level)));
+            }
+            if trimmed.starts_with('|') {
+                let cells = trimmed.trim_matches('|').split('|').count();
+                columns = columns.max(cells);
+            }
+            longest = longest.max(line.chars().count());
+        }
+
+        match heading {
+            Some(level) if level < self.config.min_heading_level => problems.push(format!(
+                "it has a level {} heading, but headings above level {} break \
+                the hierarchy of the documentation page",
+                level, self.config.min_heading_level,
+            )),
+            _ => {}
+        }
+        match self.config.max_table_columns {
+            Some(max) if columns > max => problems.push(format!(
+                "it has a table with {} columns, more than the {} that fit on \
+                the documentation page",
+                columns, max,
+            )),
+            _ => {}
+        }
+        match self.config.max_description_line_length {
+            Some(max) if longest > max => problems.push(format!(
+                "it has a line of {} characters, more than the maximum of {}",
+                longest, max,
+            )),
+            _ => {}
+        }
+
+        if problems.is_empty() {
+            return vec![];
+        }
+        vec![Diagnostic::new(
+            &lints::DESCRIPTION_STYLE,
+            format!(
+                "Description in external interface doesn't follow the \
+                documentation style ({}): {}",
+                problems.join("; "),
+                s,
+            ),
+        )]
+    }
+
+    fn check_html(&self, s: &str) -> Vec<Diagnostic> {
+        lazy_static::lazy_static! {
+            static ref CODE: Regex = Regex::new(r"(?s)```.*?```|`[^`]*`").unwrap();
+            static ref TAG: Regex =
+                Regex::new(r"<(/?)([[:alpha:]][[:alnum:]-]*)(\s[^<>]*)?/?>").unwrap();
+        }
+
+        // HTML within code spans and blocks is rendered literally.
+        let prose = CODE.replace_all(s, "");
+
+        let tags = TAG
+            .captures_iter(&prose)
+            .map(|captures| captures[2].to_lowercase())
+            .filter(|tag| !self.config.allowed_html_tags.contains(tag))
+            // A <summary> only appears within a <details>; report the pair
+            // once.
+            .map(|tag| match tag.as_str() {
+                "summary" => "details".to_string(),
+                _ => tag,
+            })
+            .collect::<BTreeSet<_>>();
+
+        tags.into_iter()
+            .map(|tag| match tag.as_str() {
+                "details" => Diagnostic::new(
+                    &lints::HTML_IN_DESCRIPTION,
+                    format!(
+                        "HTML found in external interface: <{}> in {}\nprogenitor \
+                        already places a <details> block with the JSON schema in the \
+                        documentation it generates, and disclosure elements nested \
+                        within it render poorly.",
+                        tag, s,
+                    ),
+                ),
+                _ => Diagnostic::new(
+                    &lints::HTML_IN_DESCRIPTION,
+                    format!(
+                        "HTML found in external interface: <{}> in {}\nOur \
+                        documentation renderer and SDK generators escape HTML \
+                        inconsistently; use Markdown instead.",
+                        tag, s,
+                    ),
+                ),
+            })
+            .collect()
+    }
+}
+
+/// Whether a schema, if it weren't a named component, would need a name
+/// synthesized for the type generated from it: an object with properties, a
+/// string enumeration (other than a single-valued one, typically an enum tag),
+/// or a union or combination of subschemas.
+fn is_anonymous_type(schema: &Schema) -> bool {
+    match &schema.schema_kind {
+        SchemaKind::Type(Type::Object(obj)) => !obj.properties.is_empty(),
+        SchemaKind::Type(Type::String(string)) => string.enumeration.len() > 1,
+        SchemaKind::Type(_) => false,
+        SchemaKind::OneOf { .. } | SchemaKind::AnyOf { .. } => true,
+        // An allOf of a single schema is just a wrapper for it.
+        SchemaKind::AllOf { all_of } => all_of.len() > 1,
+        SchemaKind::Not { .. } => false,
+        SchemaKind::Any(any) => {
+            !any.properties.is_empty() || !any.one_of.is_empty() || !any.any_of.is_empty()
+        }
+    }
+}
+
+/// The format of a string schema, if it has one.
+fn string_format(schema: &Schema) -> Option<&str> {
+    match &schema.schema_kind {
+        SchemaKind::Type(Type::String(StringType { format, .. })) => match format {
+            VariantOrUnknownOrEmpty::Item(StringFormat::Date) => Some("date"),
+            VariantOrUnknownOrEmpty::Item(StringFormat::DateTime) => Some("date-time"),
+            VariantOrUnknownOrEmpty::Item(StringFormat::Password) => Some("password"),
+            VariantOrUnknownOrEmpty::Item(StringFormat::Byte) => Some("byte"),
+            VariantOrUnknownOrEmpty::Item(StringFormat::Binary) => Some("binary"),
+            VariantOrUnknownOrEmpty::Unknown(format) => Some(format),
+            VariantOrUnknownOrEmpty::Empty => None,
+        },
+        _ => None,
+    }
+}
+
+/// The well-known formats of strings and numbers, as they are spelled in the
+/// OpenAPI specification and the JSON Schema drafts on which it draws, along
+/// with the sized integers that Rust generators use.
+const FORMATS: &[&str] = &[
+    "date",
+    "date-time",
+    "time",
+    "duration",
+    "password",
+    "byte",
+    "binary",
+    "uuid",
+    "uri",
+    "uri-reference",
+    "uri-template",
+    "email",
+    "hostname",
+    "ip",
+    "ipv4",
+    "ipv6",
+    "regex",
+    "int8",
+    "int16",
+    "int32",
+    "int64",
+    "uint8",
+    "uint16",
+    "uint32",
+    "uint64",
+    "float",
+    "double",
+];
+
+/// The format of a schema, if it has one that openapiv3 doesn't recognize (it
+/// recognizes only the formats named by OpenAPI, spelled exactly).
+fn unrecognized_format(schema: &Schema) -> Option<&str> {
+    match &schema.schema_kind {
+        SchemaKind::Type(Type::String(StringType {
+            format: VariantOrUnknownOrEmpty::Unknown(format),
+            ..
+        }))
+        | SchemaKind::Type(Type::Integer(IntegerType {
+            format: VariantOrUnknownOrEmpty::Unknown(format),
+            ..
+        }))
+        | SchemaKind::Type(Type::Number(NumberType {
+            format: VariantOrUnknownOrEmpty::Unknown(format),
+            ..
+        })) => Some(format),
+        SchemaKind::Any(any) => any.format.as_deref(),
+        _ => None,
+    }
+}
+
+/// The well-known format of which `format` is a miscased or misspelled form
+/// (e.g. "date-time" for "Date_Time"), or `None` if it is well-known already or
+/// is not a form of one.
+fn canonical_format(format: &str) -> Option<&'static str> {
+    let normalized = format.to_lowercase().replace('_', "-");
+    FORMATS
+        .iter()
+        .find(|known| **known == normalized)
+        .filter(|known| **known != format)
+        .copied()
+}
+
+/// The resource on which the operations at a path act: the first literal
+/// segment of the path, after any version (e.g. `v1`).
+fn path_resource(path: &str) -> Option<&str> {
+    lazy_static::lazy_static! {
+        static ref VERSION: Regex = Regex::new(r"^v[0-9]+$").unwrap();
+    }
+    path.split('/')
+        .find(|segment| !segment.is_empty() && !VERSION.is_match(segment))
+        .filter(|segment| !segment.starts_with('{'))
+}
+
+/// The path of a URL (e.g. `/v1/projects/p` of
+/// `https://oxide.example/v1/projects/p?limit=1`), which may be a path already.
+fn url_path(url: &str) -> &str {
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("/", |start| &rest[start..]),
+        None => url,
+    };
+    path.split(['?', '#']).next().unwrap_or(path)
+}
+
+/// Whether the path template `path` matches `target`, a path or another
+/// template: a parameter of the template matches any segment of the other,
+/// however the parameter is named.
+fn path_matches(path: &str, target: &str) -> bool {
+    let is_parameter = |segment: &str| segment.starts_with('{') && segment.ends_with('}');
+    let segments = path.trim_end_matches('/').split('/');
+    let targets = target.trim_end_matches('/').split('/');
+    segments.clone().count() == targets.clone().count()
+        && segments.zip(targets).all(|(segment, target)| {
+            segment == target || (is_parameter(segment) && !target.is_empty())
+        })
+}
+
+/// Whether a schema is a string with no pattern or enumerated values (as a
+/// Name or an enumeration would have), though it may have a format.
+fn is_free_form_string(schema: &Schema) -> bool {
+    matches!(
+        &schema.schema_kind,
+        SchemaKind::Type(Type::String(StringType {
+            pattern: None,
+            enumeration,
+            ..
+        })) if enumeration.is_empty()
+    )
+}
+
+/// A rough guess as to whether an English word is plural.
+fn is_plural(word: &str) -> bool {
+    word.len() > 3
+        && word.ends_with('s')
+        && !["ss", "us", "is", "as"]
+            .iter()
+            .any(|suffix| word.ends_with(suffix))
+}
+
+/// A rough guess as to the singular of a plural English word (or of the last
+/// word of a `snake_case` phrase).
+fn singular(word: &str) -> String {
+    if let Some(stem) = word.strip_suffix("ies") {
+        format!("{}y", stem)
+    } else if ["sses", "xes", "ches", "shes"]
+        .iter()
+        .any(|suffix| word.ends_with(suffix))
+    {
+        word[..word.len() - 2].to_string()
+    } else {
+        word.strip_suffix('s').unwrap_or(word).to_string()
+    }
+}
+
+/// The schemas (other than references) of an operation's parameters, request
+/// body, and responses, without those nested within them.
+fn operation_schemas<'a>(spec: &'a OpenAPI, op: &'a Operation) -> Vec<&'a Schema> {
+    let parameters = op
+        .parameters
+        .iter()
+        .filter_map(|param| param.item(&spec.components))
+        .filter_map(|param| match &param.parameter_data_ref().format {
+            ParameterSchemaOrContent::Schema(schema) => schema.as_item(),
+            ParameterSchemaOrContent::Content(_) => None,
+        });
+    let bodies = op
+        .request_body
+        .iter()
+        .filter_map(|body| body.as_item())
+        .flat_map(|body| body.content.values());
+    let responses = op
+        .responses
+        .responses
+        .values()
+        .chain(&op.responses.default)
+        .filter_map(|response| response.as_item())
+        .flat_map(|response| response.content.values());
+
+    parameters
+        .chain(
+            bodies
+                .chain(responses)
+                .filter_map(|media| media.schema.as_ref()?.as_item()),
+        )
+        .collect()
+}
+
+/// A size in bytes, in KiB.
+fn kib(bytes: usize) -> String {
+    format!("{:.1} KiB", bytes as f64 / 1024.0)
+}
+
+/// The longest excerpt of a schema that a message quotes.
+const EXCERPT_LENGTH: usize = 80;
+
+/// A schema (or part of one) as compact JSON for a message, cut short if it's
+/// long; [`pretty`] gives the whole of it, for the detail.
+fn excerpt(value: &impl serde::Serialize) -> String {
+    let json = serde_json::to_string(value).unwrap_or_default();
+    match json.char_indices().nth(EXCERPT_LENGTH) {
+        Some((end, _)) => format!("{}...", &json[..end]),
+        None => json,
+    }
+}
+
+/// A schema (or part of one) as indented JSON.
+fn pretty(value: &impl serde::Serialize) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+/// The name of the component that a `$ref` refers to.
+fn reference_name(reference: &str) -> Option<&str> {
+    reference.rsplit_once('/').map(|(_, name)| name)
+}
+
+/// The name of the component that a schema refers to, if it's a reference.
+fn schema_reference_name(schema: &ReferenceOr<Schema>) -> Option<&str> {
+    match schema {
+        ReferenceOr::Reference { reference } => reference_name(reference),
+        ReferenceOr::Item(_) => None,
+    }
+}
+
+/// The schema of the JSON body of the first successful response of an
+/// operation.
+fn success_schema<'a>(spec: &'a OpenAPI, op: &'a Operation) -> Option<&'a ReferenceOr<Schema>> {
+    op.responses
+        .responses
+        .iter()
+        .filter(|(status, _)| matches!(status, StatusCode::Code(200..=299)))
+        .filter_map(|(_, response)| response.item(&spec.components))
+        .find_map(|response| response.content.get("application/json")?.schema.as_ref())
+}
+
+/// The name of the schema of the JSON body of the first successful response
+/// of an operation, if that schema is a reference.
+fn success_schema_name<'a>(spec: &'a OpenAPI, op: &'a Operation) -> Option<&'a str> {
+    match success_schema(spec, op)? {
+        ReferenceOr::Reference { reference } => reference_name(reference),
+        ReferenceOr::Item(_) => None,
+    }
+}
+
+/// Whether a schema describes a single string, number, or boolean (possibly
+/// by way of an `allOf` wrapping a single such schema).
+fn is_scalar(spec: &OpenAPI, schema: &Schema) -> bool {
+    match &schema.schema_kind {
+        SchemaKind::Type(
+            Type::String(_) | Type::Number(_) | Type::Integer(_) | Type::Boolean(_),
+        ) => true,
+        SchemaKind::AllOf { all_of } if all_of.len() == 1 => all_of[0]
+            .item(&spec.components)
+            .is_some_and(|schema| is_scalar(spec, schema)),
+        _ => false,
+    }
+}
+
+/// Every `$ref` in a JSON value, with the JSON pointer to the object that
+/// holds it.
+fn find_references<'a>(
+    value: &'a serde_json::Value,
+    pointer: String,
+    out: &mut Vec<(String, &'a str)>,
+) {
+    match value {
+        serde_json::Value::Object(object) => {
+            if let Some(serde_json::Value::String(reference)) = object.get("$ref") {
+                out.push((pointer.clone(), reference));
+            }
+            for (key, value) in object {
+                find_references(value, format!("{}/{}", pointer, fields::escape(key)), out);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for (index, value) in items.iter().enumerate() {
+                find_references(value, format!("{}/{}", pointer, index), out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Whether a description says nothing that the name doesn't, e.g. "Projects"
+/// for the tag `projects`.
+fn is_trivial_description(name: &str, description: Option<&str>) -> bool {
+    let words = |text: &str| {
+        text.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect::<String>()
+    };
+    description.is_none_or(|description| {
+        let description = words(description);
+        description.is_empty() || description == words(name)
+    })
+}
+
+/// How to refer to an element in a message.
+pub(crate) fn element_name(element: Element) -> &'static str {
+    match element {
+        Element::Operation => "operation",
+        Element::Tag => "tag",
+        Element::Schema => "schema",
+        Element::Parameter => "parameter",
+    }
+}
+
+/// The value of `in` for a parameter.
+fn parameter_location(param: &Parameter) -> &'static str {
+    match param {
+        Parameter::Query { .. } => "query",
+        Parameter::Header { .. } => "header",
+        Parameter::Path { .. } => "path",
+        Parameter::Cookie { .. } => "cookie",
+    }
+}
+
+/// The parameters of an operation including those declared on its path item,
+/// less those that the operation overrides (by declaring a parameter with the
+/// same name and location).
+fn operation_parameters<'a>(
+    spec: &'a OpenAPI,
+    path: &str,
+    op: &'a Operation,
+) -> Vec<&'a Parameter> {
+    let key = |param: &Parameter| {
+        (
+            param.parameter_data_ref().name.clone(),
+            parameter_location(param),
+        )
+    };
+    let op_params = op
+        .parameters
+        .iter()
+        .filter_map(|param| param.item(&spec.components))
+        .collect::<Vec<_>>();
+    let overridden = op_params
+        .iter()
+        .map(|param| key(param))
+        .collect::<BTreeSet<_>>();
+    let path_params = spec
+        .paths
+        .paths
+        .get(path)
+        .and_then(ReferenceOr::as_item)
+        .into_iter()
+        .flat_map(|path_item| path_item.parameters.iter())
+        .filter_map(|param| param.item(&spec.components))
+        .filter(|param| !overridden.contains(&key(param)));
+
+    path_params.chain(op_params).collect()
+}
+
+fn check_doc_string(s: &str) -> Option<Diagnostic> {
+    lazy_static::lazy_static! {
+        static ref PATH: Regex = Regex::new(r#"[[:alnum:]]::[[:alpha:]]"#).unwrap();
+        static ref LINK: Regex = Regex::new(r#"\][^(]"#).unwrap();
+    }
+
+    (PATH.is_match(s) || LINK.is_match(s)).then(|| {
+        Diagnostic::new(
+            &lints::RUST_DOCUMENTATION,
+            format!("Rust documentation found in external interface: {}", s),
+        )
+    })
+}
+
+/// If the schema looks like a serde-tagged enum (a `oneOf` or `anyOf` whose
+/// variants are each either a unit variant, an externally tagged variant, or
+/// an internally or adjacently tagged variant), return the tag value for each
+/// of its variants.
+fn enum_variant_tags<'a>(spec: &'a OpenAPI, schema: &'a Schema) -> Option<Vec<&'a str>> {
+    let variants = match &schema.schema_kind {
+        SchemaKind::OneOf { one_of: ofs } | SchemaKind::AnyOf { any_of: ofs } => ofs
+            .iter()
+            .map(|variant| variant.item(&spec.components))
+            .collect::<Option<Vec<_>>>()?,
+        _ => return None,
+    };
+
+    internal_tags(spec, &variants).or_else(|| external_tags(&variants))
+}
+
+/// Internally and adjacently tagged enums have a common, required property
+/// whose value is a single-valued string enumeration.
+fn internal_tags<'a>(spec: &'a OpenAPI, variants: &[&'a Schema]) -> Option<Vec<&'a str>> {
+    let tag_value = |variant: &'a Schema, tag_name: &str| -> Option<&'a str> {
+        let SchemaKind::Type(Type::Object(obj)) = &variant.schema_kind else {
+            return None;
+        };
+        if !obj.required.iter().any(|required| required == tag_name) {
+            return None;
+        }
+        match &obj
+            .properties
+            .get(tag_name)?
+            .item(&spec.components)?
+            .schema_kind
+        {
+            SchemaKind::Type(Type::String(StringType { enumeration, .. }))
+                if enumeration.len() == 1 =>
+            {
+                enumeration[0].as_deref()
+            }
+            _ => None,
+        }
+    };
+
+    let SchemaKind::Type(Type::Object(first)) = &variants.first()?.schema_kind else {
+        return None;
+    };
+    first.properties.keys().find_map(|tag_name| {
+        variants
+            .iter()
+            .map(|variant| tag_value(variant, tag_name))
+            .collect()
+    })
+}
+
+/// Externally tagged enums have string enumerations for unit variants and
+/// objects with a single, required property for other variants.
+fn external_tags<'a>(variants: &[&'a Schema]) -> Option<Vec<&'a str>> {
+    variants.iter().try_fold(Vec::new(), |mut tags, variant| {
+        match &variant.schema_kind {
+            SchemaKind::Type(Type::String(StringType { enumeration, .. }))
+                if !enumeration.is_empty() =>
+            {
+                for value in enumeration {
+                    tags.push(value.as_deref()?);
+                }
+            }
+            SchemaKind::Type(Type::Object(obj))
+                if obj.properties.len() == 1 && obj.required.len() == 1 =>
+            {
+                let (prop_name, _) = obj.properties.first()?;
+                (obj.required[0] == *prop_name).then_some(())?;
+                tags.push(prop_name);
+            }
+            _ => return None,
+        }
+        Some(tags)
+    })
+}
+
+/// The JSON Schema type name of a value; `None` for null, which may appear in
+/// the enumeration of a nullable schema of any type.
+fn json_type(value: &serde_json::Value) -> Option<&'static str> {
+    match value {
+        serde_json::Value::Null => None,
+        serde_json::Value::Bool(_) => Some("boolean"),
+        serde_json::Value::Number(n) if n.is_f64() => Some("number"),
+        serde_json::Value::Number(_) => Some("integer"),
+        serde_json::Value::String(_) => Some("string"),
+        serde_json::Value::Array(_) => Some("array"),
+        serde_json::Value::Object(_) => Some("object"),
+    }
+}
+
+/// Whether the parameter is the `Idempotency-Key` header.
+fn is_idempotency_key(param: &Parameter) -> bool {
+    matches!(
+        param,
+        Parameter::Header { parameter_data, .. }
+            if parameter_data.name.eq_ignore_ascii_case("Idempotency-Key")
+    )
+}
+
+fn is_permissive(any: &AnySchema) -> bool {
+    matches!(
+        any,
+        AnySchema {
+            typ: None,
+            pattern: None,
+            multiple_of: None,
+            exclusive_minimum: None,
+            exclusive_maximum: None,
+            minimum: None,
+            maximum: None,
+            properties,
+            required,
+            additional_properties: None,
+            min_properties: None,
+            max_properties: None,
+            items: None,
+            min_items: None,
+            max_items: None,
+            unique_items: None,
+            enumeration,
+            format: None,
+            min_length: None,
+            max_length: None,
+            one_of,
+            all_of,
+            any_of,
+            not: None,
+        } if properties.is_empty()
+            && required.is_empty()
+            && enumeration.is_empty()
+            && one_of.is_empty()
+            && all_of.is_empty()
+            && any_of.is_empty()
+    )
+}
+
+trait ReferenceOrExt<T: ComponentLookup> {
+    /// The item, or the reference to it.
+    fn split(&self) -> Result<&T, &str>;
+
+    fn item<'a>(&'a self, components: &'a Option<Components>) -> Option<&'a T> {
+        match self.split() {
+            Ok(item) => Some(item),
+            Err(reference) => resolve_reference(reference, components),
+        }
+    }
+}
+trait ComponentLookup: Sized {
+    /// The name of the section of the components in which these are found.
+    const SECTION: &'static str;
+
+    fn get_components(components: &Components) -> &IndexMap<String, ReferenceOr<Self>>;
+}
+
+impl<T: ComponentLookup> ReferenceOrExt<T> for openapiv3::ReferenceOr<T> {
+    fn split(&self) -> Result<&T, &str> {
+        match self {
+            ReferenceOr::Item(item) => Ok(item),
+            ReferenceOr::Reference { reference } => Err(reference),
+        }
+    }
+}
+
+impl<T: ComponentLookup> ReferenceOrExt<T> for openapiv3::ReferenceOr<Box<T>> {
+    fn split(&self) -> Result<&T, &str> {
+        match self {
+            ReferenceOr::Item(item) => Ok(item.as_ref()),
+            ReferenceOr::Reference { reference } => Err(reference),
+        }
+    }
+}
+
+/// The component to which a reference refers, or `None` if there isn't one;
+/// such references are reported by [`Rules::validate_references`].
+fn resolve_reference<'a, T: ComponentLookup>(
+    reference: &str,
+    components: &'a Option<Components>,
+) -> Option<&'a T> {
+    let items = T::get_components(components.as_ref()?);
+    items
+        .get_index(resolve_index(reference, items)?)?
+        .1
+        .as_item()
+}
+
+/// The index among `items` of the component to which a reference refers.
+fn resolve_index<T>(reference: &str, items: &IndexMap<String, ReferenceOr<T>>) -> Option<usize> {
+    let mut reference = reference;
+    // A component may itself be a reference; a cycle of them resolves to
+    // nothing rather than recursing forever.
+    for _ in 0..=items.len() {
+        let (_, key) = reference.rsplit_once('/')?;
+        match items.get_full(key)? {
+            (index, _, ReferenceOr::Item(_)) => return Some(index),
+            (_, _, ReferenceOr::Reference { reference: next }) => reference = next,
+        }
+    }
+    None
+}
+
+impl ComponentLookup for Parameter {
+    const SECTION: &'static str = "parameters";
+
+    fn get_components(components: &Components) -> &IndexMap<String, ReferenceOr<Self>> {
+        &components.parameters
+    }
+}
+
+impl ComponentLookup for Schema {
+    const SECTION: &'static str = "schemas";
+
+    fn get_components(components: &Components) -> &IndexMap<String, ReferenceOr<Self>> {
+        &components.schemas
+    }
+}
+
+impl ComponentLookup for Header {
+    const SECTION: &'static str = "headers";
+
+    fn get_components(components: &Components) -> &IndexMap<String, ReferenceOr<Self>> {
+        &components.headers
+    }
+}
+
+impl ComponentLookup for RequestBody {
+    const SECTION: &'static str = "requestBodies";
+
+    fn get_components(components: &Components) -> &IndexMap<String, ReferenceOr<Self>> {
+        &components.request_bodies
+    }
+}
+
+impl ComponentLookup for Response {
+    const SECTION: &'static str = "responses";
+
+    fn get_components(components: &Components) -> &IndexMap<String, ReferenceOr<Self>> {
+        &components.responses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use heck::ToSnakeCase;
+
+    use crate::{
+        convert_3_1,
+        naming::{Case, Target},
+        sort_components, validate, validate_document, validate_external, validate_with_config,
+        validate_with_report, Baseline, Category, Check, Config, ConfigError, Context, Diagnostic,
+        Element, InputError, Lint, Location, Node, Profile, Severity, SourceMap, SpecGraph,
+        Validator,
+    };
+
+    fn render<'a>(findings: impl IntoIterator<Item = &'a Diagnostic>) -> String {
+        findings
+            .into_iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    #[test]
+    fn bad_schema() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+
+        let actual = render(&validate_external(&openapi));
+        expectorate::assert_contents("src/tests/errors.out", &actual);
+    }
+
+    #[test]
+    fn locations() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+
+        let actual = validate_external(&openapi)
+            .iter()
+            .map(|finding| {
+                let location = finding.location();
+                format!(
+                    "{} component={} operation={} pointer={}",
+                    finding.code(),
+                    location.component().unwrap_or("-"),
+                    location.operation_id().unwrap_or("-"),
+                    location.pointer().unwrap_or("-"),
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        expectorate::assert_contents("src/tests/locations.out", &actual);
+    }
+
+    #[test]
+    fn details() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+
+        let findings = validate(&openapi);
+        let finding = findings
+            .iter()
+            .find(|finding| finding.code() == "schema/type-mismatch")
+            .unwrap();
+        let detail = finding.detail().unwrap();
+        assert!(detail.starts_with("this schema's type\n{\n  \"type\": \"string\","));
+        assert!(!finding.to_string().contains(detail));
+        assert!(format!("{:#}", finding).contains(detail));
+        assert_eq!(finding.to_json()["detail"], detail);
+    }
+
+    #[test]
+    fn suggestions() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+
+        let findings = validate(&openapi);
+        let suggestion = findings
+            .iter()
+            .filter_map(Diagnostic::suggestion)
+            .find(|suggestion| suggestion.from() == "projectInstancesPost")
+            .unwrap();
+        assert_eq!(suggestion.target(), Some(Target::OperationId));
+        assert_eq!(suggestion.to(), "project_instances_post");
+        assert_eq!(suggestion.attribute(), None);
+        assert_eq!(
+            suggestion.to_string(),
+            "rename the operation id \"projectInstancesPost\" to \"project_instances_post\"",
+        );
+
+        let finding = findings
+            .iter()
+            .find(|finding| finding.code() == "naming/pascal-case-type")
+            .unwrap();
+        let suggestion = finding.suggestion().unwrap();
+        assert_eq!(suggestion.to(), "FakeIdSortMode");
+        assert_eq!(
+            finding.to_json()["suggestion"]["attribute"],
+            "#[serde(rename = \"FakeIdSortMode\")]",
+        );
+
+        let finding = findings
+            .iter()
+            .find(|finding| finding.code() == "schema/format-case")
+            .unwrap();
+        let suggestion = finding.suggestion().unwrap();
+        assert_eq!(suggestion.target(), None);
+        assert_eq!(suggestion.to_string(), "replace \"UUID\" with \"uuid\"");
+        assert_eq!(finding.to_json()["suggestion"]["kind"], "replace");
+    }
+
+    #[test]
+    fn fixes() {
+        let mut document = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+        let openapi = serde_json::from_value(serde_json::Value::clone(&document)).unwrap();
+        let findings = validate(&openapi);
+        let fixable = findings
+            .iter()
+            .filter(|finding| finding.suggestion().is_some())
+            .count();
+
+        let fixes = crate::apply_fixes(&mut document, findings);
+        assert_eq!(fixes.fixed().len(), fixable);
+        assert!(fixes.unfixed().iter().all(|f| f.suggestion().is_none()));
+        assert_eq!(
+            document.pointer("/components/schemas/Things/properties/my_id/format"),
+            Some(&serde_json::json!("uuid")),
+        );
+        assert!(document
+            .pointer("/components/schemas/FakeIdSortMode")
+            .is_some());
+
+        // What's left is what couldn't be fixed, and nothing new.
+        let openapi = serde_json::from_value(document).unwrap();
+        let codes = |findings: &[Diagnostic]| {
+            let mut codes = findings.iter().map(Diagnostic::code).collect::<Vec<_>>();
+            codes.sort();
+            codes
+        };
+        let remaining = validate(&openapi);
+        assert!(remaining.iter().all(|f| f.suggestion().is_none()));
+        assert!(codes(&remaining)
+            .iter()
+            .all(|code| codes(fixes.unfixed()).contains(code)));
+    }
+
+    #[test]
+    fn serialization() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+        let source = SourceMap::new("errors.json", include_str!("tests/errors.json"));
+
+        let mut findings = validate(&openapi);
+        for finding in &mut findings {
+            source.annotate(finding);
+        }
+        let json = serde_json::to_string(&findings).unwrap();
+        let read = serde_json::from_str::<Vec<Diagnostic>>(&json).unwrap();
+        assert_eq!(read, findings);
+        assert_eq!(
+            serde_json::to_value(&findings[0]).unwrap(),
+            findings[0].to_json()
+        );
+
+        // A check defined elsewhere is known by its code and URL.
+        let custom = serde_json::json!({
+            "code": "acme/resource-name",
+            "severity": "warning",
+            "message": "Resources are named after animals.",
+            "location": { "component": null, "operation_id": null, "pointer": "" },
+            "url": "https://example.com/lints#resource-name",
+        });
+        let finding = serde_json::from_value::<Diagnostic>(custom).unwrap();
+        assert_eq!(finding.code(), "acme/resource-name");
+        assert_eq!(finding.url(), "https://example.com/lints#resource-name");
+        assert_eq!(finding.severity(), Severity::Warning);
+        assert!(serde_json::from_value::<Diagnostic>(serde_json::json!({
+            "code": "acme/resource-name",
+            "severity": "warning",
+            "message": "Resources are named after animals.",
+            "location": { "component": null, "operation_id": null, "pointer": "" },
+        }))
+        .is_err());
+    }
+
+    #[test]
+    fn explain() {
+        let lint = crate::explain("response/trivial-null").unwrap();
+        assert_eq!(lint.rule(), "trivial-null-response");
+        assert!(crate::explain("response/trivial").is_none());
+
+        // Codes are unique and each links to a section of the README.
+        let readme = include_str!("../README.md");
+        let anchors = readme
+            .lines()
+            .filter_map(|line| line.strip_prefix("### "))
+            .map(|heading| heading.to_lowercase().replace(' ', "-"))
+            .collect::<Vec<_>>();
+        let mut codes = std::collections::BTreeSet::new();
+        for lint in crate::lints() {
+            assert!(codes.insert(lint.code()), "duplicate code {}", lint.code());
+            assert!(
+                anchors.iter().any(|anchor| anchor == lint.rule()),
+                "{} has no section in the README",
+                lint.rule(),
+            );
+        }
+    }
+
+    #[test]
+    fn severities() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+        let severities = |config: &Config| {
+            validate_with_config(&openapi, config)
+                .into_iter()
+                .filter(|finding| finding.code() == "request/nullable-required")
+                .map(|finding| finding.severity())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(severities(&Config::default()), [Severity::Warning; 3]);
+        assert_eq!(
+            severities(
+                Config::default().with_severity("request/nullable-required", Severity::Error)
+            ),
+            [Severity::Error; 3],
+        );
+        assert!(Severity::Advice < Severity::Warning && Severity::Warning < Severity::Error);
+    }
+
+    #[test]
+    fn builder() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+        let codes = |validator: Validator| {
+            validator
+                .validate(&openapi)
+                .iter()
+                .map(Diagnostic::code)
+                .collect::<std::collections::BTreeSet<_>>()
+        };
+
+        let all = codes(Validator::builder().external(true).build());
+        assert!(all.contains("naming/pascal-case-type"));
+        assert!(all.contains("docs/rust-documentation"));
+
+        let some = codes(
+            Validator::builder()
+                .external(true)
+                .disable("naming/pascal-case-type")
+                .build(),
+        );
+        assert!(!some.contains("naming/pascal-case-type"));
+        assert_eq!(some.len(), all.len() - 1);
+
+        let only = codes(
+            Validator::builder()
+                .disable_all()
+                .enable("naming/pascal-case-type")
+                .build(),
+        );
+        assert_eq!(
+            only.into_iter().collect::<Vec<_>>(),
+            ["naming/pascal-case-type"]
+        );
+
+        let again = codes(
+            Validator::builder()
+                .external(true)
+                .disable("naming/pascal-case-type")
+                .enable_all()
+                .build(),
+        );
+        assert_eq!(again, all);
+    }
+
+    #[test]
+    fn profiles() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+        let with = |profile: Profile| Validator::builder().profile(profile).build();
+
+        assert_eq!(
+            with(Profile::internal()).validate(&openapi),
+            validate(&openapi)
+        );
+        let external = validate_external(&openapi);
+        assert_eq!(with(Profile::external()).validate(&openapi), external);
+
+        // The strict profile finds the same problems, all of them errors.
+        let strict = with(Profile::strict()).validate(&openapi);
+        assert_eq!(strict.len(), external.len());
+        assert!(strict
+            .iter()
+            .all(|finding| finding.severity() == Severity::Error));
+
+        let acme = with(Profile::new("acme").disable_all().enable("docs/html"));
+        assert_eq!(acme.config().profile(), Some("acme"));
+        assert!(acme
+            .validate(&openapi)
+            .iter()
+            .all(|finding| finding.code() == "docs/html"));
+
+        let config = Config::from_toml("profile = \"strict\"\n").unwrap();
+        assert_eq!(config.profile(), Some("strict"));
+        let err = Config::from_toml("profile = \"lax\"\n").unwrap_err();
+        assert!(matches!(err, ConfigError::UnknownProfile(_)));
+    }
+
+    #[test]
+    fn categories() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+        let all = validate(&openapi);
+        let naming = |finding: &Diagnostic| finding.lint().categories().contains(&Category::Naming);
+        assert!(all.iter().any(naming));
+
+        let mut config = Config::default();
+        config
+            .with_category(Category::Naming, false)
+            .with_category_severity(Category::Compat, Severity::Advice)
+            .with_severity("request/body-union", Severity::Error);
+        let some = validate_with_config(&openapi, &config);
+        assert!(!some.iter().any(naming));
+        for finding in &some {
+            if finding.code() == "request/body-union" {
+                assert_eq!(finding.severity(), Severity::Error);
+            } else if finding.lint().categories()[0] == Category::Compat {
+                assert_eq!(finding.severity(), Severity::Advice);
+            }
+        }
+
+        let config = Config::from_toml("[lints]\nnaming = false\n").unwrap();
+        assert!(!validate_with_config(&openapi, &config).iter().any(naming));
+    }
+
+    #[test]
+    #[cfg(feature = "fs")]
+    fn config_file() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+
+        let validator = Validator::from_config("src/tests/openapi-lint.toml").unwrap();
+        let findings = validator.validate(&openapi);
+        let codes = findings
+            .iter()
+            .map(Diagnostic::code)
+            .collect::<std::collections::BTreeSet<_>>();
+        assert!(codes.contains("docs/rust-documentation"));
+        assert!(codes.contains("extension/required"));
+        assert!(!codes.contains("naming/pascal-case-type"));
+        assert!(!codes.contains("operation/method"));
+        assert!(!findings.iter().any(|finding| finding.rule() == "naming"
+            && finding.message().contains("projectInstancesPost")));
+        assert!(findings
+            .iter()
+            .filter(|finding| finding.code() == "naming/kebab-case-path")
+            .all(|finding| finding.severity() == Severity::Warning));
+
+        let err = Config::from_toml("[lints]\n\"naming/pascal\" = false\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "there is no check or category with the code naming/pascal"
+        );
+        let err = Config::from_toml("[severities]\n\"docs/html\" = \"fatal\"\n").unwrap_err();
+        assert!(matches!(err, ConfigError::UnknownSeverity(_)));
+        let err = Config::from_toml("[allow]\nname-patterns = [\"(\"]\n").unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidPattern(_)));
+        let err = Config::from_toml("extrenal = true\n").unwrap_err();
+        assert!(matches!(err, ConfigError::Parse(_)));
+        assert!(err.to_string().contains("line 1, column 1"));
+    }
+
+    #[test]
+    fn tag_order() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+        let order = |config: &Config| {
+            validate_with_config(&openapi, config)
+                .into_iter()
+                .filter(|finding| finding.code() == "tag/order")
+                .map(|finding| finding.message().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let mut config = Config::default();
+        config.with_tag_order(["projects"]);
+        assert_eq!(
+            order(&config),
+            [
+                "The top-level tags aren't in the canonical order; reorder them as \
+            [\"projects\",\"hardware\",\"instances\"]."
+            ],
+        );
+        config.with_tag_order(["hardware", "projects", "instances"]);
+        assert!(order(&config).is_empty());
+
+        let config = Config::from_toml("tag-order = [\"hardware\", \"projects\"]\n").unwrap();
+        assert!(order(&config).is_empty());
+    }
+
+    #[test]
+    fn spec_graph() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+        let graph = SpecGraph::new(&openapi);
+
+        assert_eq!(
+            graph.who_references("DiskState"),
+            [&Node::schema("DiskAttachment"), &Node::schema("DiskView")],
+        );
+        assert_eq!(
+            graph
+                .operations_using_schema("DiskState")
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            [
+                "GET /projects/{project_name}/disks",
+                "GET /projects/{project_name}/disks/{disk_name}",
+                "GET /projects/{project_name}/instances/{instance_name}/disks",
+                "GET /projects/{project_name}/instances/{instance_name}/disks/{disk_name}",
+                "GET /snapshots",
+                "POST /projects/{project_name}/disks",
+                "PUT /projects/{project_name}/instances/{instance_name}/disks/{disk_name}",
+            ],
+        );
+        // By way of the shared request body DiskCreate.
+        assert_eq!(
+            graph.schemas_reachable_from("project_disks_post"),
+            [
+                "ByteCount",
+                "DiskCreateParams",
+                "DiskState",
+                "DiskView",
+                "Name"
+            ],
+        );
+        assert!(graph.schemas_reachable_from("no_such_operation").is_empty());
+    }
+
+    #[test]
+    fn sorted_components() {
+        let mut openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+        let unsorted = |openapi: &openapiv3::OpenAPI, config: &Config| {
+            validate_with_config(openapi, config)
+                .into_iter()
+                .filter(|finding| finding.code() == "document/unsorted-components")
+                .map(|finding| finding.message().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert!(unsorted(&openapi, &Config::default()).is_empty());
+        let config = Config::from_toml("sorted-components = true\n").unwrap();
+        assert_eq!(
+            unsorted(&openapi, &config),
+            [
+                "The entries of components.schemas aren't sorted by name: \"IdSortMode\" \
+                comes after \"fake_id_sort_mode\"."
+            ],
+        );
+
+        // Only the components are reordered, not (e.g.) their properties.
+        let properties = |openapi: &openapiv3::OpenAPI| {
+            let schema = openapi.components.as_ref().unwrap().schemas["DiskView"].as_item();
+            match schema.map(|schema| &schema.schema_kind) {
+                Some(openapiv3::SchemaKind::Type(openapiv3::Type::Object(object))) => {
+                    object.properties.keys().cloned().collect::<Vec<_>>()
+                }
+                _ => panic!("DiskView is not an object"),
+            }
+        };
+        let before = properties(&openapi);
+        sort_components(&mut openapi);
+        assert!(unsorted(&openapi, &config).is_empty());
+        assert_eq!(properties(&openapi), before);
+    }
+
+    #[test]
+    fn waivers() {
+        let openapi = serde_json::from_str(include_str!("tests/waivers.json")).unwrap();
+
+        let actual = render(&validate(&openapi));
+        expectorate::assert_contents("src/tests/waivers.out", &actual);
+    }
+
+    #[test]
+    fn custom_checks() {
+        static EVERY_OPERATION: Lint = Lint::custom(
+            "test/every-operation",
+            "https://example.com/every-operation",
+            Severity::Advice,
+            "Every operation is worth a look.",
+            "Look at it.",
+        );
+
+        struct EveryOperation;
+
+        impl Check for EveryOperation {
+            fn lint(&self) -> &'static Lint {
+                &EVERY_OPERATION
+            }
+
+            fn check(&self, cx: &mut Context<'_>) {
+                for (path, method, op) in cx.spec().operations() {
+                    cx.report(
+                        format!("{} {}", method, path),
+                        Location::operation(path, method, op),
+                    );
+                }
+            }
+        }
+
+        let openapi: openapiv3::OpenAPI =
+            serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+        let builtin = validate(&openapi);
+
+        let validator = Validator::builder().check(EveryOperation).build();
+        let findings = validator.validate(&openapi);
+        let (custom, rest): (Vec<_>, Vec<_>) = findings
+            .into_iter()
+            .partition(|finding| finding.code() == "test/every-operation");
+        assert_eq!(rest, builtin);
+        assert_eq!(custom.len(), openapi.operations().count());
+        assert_eq!(custom[0].url(), "https://example.com/every-operation");
+        assert_eq!(custom[0].severity(), Severity::Advice);
+
+        // Custom checks are configured by code like the built-in ones.
+        let mut config = Config::default();
+        config
+            .with_check(EveryOperation)
+            .with_severity("test/every-operation", Severity::Error);
+        assert!(validate_with_config(&openapi, &config)
+            .iter()
+            .any(|finding| finding.severity() == Severity::Error
+                && finding.code() == "test/every-operation"));
+        let validator = Validator::builder()
+            .check(EveryOperation)
+            .disable("test/every-operation")
+            .build();
+        assert_eq!(validator.validate(&openapi), builtin);
+
+        // A closure does the same for one operation at a time.
+        let validator = Validator::builder()
+            .operation_check(&EVERY_OPERATION, |path, method, _| {
+                Some(format!("{} {}", method, path))
+            })
+            .build();
+        assert_eq!(
+            validator.validate(&openapi),
+            Validator::builder()
+                .check(EveryOperation)
+                .build()
+                .validate(&openapi),
+        );
+    }
+
+    #[test]
+    fn baseline() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+
+        let baseline = Baseline::new(&validate(&openapi));
+        assert!(baseline.filter(validate(&openapi)).is_empty());
+
+        // Only the problems that the baseline doesn't account for remain.
+        let baseline = Baseline::from_json(baseline.to_json()).unwrap();
+        let new = baseline.filter(validate_external(&openapi));
+        assert!(!new.is_empty());
+        assert!(new
+            .iter()
+            .all(|finding| finding.code().starts_with("docs/")));
+    }
+
+    #[test]
+    fn rename_map() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+
+        let map = crate::rename_map(&openapi);
+        assert_eq!(
+            map.renames(Target::OperationId).collect::<Vec<_>>(),
+            [
+                ("firewall__targets_check", "firewall_targets_check"),
+                ("projectInstancesPost", "project_instances_post"),
+            ],
+        );
+
+        let actual = serde_json::to_string_pretty(&map.to_json()).unwrap();
+        expectorate::assert_contents("src/tests/rename-map.json", &actual);
+    }
+
+    #[test]
+    fn attribute_plan() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+
+        let plan = crate::attribute_plan(&openapi);
+        let fix = plan
+            .fixes()
+            .iter()
+            .find(|fix| fix.name() == "fake_id_sort_mode")
+            .unwrap();
+        assert_eq!(fix.member(), None);
+        assert_eq!(fix.attribute(), "#[serde(rename = \"FakeIdSortMode\")]");
+
+        let actual = serde_json::to_string_pretty(&plan.to_json()).unwrap();
+        expectorate::assert_contents("src/tests/attribute-plan.json", &actual);
+    }
+
+    #[test]
+    #[cfg(feature = "fs")]
+    fn resolver() {
+        use crate::{ResolveError, Resolver};
+
+        let path = std::path::Path::new("src/tests/split/openapi.json");
+        let document = serde_json::from_str(include_str!("tests/split/openapi.json")).unwrap();
+        let bundled = Resolver::new().bundle(document, path).unwrap();
+
+        let components = &bundled["components"];
+        assert_eq!(
+            bundled["paths"]["/widgets"]["get"]["responses"]["4XX"]["$ref"],
+            "#/components/responses/Error"
+        );
+        assert!(components["responses"]["Error"].is_object());
+        assert!(components["schemas"]["Error"].is_object());
+        assert!(components["schemas"]["ErrorCode"].is_object());
+        assert_eq!(
+            components["schemas"]["Widget"]["properties"]["size"]["type"],
+            "string"
+        );
+        let spec = serde_json::from_value(bundled).unwrap();
+        assert!(!validate(&spec)
+            .iter()
+            .any(|finding| finding.code() == "ref/unresolved"));
+
+        // The spec itself has Error, so that bringing in another is a
+        // conflict.
+        let mut document =
+            serde_json::from_str::<serde_json::Value>(include_str!("tests/split/openapi.json"))
+                .unwrap();
+        document["components"]["schemas"]["Error"] = serde_json::json!({ "type": "string" });
+        let err = Resolver::new().bundle(document, path).unwrap_err();
+        assert!(matches!(err, ResolveError::Conflict { .. }));
+
+        let document = serde_json::json!({ "$ref": "missing.json" });
+        let err = Resolver::new().bundle(document, path).unwrap_err();
+        assert!(matches!(err, ResolveError::Read { .. }));
+        let document = serde_json::json!({ "$ref": "https://example.com/common.json" });
+        let err = Resolver::new()
+            .with_fetch(|_| Err("offline".to_string()))
+            .bundle(document, path)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "failed to fetch https://example.com/common.json: offline"
+        );
+    }
+
+    #[test]
+    fn tag_report() {
+        let openapi = serde_json::from_str(include_str!("tests/tags.json")).unwrap();
+
+        let report = crate::tag_report(&openapi);
+        assert_eq!(
+            report.single_operation_tags().collect::<Vec<_>>(),
+            ["disks", "policy"],
+        );
+        assert_eq!(
+            report
+                .multi_tag_operations()
+                .map(|op| op.operation_id())
+                .collect::<Vec<_>>(),
+            [Some("disk_list")],
+        );
+
+        expectorate::assert_contents("src/tests/tag-report.md", &report.to_markdown());
+        let actual = serde_json::to_string_pretty(&report.to_json()).unwrap();
+        expectorate::assert_contents("src/tests/tag-report.json", &actual);
+    }
+
+    #[test]
+    fn resource_tags() {
+        let operation = |id: &str, tags: &[&str]| {
+            serde_json::json!({
+                "operationId": id,
+                "tags": tags,
+                "responses": { "204": { "description": "done" } },
+            })
+        };
+        let openapi = serde_json::from_value(serde_json::json!({
+            "openapi": "3.0.3",
+            "info": { "title": "resources", "version": "1.0.0" },
+            "paths": {
+                "/v1/disks": {
+                    "get": operation("disk_list", &["disks"]),
+                    "post": operation("disk_create", &["disks"]),
+                },
+                "/v1/disks/{disk}": {
+                    "delete": operation("disk_delete", &["instances"]),
+                    "put": operation("disk_update", &["disks", "instances"]),
+                },
+                "/v1/instances": {
+                    "get": operation("instance_list", &["instances"]),
+                    "post": operation("instance_create", &["compute"]),
+                },
+            },
+        }))
+        .unwrap();
+
+        // Nothing is said of the instances, whose tags are split evenly.
+        let strays = validate(&openapi)
+            .into_iter()
+            .filter(|finding| finding.code() == "tag/resource-consistency")
+            .map(|finding| finding.location().operation_id().unwrap().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(strays, ["disk_delete"]);
+    }
+
+    #[test]
+    fn updates() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+
+        let mut config = Config::default();
+        config.with_allowed_methods(["get", "post", "put", "patch", "delete"]);
+        let actual = render(
+            validate_with_config(&openapi, &config)
+                .iter()
+                .filter(|finding| finding.rule() == "updates" || finding.rule() == "http-methods"),
+        );
+        expectorate::assert_contents("src/tests/updates.out", &actual);
+    }
+
+    #[test]
+    fn request_body_unions_allowed() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+
+        let mut config = Config::default();
+        config.with_request_body_unions(true);
+        assert!(!validate_with_config(&openapi, &config)
+            .iter()
+            .any(|finding| finding.rule() == "request-body-unions"));
+    }
+
+    #[test]
+    fn allowed_names() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+        let naming = |config: &Config| {
+            validate_with_config(&openapi, config)
+                .into_iter()
+                .filter(|finding| finding.rule() == "naming")
+                .map(|finding| finding.message().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let all = naming(&Config::default());
+        let mut config = Config::default();
+        config
+            .with_allowed_names(["projectInstancesPost"])
+            .with_allowed_name_patterns([regex::Regex::new("^project[A-Z]").unwrap()]);
+        let some = naming(&config);
+        assert!(some.len() < all.len());
+        assert!(all
+            .iter()
+            .any(|message| message.contains("projectInstancesPost")));
+        assert!(!some.iter().any(|message| message.contains("'projectId'")));
+        assert!(!some
+            .iter()
+            .any(|message| message.contains("projectInstancesPost")));
+    }
+
+    #[test]
+    fn report_json() {
+        let openapi = serde_json::from_str(include_str!("tests/waivers.json")).unwrap();
+        let mut config = Config::default();
+        config
+            .with_profile(&Profile::external())
+            .with_category(Category::Docs, false)
+            .with_severity("naming/pascal-case-type", Severity::Error);
+        let report = validate_with_report(&openapi, &config);
+        let json = report.to_json();
+
+        assert_eq!(json["config"]["profile"], "external");
+        assert_eq!(json["config"]["external"], true);
+        let lints = &json["config"]["lints"];
+        assert_eq!(lints["docs/html"]["enabled"], false);
+        assert_eq!(lints["naming/pascal-case-type"]["severity"], "error");
+        assert_eq!(lints["schema/mixed-object"]["severity"], "warning");
+        assert_eq!(json["config"]["cases"]["type"], "PascalCase");
+
+        assert!(!report.waived().is_empty());
+        let waived = json["waived"].as_array().unwrap();
+        assert_eq!(waived.len(), report.waived().len());
+        assert!(waived
+            .iter()
+            .all(|waived| lints[waived["code"].as_str().unwrap()].is_object()));
+        assert_eq!(
+            json["findings"].as_array().unwrap().len(),
+            report.findings().len()
+        );
+    }
+
+    #[test]
+    fn report_queries() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+        let mut config = Config::default();
+        config.with_severity("naming/snake-case-property", Severity::Error);
+        let report = validate_with_report(&openapi, &config);
+        assert!(!report.is_clean());
+
+        let properties = report.clone().filter_code("naming/snake-case-property");
+        assert!(!properties.is_clean());
+        assert!(properties
+            .findings()
+            .iter()
+            .all(|finding| finding.code() == "naming/snake-case-property"));
+        // Filters compose with the configured severities.
+        assert_eq!(
+            properties.clone().errors_only().findings().len(),
+            properties.findings().len(),
+        );
+        assert!(report
+            .clone()
+            .filter_category(Category::Docs)
+            .findings()
+            .iter()
+            .all(|finding| finding.lint().categories().contains(&Category::Docs)));
+
+        // An operation's findings include those of the components noted as
+        // used by it.
+        let operation = report.clone().for_operation("projects_post");
+        assert!(operation
+            .findings()
+            .iter()
+            .any(|finding| finding.location().operation_id() == Some("projects_post")));
+        assert!(operation
+            .findings()
+            .iter()
+            .any(|finding| finding.location().operation_id().is_none()));
+        assert!(report.clone().for_operation("nonexistent").is_clean());
+
+        let findings = report.into_findings();
+        let errors = findings
+            .iter()
+            .filter(|finding| finding.severity() == Severity::Error)
+            .count();
+        assert_eq!(
+            crate::Report::from(findings).errors_only().findings().len(),
+            errors,
+        );
+    }
+
+    #[test]
+    fn grouped_findings() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+        let report = validate_with_report(&openapi, &Config::default());
+
+        let operations = report.by_operation();
+        assert!(operations["projects_post"]
+            .iter()
+            .any(|finding| finding.location().operation_id().is_none()));
+        for (operation_id, findings) in &operations {
+            assert!(findings.iter().all(|finding| {
+                finding.location().operation_id() == Some(*operation_id)
+                    || finding
+                        .referenced_from()
+                        .iter()
+                        .any(|site| site.operation_id() == Some(*operation_id))
+            }));
+        }
+
+        let components = report.by_component();
+        assert!(!components["ActionError"].is_empty());
+        assert_eq!(
+            components.values().map(Vec::len).sum::<usize>(),
+            report
+                .findings()
+                .iter()
+                .filter(|finding| finding.location().component().is_some())
+                .count(),
+        );
+    }
+
+    #[test]
+    fn max_findings() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+        let all = validate(&openapi);
+
+        let mut config = Config::default();
+        config.with_max_findings(Some(5));
+        let report = validate_with_report(&openapi, &config);
+        assert!(report.truncated());
+        assert_eq!(report.findings(), &all[..5]);
+        assert_eq!(report.to_json()["truncated"], true);
+        assert_eq!(
+            crate::validate_iter_with_config(&openapi, &config).count(),
+            5
+        );
+
+        config.with_max_findings(Some(all.len()));
+        let report = validate_with_report(&openapi, &config);
+        assert!(!report.truncated());
+        assert_eq!(report.findings().len(), all.len());
+
+        let config = Config::from_toml("max-findings = 0\n").unwrap();
+        assert!(!validate_with_report(&openapi, &config).truncated());
+    }
+
+    #[test]
+    #[should_panic(expected = "at least 1")]
+    fn zero_max_findings() {
+        Config::default().with_max_findings(Some(0));
+    }
+
+    #[test]
+    fn cases() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+
+        let mut config = Config::default();
+        config
+            .with_case(Target::Property, Case::Camel)
+            .with_case(Target::OperationId, Case::Camel);
+        let messages = validate_with_config(&openapi, &config)
+            .into_iter()
+            .filter(|finding| finding.rule() == "naming")
+            .map(|finding| finding.message().to_string())
+            .collect::<Vec<_>>();
+        assert!(!messages
+            .iter()
+            .any(|message| message.contains("projectInstancesPost")));
+        assert!(messages.iter().any(|message| message.contains(
+            "The operation for /projects post is named \"projects_post\" which is not camelCase"
+        )));
+
+        let config = Config::from_toml("[cases]\nproperty = \"camelCase\"\n").unwrap();
+        assert_eq!(config.cases.get(&Target::Property), Some(&Case::Camel));
+        let err = Config::from_toml("[cases]\nproperty = \"camel\"\n").unwrap_err();
+        assert!(matches!(err, ConfigError::UnknownCase(_)));
+    }
+
+    #[test]
+    fn nullable_collections_allowed() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+
+        let mut config = Config::default();
+        config.with_nullable_collections(true);
+        assert!(!validate_with_config(&openapi, &config)
+            .iter()
+            .any(|finding| finding.rule() == "nullable-collections"));
+    }
+
+    #[test]
+    fn literal_path_prefixes_disabled() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+
+        let config = Config::from_toml("literal-path-prefixes = false\n").unwrap();
+        assert!(!validate_with_config(&openapi, &config)
+            .iter()
+            .any(|finding| finding.code() == "path/leading-parameter"));
+    }
+
+    #[test]
+    fn allowed_html_tags() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+
+        let mut config = Config::default();
+        config.with_external(true).with_allowed_html_tags(["BR"]);
+        let html = validate_with_config(&openapi, &config)
+            .into_iter()
+            .filter(|finding| finding.rule() == "html-in-descriptions")
+            .collect::<Vec<_>>();
+        assert_eq!(html.len(), 2);
+        assert!(!html.iter().any(|finding| finding
+            .message()
+            .starts_with("HTML found in external interface: <br>")));
+    }
+
+    #[test]
+    fn description_style() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+
+        let mut config = Config::default();
+        config
+            .with_external(true)
+            .with_min_heading_level(1)
+            .with_max_table_columns(None)
+            .with_max_description_line_length(Some(100));
+        let actual = render(
+            validate_with_config(&openapi, &config)
+                .iter()
+                .filter(|finding| finding.rule() == "description-style"),
+        );
+        expectorate::assert_contents("src/tests/description-style.out", &actual);
+    }
+
+    #[test]
+    fn spec_size() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+
+        let mut config = Config::default();
+        config.with_max_bytes_per_operation(Some(1024));
+        let actual = render(
+            validate_with_config(&openapi, &config)
+                .iter()
+                .filter(|finding| finding.rule() == "spec-size"),
+        );
+        expectorate::assert_contents("src/tests/spec-size.out", &actual);
+
+        assert!(!validate(&openapi)
+            .iter()
+            .any(|finding| finding.rule() == "spec-size"));
+    }
+
+    #[test]
+    fn anonymous_types() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+
+        let mut config = Config::default();
+        config.with_max_anonymous_types(Some(10));
+        let actual = render(
+            validate_with_config(&openapi, &config)
+                .iter()
+                .filter(|finding| finding.rule() == "anonymous-types"),
+        );
+        expectorate::assert_contents("src/tests/anonymous-types.out", &actual);
+
+        config.with_max_anonymous_types(Some(1000));
+        assert!(!validate_with_config(&openapi, &config)
+            .iter()
+            .any(|finding| finding.rule() == "anonymous-types"));
+    }
+
+    #[test]
+    fn required_extensions() {
+        let openapi = serde_json::from_str(include_str!("tests/extensions.json")).unwrap();
+
+        let mut config = Config::default();
+        config
+            .with_required_extension(Element::Operation, "x-cli-command")
+            .with_required_extension(Element::Tag, "x-docs-section");
+        let actual = render(
+            validate_with_config(&openapi, &config)
+                .iter()
+                .filter(|finding| finding.rule() == "required-extensions"),
+        );
+        expectorate::assert_contents("src/tests/required-extensions.out", &actual);
+    }
+
+    #[test]
+    fn redirect_operations() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+
+        let mut config = Config::default();
+        config.with_redirect_operations(["projects_get_project"]);
+        assert!(!validate_with_config(&openapi, &config)
+            .iter()
+            .any(|finding| finding.rule() == "redirects"));
+    }
+
+    #[test]
+    fn overridden_parameters() {
+        let openapi = serde_json::from_value(serde_json::json!({
+            "openapi": "3.0.3",
+            "info": { "title": "disks", "version": "1.0.0" },
+            "paths": { "/v1/disks/{disk}": {
+                "parameters": [
+                    { "in": "path", "name": "disk", "required": true,
+                      "schema": { "type": "string" } },
+                    { "in": "query", "name": "tags", "style": "spaceDelimited",
+                      "schema": { "type": "array", "items": { "type": "string" } } },
+                ],
+                "get": {
+                    "operationId": "disk_view",
+                    "parameters": [
+                        { "in": "path", "name": "disk", "required": true,
+                          "schema": { "type": "string", "format": "uuid" } },
+                        { "in": "query", "name": "tags", "style": "spaceDelimited",
+                          "schema": { "type": "array", "items": { "type": "string" } } },
+                    ],
+                    "responses": { "204": { "description": "nothing" } },
+                },
+            } },
+        }))
+        .unwrap();
+
+        // An operation may override the parameters of its path item, and its
+        // own declarations are the ones checked.
+        let findings = validate(&openapi);
+        assert!(!findings
+            .iter()
+            .any(|finding| finding.code() == "parameter/duplicate"));
+        let mut codes = findings.iter().map(Diagnostic::code).collect::<Vec<_>>();
+        let count = codes.len();
+        codes.dedup();
+        assert_eq!(codes.len(), count, "{:?}", codes);
+    }
+
+    #[test]
+    fn location_headers() {
+        let created = |location: serde_json::Value| {
+            serde_json::json!({
+                "responses": { "201": {
+                    "description": "created",
+                    "headers": { "Location": location },
+                } },
+            })
+        };
+        let openapi = serde_json::from_value(serde_json::json!({
+            "openapi": "3.0.3",
+            "info": { "title": "locations", "version": "1.0.0" },
+            "paths": {
+                "/v1/disks": {
+                    "post": created(serde_json::json!({
+                        "schema": { "type": "string" },
+                        "x-path-template": "/v1/disks/{disk}",
+                    })),
+                },
+                "/v1/disks/{disk_name}": {},
+                "/v1/images": {
+                    "post": created(serde_json::json!({
+                        "schema": { "type": "string" },
+                        "example": "https://oxide.example/v1/images/debian?project=p",
+                    })),
+                },
+                "/v1/images/{image}": {},
+                "/v1/instances": {
+                    "post": created(serde_json::json!({
+                        "schema": { "type": "string" },
+                        "x-path-template": "/v1/instance/{instance}",
+                    })),
+                },
+                "/v1/snapshots": {
+                    "post": { "responses": {
+                        "201": { "description": "created" },
+                        "204": { "description": "created", "x-location": false },
+                    } },
+                },
+            },
+        }))
+        .unwrap();
+
+        let findings = |config: &Config| {
+            validate_with_config(&openapi, config)
+                .into_iter()
+                .filter(|finding| finding.rule() == "location-headers")
+                .map(|finding| finding.message().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert!(findings(&Config::default()).is_empty());
+        let mut config = Config::default();
+        config.with_location_statuses([201, 204]);
+        assert_eq!(
+            findings(&config),
+            [
+                "The Location header of the 201 response for <unknown> points at \
+                \"/v1/instance/{instance}\", which matches none of the spec's paths.",
+                "The 201 response for <unknown> does not declare a Location header.",
+            ],
+        );
+    }
+
+    #[test]
+    fn singular_type_names() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+
+        let mut config = Config::default();
+        config.with_singular_type_names(true);
+        let actual = render(
+            validate_with_config(&openapi, &config)
+                .iter()
+                .filter(|finding| finding.rule() == "singular-type-names"),
+        );
+        expectorate::assert_contents("src/tests/singular-type-names.out", &actual);
+    }
+
+    #[test]
+    fn unknown_fields() {
+        let value = serde_json::from_str(include_str!("tests/unknown-fields.json")).unwrap();
+
+        let actual = render(&validate_document(
+            &value,
+            Config::default().with_strict_parse(true),
+        ));
+        expectorate::assert_contents("src/tests/unknown-fields.out", &actual);
+    }
+
+    #[test]
+    fn typos() {
+        let value = serde_json::from_str(include_str!("tests/unknown-fields.json")).unwrap();
+
+        let actual = render(&validate_document(&value, &Config::default()));
+        expectorate::assert_contents("src/tests/typos.out", &actual);
+    }
+
+    #[test]
+    fn example_style() {
+        let mut value = serde_json::json!({
+            "openapi": "3.0.3",
+            "info": { "title": "widgets", "version": "1.0.0" },
+            "paths": { "/widgets": { "get": {
+                "parameters": [{
+                    "name": "limit",
+                    "in": "query",
+                    "schema": { "type": "integer", "example": 10 },
+                    "example": 10,
+                }],
+                "responses": { "200": {
+                    "description": "the widgets",
+                    "content": { "application/json": {
+                        "examples": { "empty": { "value": [] } },
+                    } },
+                } },
+            } } },
+        });
+        let pointers = |config: &Config, value: &serde_json::Value| {
+            validate_document(value, config)
+                .into_iter()
+                .filter(|finding| finding.code() == "docs/example-style")
+                .map(|finding| finding.location().pointer().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        // Schemas have only the singular `example` in OpenAPI 3.0.
+        assert_eq!(
+            pointers(&Config::default(), &value),
+            ["/paths/~1widgets/get/parameters/0/example"],
+        );
+        assert_eq!(
+            pointers(Config::default().with_singular_examples(true), &value),
+            ["/paths/~1widgets/get/responses/200/content/application~1json/examples"],
+        );
+
+        let findings = validate_document(&value, &Config::default());
+        let fixes = crate::apply_fixes(&mut value, findings);
+        assert_eq!(fixes.fixed().len(), 1);
+        assert_eq!(
+            value["paths"]["/widgets"]["get"]["parameters"][0]["examples"],
+            serde_json::json!({ "example": { "value": 10 } }),
+        );
+        assert!(pointers(&Config::default(), &value).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    fn validate_str() {
+        let text = include_str!("tests/errors.json");
+        let config = Config::default();
+        let value = serde_json::from_str(text).unwrap();
+        let openapi = serde_json::from_str(text).unwrap();
+        let expected = render(
+            &validate_document(&value, &config)
+                .into_iter()
+                .chain(validate_with_config(&openapi, &config))
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(render(&crate::validate_json_str(text, &config)), expected);
+        assert_eq!(render(&crate::validate_yaml_str(text, &config)), expected);
+        assert_eq!(
+            render(&crate::validate_reader(text.as_bytes(), &config).unwrap()),
+            expected,
+        );
+
+        let invalid = |findings: Vec<Diagnostic>| {
+            assert_eq!(findings.len(), 1);
+            assert_eq!(findings[0].code(), "document/invalid");
+            findings[0]
+                .location()
+                .position()
+                .map(|p| (p.line(), p.column()))
+        };
+        assert_eq!(
+            invalid(crate::validate_json_str("{\n  \"openapi\": ,\n}", &config)),
+            Some((2, 14)),
+        );
+        assert_eq!(
+            invalid(crate::validate_yaml_str("openapi: [\n", &config)).map(|(line, _)| line),
+            Some(2),
+        );
+        invalid(crate::validate_yaml_str(
+            "openapi: 3.0.3\n---\nopenapi: 3.0.3\n",
+            &config,
+        ));
+        invalid(crate::validate_yaml_str("openapi: 3.0.3\n", &config));
+    }
+
+    #[test]
+    fn validate_value() {
+        let value = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+        let config = Config::default();
+        let codes = |findings: Vec<Diagnostic>| {
+            let mut codes = findings
+                .iter()
+                .map(|finding| finding.code())
+                .collect::<Vec<_>>();
+            codes.sort_unstable();
+            codes
+        };
+        // The value's maps are sorted, so the findings may come in another
+        // order than from the text.
+        assert_eq!(
+            codes(crate::validate_value_with_config(&value, &config)),
+            codes(crate::validate_json_str(
+                include_str!("tests/errors.json"),
+                &config,
+            )),
+        );
+
+        let findings = crate::validate_value(&serde_json::json!({ "openapi": "3.0.3" }));
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].code(), "document/invalid");
+    }
+
+    #[test]
+    fn allowed_status_codes() {
+        let value = serde_json::from_str(include_str!("tests/unknown-fields.json")).unwrap();
+        let keys = |config: &Config| {
+            validate_document(&value, config)
+                .into_iter()
+                .filter(|finding| finding.code() == "response/status-code")
+                .map(|finding| finding.location().pointer().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(keys(&Config::default()).len(), 4);
+        let config = Config::from_toml("[allow]\nstatus-codes = [599, 700]\n").unwrap();
+        assert_eq!(
+            keys(&config),
+            [
+                "/paths/~1projects~1{project}/get/responses/20O",
+                "/paths/~1projects~1{project}/get/responses/2xx",
+            ],
+        );
+    }
+
+    #[test]
+    fn draft_2020_12() {
+        let value = serde_json::from_str(include_str!("tests/draft-2020-12.json")).unwrap();
+
+        let actual = render(&validate_document(
+            &value,
+            Config::default().with_draft_2020_12(true),
+        ));
+        expectorate::assert_contents("src/tests/draft-2020-12.out", &actual);
+    }
+
+    #[test]
+    fn pointers() {
+        let mut config = Config::default();
+        config
+            .with_profile(&Profile::strict())
+            .with_max_anonymous_types(Some(0))
+            .with_max_bytes_per_operation(Some(10));
+        for text in [
+            include_str!("tests/errors.json"),
+            include_str!("tests/tags.json"),
+            include_str!("tests/waivers.json"),
+        ] {
+            let value = serde_json::from_str(text).unwrap();
+            let openapi = serde_json::from_str(text).unwrap();
+            let findings = validate_document(&value, &config)
+                .into_iter()
+                .chain(validate_with_config(&openapi, &config));
+            for finding in findings {
+                let pointer = finding.location().pointer();
+                assert!(
+                    pointer.is_some_and(|pointer| value.pointer(pointer).is_some()),
+                    "{} at {:?}",
+                    finding.code(),
+                    pointer,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn input() {
+        let json = "{\"openapi\": \"3.0.3\"}\n{\"openapi\": \"3.0.3\"}\n";
+        assert!(matches!(
+            crate::parse_document(json),
+            Err(InputError::MultipleDocuments { count: 2 }),
+        ));
+        assert!(matches!(
+            crate::parse_document("[{}, {}, {}]"),
+            Err(InputError::Array { len: 3 }),
+        ));
+        #[cfg(not(feature = "yaml"))]
+        assert!(matches!(
+            crate::parse_document("{"),
+            Err(InputError::Json(_)),
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    fn yaml_input() {
+        let spec = "openapi: 3.0.3\ninfo:\n  title: a\n  version: '1'\npaths: {}\n";
+        assert!(crate::parse_document(spec).is_ok());
+        assert!(crate::parse_document(&format!("---\n{}...\n", spec)).is_ok());
+
+        let stream = format!("# specs\n{}---\n{}", spec, spec);
+        assert!(matches!(
+            crate::parse_document(&stream),
+            Err(InputError::MultipleDocuments { count: 2 }),
+        ));
+        assert!(matches!(
+            crate::parse_document("{"),
+            Err(InputError::Parse(_)),
+        ));
+
+        let documents = crate::parse_stream(&stream).unwrap();
+        assert_eq!(
+            documents.iter().map(|d| d.line()).collect::<Vec<_>>(),
+            [1, 8],
+        );
+        assert_eq!(documents[1].text(), spec);
+        let map = SourceMap::new("specs.yaml", documents[1].text()).with_first_line(8);
+        let position = map.position("/paths").unwrap();
+        assert_eq!((position.line(), position.column()), (12, 1));
+    }
+
+    #[test]
+    fn source_map() {
+        let yaml = SourceMap::new("common.yaml", include_str!("tests/split/common.yaml"));
+        let at = |map: &SourceMap, pointer| {
+            let position = map.position(pointer).unwrap();
+            (position.line(), position.column())
+        };
+        assert_eq!(at(&yaml, "/components/schemas/Error/required/0"), (19, 9));
+        assert_eq!(at(&yaml, "/components/schemas/ErrorCode"), (20, 5));
+        assert_eq!(at(&yaml, "/components/schemas/ErrorCode/enum/1"), (25, 9));
+        // Not in the document, so placed at the nearest element that is.
+        assert_eq!(at(&yaml, "/components/schemas/Missing/type"), (9, 3));
+
+        let text = "\
+paths:
+  /widgets:
+    get:
+      description: |
+        List: widgets.
+      parameters:
+      - name: limit
+        in: query
+      - in: query
+        name: \"page_token\"
+      tags: [widgets, 'hardware']
+  \"/widget/{id}\":
+    get: {}
+";
+        let yaml = SourceMap::new("openapi.yaml", text);
+        assert_eq!(at(&yaml, "/paths/~1widgets/get/parameters/0/in"), (8, 9));
+        assert_eq!(at(&yaml, "/paths/~1widgets/get/parameters/1/name"), (10, 9));
+        assert_eq!(at(&yaml, "/paths/~1widgets/get/tags/1"), (11, 7));
+        assert_eq!(at(&yaml, "/paths/~1widget~1{id}/get"), (13, 5));
+
+        let contents = include_str!("tests/errors.json");
+        let json = SourceMap::new("errors.json", contents);
+        assert_eq!(at(&json, ""), (1, 1));
+        assert_eq!(
+            at(&json, "/paths/~1hardware~1racks/get/parameters/1/name"),
+            (67, 13)
+        );
+
+        let openapi = serde_json::from_str(contents).unwrap();
+        let mut findings = validate(&openapi);
+        findings
+            .iter_mut()
+            .for_each(|finding| json.annotate(finding));
+        let finding = findings
+            .iter()
+            .find(|finding| finding.code() == "request/body-union")
+            .unwrap();
+        assert_eq!(finding.location().file(), Some("errors.json"));
+        let position = finding.location().position().unwrap();
+        assert_eq!(
+            contents.lines().nth(position.line() - 1).map(str::trim),
+            Some("\"FirewallRuleTarget\": {"),
+        );
+    }
+
+    #[test]
+    fn openapi_3_1() {
+        let mut value = serde_json::from_str(include_str!("tests/openapi-3.1.json")).unwrap();
+        assert!(convert_3_1(&mut value));
+        assert!(!convert_3_1(&mut value));
+
+        let widget = &value["components"]["schemas"]["Widget"]["properties"];
+        assert_eq!(widget["kind"]["enum"], serde_json::json!(["widget"]));
+        assert_eq!(widget["size"]["minimum"], 0);
+        assert_eq!(widget["size"]["exclusiveMinimum"], true);
+        assert_eq!(widget["size"]["example"], 3);
+        assert_eq!(widget["label"]["type"], "string");
+        assert_eq!(widget["label"]["nullable"], true);
+        assert_eq!(
+            widget["shape"]["allOf"][0]["$ref"],
+            "#/components/schemas/Shape"
+        );
+        assert_eq!(widget["parent"]["nullable"], true);
+        assert!(value["x-webhooks"].is_object());
+
+        // A subschema under $defs whose name is taken is named for the
+        // component in which it was defined.
+        let schemas = &value["components"]["schemas"];
+        assert_eq!(schemas["Color"]["type"], "string");
+        assert_eq!(schemas["GadgetColor"]["type"], "object");
+        let gadget = &schemas["Gadget"]["properties"];
+        assert_eq!(gadget["color"]["$ref"], "#/components/schemas/GadgetColor");
+        // The stricter of an inclusive and an exclusive bound stands.
+        assert_eq!(gadget["weight"]["minimum"], 1);
+        assert!(gadget["weight"].get("exclusiveMinimum").is_none());
+        assert_eq!(gadget["height"]["maximum"], 5);
+        assert_eq!(gadget["height"]["exclusiveMaximum"], true);
+
+        assert!(validate_document(&value, &Config::default()).is_empty());
+        let spec = serde_json::from_value(value).unwrap();
+        assert!(!validate(&spec)
+            .iter()
+            .any(|finding| finding.code() == "ref/unresolved"));
+    }
+
+    #[test]
+    fn extension_values() {
+        let value = serde_json::from_str(include_str!("tests/extensions.json")).unwrap();
+        let rust_type = serde_json::from_value(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "crate": { "type": "string" },
+                "version": { "type": "string" },
+                "path": { "type": "string" }
+            },
+            "required": ["crate", "version", "path"]
+        }))
+        .unwrap();
+
+        let actual = render(&validate_document(
+            &value,
+            Config::default().with_extension_schema("x-rust-type", rust_type),
+        ));
+        expectorate::assert_contents("src/tests/extensions.out", &actual);
+    }
+
+    #[test]
+    fn skipped() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+
+        let report = validate_with_report(&openapi, &Config::default());
+        assert_eq!(report.findings(), crate::validate(&openapi));
+
+        let actual = report
+            .skipped()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n");
+        expectorate::assert_contents("src/tests/skipped.out", &actual);
+    }
+
+    #[test]
+    fn lazy() {
+        let openapi = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+
+        assert_eq!(
+            crate::validate_iter(&openapi).collect::<Vec<_>>(),
+            crate::validate(&openapi),
+        );
+        let first =
+            crate::validate_iter_with_config(&openapi, Config::default().with_external(true))
+                .next();
+        assert_eq!(first.as_ref(), validate_external(&openapi).first());
     }
 
     #[test]
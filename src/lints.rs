@@ -0,0 +1,962 @@
+// Copyright 2026 Oxide Computer Company
+
+//! The checks that the rules perform, each with a stable code. A rule (i.e. a
+//! section of the README) may comprise several checks; the code identifies
+//! the check precisely, so that it can be referenced from suppressions or CI
+//! policies independently of the wording of messages.
+
+use crate::Severity;
+
+/// A check that the linter performs, as returned by [`crate::explain`].
+#[derive(Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Lint {
+    code: &'static str,
+    rule: &'static str,
+    severity: Severity,
+    categories: &'static [Category],
+    rationale: &'static str,
+    fix: &'static str,
+    url: Option<&'static str>,
+}
+
+impl Lint {
+    /// A check defined outside this crate, for a [`crate::Check`], with its
+    /// code (e.g. "acme/resource-name") and the URL of its documentation.
+    pub const fn custom(
+        code: &'static str,
+        url: &'static str,
+        severity: Severity,
+        rationale: &'static str,
+        fix: &'static str,
+    ) -> Self {
+        Lint {
+            code,
+            rule: code,
+            severity,
+            categories: &[],
+            rationale,
+            fix,
+            url: Some(url),
+        }
+    }
+
+    /// The same check, in the given categories.
+    pub const fn with_categories(self, categories: &'static [Category]) -> Self {
+        Lint { categories, ..self }
+    }
+
+    /// The stable identifier of the check, e.g. "naming/pascal-case-type".
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    /// The rule to which the check belongs: the anchor of its section of the
+    /// README, e.g. "naming". For a custom check, this is its code.
+    pub fn rule(&self) -> &'static str {
+        self.rule
+    }
+
+    /// The severity of what the check finds unless overridden with
+    /// [`crate::Config::with_severity`].
+    pub fn default_severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// The documentation for the rule.
+    pub fn url(&self) -> String {
+        match self.url {
+            Some(url) => url.to_string(),
+            None => format!(
+                "https://github.com/oxidecomputer/openapi-lint#{}",
+                self.rule
+            ),
+        }
+    }
+
+    /// The families of checks to which the check belongs, by which checks can
+    /// be enabled, disabled, or given a severity together.
+    pub fn categories(&self) -> &'static [Category] {
+        self.categories
+    }
+
+    /// Why the check exists.
+    pub fn rationale(&self) -> &'static str {
+        self.rationale
+    }
+
+    /// How to fix what it finds.
+    pub fn fix(&self) -> &'static str {
+        self.fix
+    }
+}
+
+/// A family of checks, e.g. those of naming conventions, so that whole
+/// families can be adopted at once rather than check by check. New
+/// categories may be added without a major version bump, so matches on this
+/// must have a wildcard arm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum Category {
+    /// The case and wording of names.
+    Naming,
+    /// The documentation of external APIs.
+    Docs,
+    /// Constructs that clients or SDK generators handle poorly or
+    /// inconsistently.
+    Compat,
+    /// The shape of the spec: how schemas, operations, and responses fit
+    /// together.
+    Structure,
+    /// Constructs that let clients make unbounded or unsafe requests.
+    Security,
+    /// Conventions particular to Oxide's APIs.
+    OxideConvention,
+}
+
+impl Category {
+    /// The name of the category, e.g. "naming" or "oxide-convention".
+    pub fn name(self) -> &'static str {
+        match self {
+            Category::Naming => "naming",
+            Category::Docs => "docs",
+            Category::Compat => "compat",
+            Category::Structure => "structure",
+            Category::Security => "security",
+            Category::OxideConvention => "oxide-convention",
+        }
+    }
+
+    /// The category with the given name, as returned by [`Category::name`].
+    pub fn by_name(name: &str) -> Option<Self> {
+        [
+            Category::Naming,
+            Category::Docs,
+            Category::Compat,
+            Category::Structure,
+            Category::Security,
+            Category::OxideConvention,
+        ]
+        .into_iter()
+        .find(|category| category.name() == name)
+    }
+}
+
+impl std::fmt::Display for Category {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The check with the given code, e.g. "response/trivial-null".
+pub fn explain(code: &str) -> Option<&'static Lint> {
+    LINTS.iter().copied().find(|lint| lint.code == code)
+}
+
+/// Every check, in the order in which the README describes their rules.
+pub fn lints() -> impl Iterator<Item = &'static Lint> {
+    LINTS.iter().copied()
+}
+
+macro_rules! lints {
+    (
+        $($name:ident = (
+            $code:literal,
+            $rule:literal,
+            $severity:ident,
+            [$($category:ident),*],
+            $rationale:literal,
+            $fix:literal $(,)?
+        )),* $(,)?
+    ) => {
+        $(
+            pub(crate) static $name: Lint = Lint {
+                code: $code,
+                rule: $rule,
+                severity: Severity::$severity,
+                categories: &[$(Category::$category),*],
+                rationale: $rationale,
+                fix: $fix,
+                url: None,
+            };
+        )*
+
+        static LINTS: &[&Lint] = &[$(&$name),*];
+    };
+}
+
+lints! {
+    TYPE_MISMATCH = (
+        "schema/type-mismatch",
+        "type-mismatch",
+        Error,
+        [Structure],
+        "The subschemas of a oneOf, anyOf, or allOf have different types. SDK \
+        generators can't turn such a union into a native type, so clients end \
+        up with an untyped value.",
+        "Use serde adjacent tagging (#[serde(tag = \"type\", content = \
+        \"value\")]) so that each variant's payload is wrapped in an object.",
+    ),
+    PATH_NAME = (
+        "naming/kebab-case-path",
+        "paths",
+        Error,
+        [Naming],
+        "Path segments are kebab-case by convention, and a consistent style \
+        makes the API predictable.",
+        "Rename the path segment in the endpoint's path to kebab-case.",
+    ),
+    PATH_PREFIX = (
+        "path/leading-parameter",
+        "literal-path-prefixes",
+        Warning,
+        [Structure],
+        "A path that starts with a parameter (or has nothing but parameters) \
+        matches every prefix, which defeats routing by prefix and the \
+        policies of API gateways.",
+        "Start the path with a literal segment, such as the name of the \
+        collection to which the parameter belongs.",
+    ),
+    METHOD = (
+        "operation/method",
+        "http-methods",
+        Error,
+        [OxideConvention],
+        "Not every HTTP method is well supported by clients and proxies, and \
+        methods such as PATCH need a clear, shared convention for what they \
+        accept.",
+        "Use one of the allowed methods, or allow the method in the \
+        configuration.",
+    ),
+    PATCH_CONTENT_TYPE = (
+        "request/patch-content-type",
+        "updates",
+        Error,
+        [Compat],
+        "A partial update must say how the body is applied to the resource; an \
+        arbitrary content type leaves that to guesswork.",
+        "Accept one of the configured patch content types (by default, \
+        application/merge-patch+json).",
+    ),
+    PUT_BODY = (
+        "request/put-body",
+        "updates",
+        Error,
+        [OxideConvention],
+        "A PUT replaces the full resource, so its body should be the \
+        resource's update schema rather than an unrelated or inline type.",
+        "Use the schema named for the resource and the update suffix (e.g. \
+        ProjectUpdate for Project) as the body.",
+    ),
+    SHARED_BODY_CREATE_UPDATE = (
+        "request/shared-body-create-update",
+        "shared-request-bodies",
+        Error,
+        [Structure],
+        "A request body component used both to create and to update a \
+        resource is usually a copy-pasted reference, as creates and updates \
+        rarely accept the same fields.",
+        "Give the create and update operations their own request bodies.",
+    ),
+    SHARED_BODY_PATH_PARAMS = (
+        "request/shared-body-path-params",
+        "shared-request-bodies",
+        Error,
+        [Structure],
+        "A request body component used by operations with different path \
+        parameters is likely shared between different kinds of resource.",
+        "Give each kind of resource its own request body.",
+    ),
+    NULLABLE_REQUIRED = (
+        "request/nullable-required",
+        "nullable-required-properties",
+        Warning,
+        [Structure, Compat],
+        "A request body property that is both required and nullable must be \
+        sent even when it has no value, which is rarely what's meant; it \
+        usually comes from an Option<T> that should either be omitted when \
+        absent or not be optional at all.",
+        "Make the property optional (e.g. with #[serde(default)]) if it may be \
+        omitted, or non-nullable if it must be given.",
+    ),
+    DUPLICATE_PARAMETER = (
+        "parameter/duplicate",
+        "unique-parameters",
+        Error,
+        [Structure],
+        "A parameter declared more than once by an operation, or by a path \
+        item, is ambiguous, and generators handle it inconsistently.",
+        "Declare each parameter once in each list of parameters.",
+    ),
+    BODY_PARAMETER = (
+        "parameter/duplicated-in-body",
+        "parameters-in-request-bodies",
+        Warning,
+        [Structure],
+        "A required property of a request body that repeats a path or query \
+        parameter leaves clients to supply the value twice and to wonder which \
+        one the server uses.",
+        "Remove the property from the request body; the parameter already \
+        identifies the value.",
+    ),
+    PATH_PARAMETER_STYLE = (
+        "parameter/path-style",
+        "path-parameter-style",
+        Error,
+        [Compat],
+        "Only the simple style of path parameter, without explode, is \
+        supported by our server and clients.",
+        "Remove the style and explode settings from the path parameter.",
+    ),
+    PATH_PARAMETER_NAME = (
+        "parameter/resource-name",
+        "path-parameter-names",
+        Warning,
+        [Naming, OxideConvention],
+        "A path parameter that selects an item from a collection becomes an \
+        argument of the generated method, which reads naturally only if the \
+        parameter is named for the resource, as in /instances/{instance}.",
+        "Rename the parameter after the singular of the collection, \
+        optionally with an allowed suffix such as _id.",
+    ),
+    UNBOUNDED_QUERY_ARRAY = (
+        "parameter/unbounded-query-array",
+        "query-parameter-arrays",
+        Error,
+        [Security],
+        "Query strings are limited in length, so an array query parameter \
+        needs an upper bound.",
+        "Declare maxItems on the array.",
+    ),
+    COMPLEX_QUERY_ARRAY = (
+        "parameter/complex-query-array",
+        "query-parameter-arrays",
+        Error,
+        [Compat],
+        "Only strings, numbers, and booleans can be represented in a query \
+        string.",
+        "Use an array of scalars, or move the values into a request body.",
+    ),
+    MUTUALLY_EXCLUSIVE_PARAMETER = (
+        "parameter/mutually-exclusive",
+        "mutually-exclusive-parameters",
+        Warning,
+        [OxideConvention],
+        "The console generates forms from the groups of mutually exclusive \
+        parameters declared with x-mutually-exclusive; a group that names a \
+        parameter the operation doesn't have, or one that must always be \
+        given, can't be rendered as a choice.",
+        "Name only optional parameters of the operation in each group.",
+    ),
+    MISSING_MUTUAL_EXCLUSION = (
+        "parameter/missing-mutual-exclusion",
+        "mutually-exclusive-parameters",
+        Warning,
+        [OxideConvention],
+        "Parameters that select the same thing in different ways (e.g. by ID \
+        or by name) can't sensibly be given together, and without an \
+        x-mutually-exclusive group the console offers both.",
+        "List the parameters as a group in the operation's \
+        x-mutually-exclusive extension.",
+    ),
+    PROPERTY_NAME = (
+        "naming/snake-case-property",
+        "naming",
+        Error,
+        [Naming],
+        "Properties are snake_case by convention, and a consistent style \
+        makes the API predictable.",
+        "Add #[serde(rename = \"...\")] to the member or #[serde(rename_all = \
+        \"snake_case\")] to the struct.",
+    ),
+    PARAMETER_NAME = (
+        "naming/snake-case-parameter",
+        "naming",
+        Error,
+        [Naming],
+        "Parameters are snake_case by convention, and a consistent style makes \
+        the API predictable.",
+        "Rename the parameter, e.g. with #[serde(rename = \"...\")] on the \
+        member of the parameter struct.",
+    ),
+    OPERATION_ID_NAME = (
+        "naming/snake-case-operation-id",
+        "naming",
+        Error,
+        [Naming],
+        "Operation IDs are snake_case by convention; generators derive method \
+        names from them.",
+        "Rename the endpoint function, or set its operation_id, in snake_case.",
+    ),
+    STRAY_UNDERSCORE = (
+        "naming/stray-underscore",
+        "stray-underscores",
+        Warning,
+        [Naming],
+        "Leading, trailing, and repeated underscores are usually left behind \
+        by mechanical renames (e.g. a removed prefix), and generators handle \
+        them inconsistently.",
+        "Rename the property, parameter, or operation without the stray \
+        underscores.",
+    ),
+    MISSING_OPERATION_ID = (
+        "operation/missing-id",
+        "naming",
+        Error,
+        [Naming],
+        "Generators derive method names from operation IDs and can only invent \
+        names for operations without them.",
+        "Give the operation an operation_id.",
+    ),
+    TYPE_NAME = (
+        "naming/pascal-case-type",
+        "naming",
+        Error,
+        [Naming],
+        "Types are PascalCase by convention, and generators derive type names \
+        from schema names.",
+        "Add #[serde(rename = \"...\")] to the type with a PascalCase name.",
+    ),
+    ENUM_VALUE_NAME = (
+        "naming/enum-value-case",
+        "naming",
+        Error,
+        [Naming],
+        "Enumerated values are snake_case (or SCREAMING_SNAKE_CASE) by \
+        convention, and a consistent style makes the API predictable.",
+        "Add #[serde(rename = \"...\")] to the variant or #[serde(rename_all = \
+        \"snake_case\")] to the enum.",
+    ),
+    OPERATION_ID_COLLISION = (
+        "operation/id-collision",
+        "operation-id-collisions",
+        Error,
+        [Naming, Compat],
+        "Operation IDs that differ only in how they are split into words \
+        produce generated names that collide where case is ignored.",
+        "Rename one of the operations so that their words differ.",
+    ),
+    OPERATION_ID_PATH = (
+        "operation/id-path-mismatch",
+        "operation-ids-and-paths",
+        Warning,
+        [Naming],
+        "An operation ID with no word in common with its path (e.g. disk_list \
+        for /v1/snapshots) is almost always left over from copying another \
+        endpoint, and gives the generated method a misleading name.",
+        "Rename the endpoint function, or set its operation_id, after the \
+        resource in its path.",
+    ),
+    TAG_DESCRIPTION = (
+        "tag/description",
+        "tags",
+        Warning,
+        [Docs],
+        "The documentation groups operations by tag and introduces each group \
+        with the tag's description; a tag without one, or without a \
+        declaration at all, leaves its group unexplained.",
+        "Declare the tag in the top-level tags with a description of what its \
+        operations have in common.",
+    ),
+    TAG_ORDER = (
+        "tag/order",
+        "tags",
+        Warning,
+        [Docs],
+        "The documentation lists groups of operations in the order of the \
+        top-level tags, which should be the same from one spec to the next.",
+        "Reorder the top-level tags as the message shows.",
+    ),
+    TAG_RESOURCE = (
+        "tag/resource-consistency",
+        "tags",
+        Warning,
+        [Structure],
+        "SDK modules and the documentation group operations by tag, so an \
+        operation on a resource tagged differently from the resource's other \
+        operations ends up apart from them.",
+        "Give the operation the tag that the resource's other operations share.",
+    ),
+    UNSORTED_COMPONENTS = (
+        "document/unsorted-components",
+        "sorted-components",
+        Warning,
+        [Structure],
+        "Generated specs are checked in and reviewed as diffs. Components \
+        emitted in an arbitrary order (e.g. that of registration, or of a hash \
+        map) move about from one version to the next, burying the changes that \
+        matter.",
+        "Sort the entries of each components section by name: have the \
+        generator emit them sorted, or rewrite the spec with the CLI's \
+        --sort-components (sort_components for library users).",
+    ),
+    ANONYMOUS_TYPES = (
+        "schema/anonymous-types",
+        "anonymous-types",
+        Error,
+        [Structure],
+        "SDK generators must synthesize a name for each inline type, and \
+        synthesized names are unstable and unhelpful.",
+        "Make some of the inline types named components, e.g. by deriving \
+        JsonSchema on a named struct rather than inlining it.",
+    ),
+    SPEC_SIZE = (
+        "document/size-budget",
+        "spec-size",
+        Warning,
+        [Structure],
+        "A spec far larger than its number of operations calls for is slow to \
+        load and generate from, and usually means that a large schema has \
+        been inlined many times.",
+        "Make the largest repeated inline schemas named components, referred \
+        to with $ref.",
+    ),
+    UNRESOLVED_REFERENCE = (
+        "ref/unresolved",
+        "unresolved-references",
+        Error,
+        [Structure],
+        "A reference to a component that doesn't exist (or to something other \
+        than a component) can't be resolved by SDK generators or \
+        documentation tools, which fail or silently drop what refers to it.",
+        "Add the missing component, or correct the reference to name an \
+        existing one as #/components/<kind>/<name>.",
+    ),
+    SCHEMA_TITLE = (
+        "schema/title-mismatch",
+        "schema-titles",
+        Warning,
+        [Naming, Compat],
+        "Some generators name types after their titles, so a title that \
+        differs from the schema's name gives the type a different name in \
+        different SDKs.",
+        "Remove the title or make it match the name.",
+    ),
+    SCHEMA_TITLE_DESCRIPTION = (
+        "schema/title-duplicates-description",
+        "schema-titles",
+        Warning,
+        [Docs],
+        "Documentation renders a schema's title as a heading above its \
+        description, so a title that repeats the description shows the same \
+        text twice.",
+        "Remove the title, or shorten the first paragraph of the doc comment \
+        from which schemars derived it.",
+    ),
+    LONG_SCHEMA_TITLE = (
+        "schema/long-title",
+        "schema-titles",
+        Warning,
+        [Docs],
+        "Documentation renders a schema's title as a heading, and a paragraph \
+        makes a poor heading.",
+        "Shorten the title (with schemars, the first paragraph of the doc \
+        comment), moving the detail into the description.",
+    ),
+    PLURAL_TYPE_NAME = (
+        "naming/plural-type-name",
+        "singular-type-names",
+        Warning,
+        [Naming, OxideConvention],
+        "A type describes a single thing; collections are arrays of it.",
+        "Rename the type in the singular.",
+    ),
+    ENUM_TAG_CONSISTENCY = (
+        "enum/tag-consistency",
+        "enum-tag-consistency",
+        Error,
+        [Naming],
+        "Variants with a mixture of snake_case and PascalCase tags usually \
+        indicate a missing rename_all on the enum or on a type in its family.",
+        "Add #[serde(rename_all = \"snake_case\")] where it is missing.",
+    ),
+    NON_STRING_ENUM = (
+        "enum/non-string-values",
+        "enumeration-types",
+        Error,
+        [Compat],
+        "SDK generators can't represent enumerations of numbers, booleans, or \
+        mixed types as native enums.",
+        "Use a string enumeration, or document the allowed values instead.",
+    ),
+    ENUM_TAG_UNIQUENESS = (
+        "enum/tag-uniqueness",
+        "enum-tag-uniqueness",
+        Error,
+        [Compat],
+        "Clients can't tell apart variants with the same tag, or with tags \
+        that are the same once normalized to snake_case.",
+        "Rename one of the variants.",
+    ),
+    DUPLICATE_ENUM = (
+        "enum/duplicate",
+        "duplicate-enumerations",
+        Warning,
+        [Structure],
+        "SDK generators make a type of each inline string enumeration, so the \
+        same values defined in several places become as many identical types, \
+        which callers can't use interchangeably.",
+        "Define the values once, as a component schema, and refer to it \
+        wherever they are used (with schemars, use the same Rust enum).",
+    ),
+    UUID_SUFFIX = (
+        "naming/redundant-uuid-suffix",
+        "uuids",
+        Warning,
+        [Naming, OxideConvention],
+        "A property that is a uuid doesn't need to say so in its name; \
+        identifiers are named *_id by convention.",
+        "Rename the property to end with _id.",
+    ),
+    IDENTIFIER_FORMAT = (
+        "naming/identifier-format",
+        "uuids",
+        Advice,
+        [Naming, OxideConvention],
+        "A property's name tells clients what it holds: a name is \
+        human-readable and an id is a uuid, so a name that is a uuid, or an id \
+        that may be any string, is likely a mistake in one or the other.",
+        "Rename the property, or make it a uuid (for an id) or a Name.",
+    ),
+    MIXED_OBJECT = (
+        "schema/mixed-object",
+        "mixed-objects",
+        Warning,
+        [Compat],
+        "An object with both fixed properties and additionalProperties is \
+        part struct and part map, which SDK generators represent in divergent \
+        and often lossy ways.",
+        "Make the map a property of its own, e.g. a HashMap field rather than \
+        one with #[serde(flatten)].",
+    ),
+    NULLABLE_COLLECTION = (
+        "schema/nullable-collection",
+        "nullable-collections",
+        Warning,
+        [Structure],
+        "A nullable array or map makes clients tell apart null and empty, \
+        which rarely differ, and wraps the collection in an optional type in \
+        every SDK.",
+        "Make the property non-nullable and use an empty collection instead.",
+    ),
+    NULLABLE_UNION = (
+        "schema/nullable-union",
+        "nullable-unions",
+        Warning,
+        [Compat],
+        "A nullable oneOf or anyOf union is ambiguous: some SDK generators \
+        treat null as another variant and others as a wrapper around the \
+        union, so clients disagree about its type.",
+        "Model the null case explicitly, as a variant of the union or as an \
+        optional property that refers to it.",
+    ),
+    REQUIRED_DEFAULT = (
+        "schema/required-default",
+        "required-defaults",
+        Warning,
+        [Structure],
+        "A default only applies when a property is omitted, which a required \
+        property can't be, so generated builders can't tell whether the \
+        property is optional.",
+        "Make the property optional (e.g. with #[serde(default)]) or remove \
+        the default.",
+    ),
+    FORMAT_CASE = (
+        "schema/format-case",
+        "format-case",
+        Warning,
+        [Compat],
+        "Generators match formats exactly, so a miscased or misspelled format \
+        (e.g. UUID or Date-Time) is silently ignored and the value becomes a \
+        plain string or number.",
+        "Spell the format as it is defined, in lowercase (e.g. uuid or date-time).",
+    ),
+    DISCRIMINATOR = (
+        "schema/discriminator-without-union",
+        "discriminators",
+        Error,
+        [Compat],
+        "Our generators support a discriminator only on a oneOf or anyOf of \
+        variants; on a plain object or an allOf hierarchy (OpenAPI's \
+        inheritance pattern) it breaks code generation.",
+        "Model the type as an internally tagged enum (#[serde(tag = \"...\")]), \
+        which produces a oneOf of variants.",
+    ),
+    TRIVIAL_NULL = (
+        "response/trivial-null",
+        "trivial-null-response",
+        Error,
+        [Compat],
+        "A response whose body is always null is awkward for clients, which \
+        must parse and discard it.",
+        "Return HttpResponseUpdatedNoContent (204) or a meaningful body.",
+    ),
+    URL_FORMAT = (
+        "response/url-format",
+        "response-urls",
+        Warning,
+        [Compat],
+        "A URL in a response that may be relative to the server leaves \
+        clients to guess the base against which to resolve it, and they have \
+        guessed wrong.",
+        "Return an absolute URL and declare the property with format: uri.",
+    ),
+    URL_DESCRIPTION = (
+        "docs/url-description",
+        "response-urls",
+        Warning,
+        [Docs],
+        "Clients need to know whether a URL in a response is absolute or \
+        relative, and to what, before they can follow it.",
+        "Describe the URL as absolute, or say what it is relative to.",
+    ),
+    UNSUPPORTED_KEYWORD = (
+        "document/unsupported-keyword",
+        "unsupported-schema-keywords",
+        Error,
+        [Compat],
+        "OpenAPI 3.0 doesn't support keywords from newer JSON Schema drafts, \
+        and most tools silently ignore them.",
+        "Use the OpenAPI 3.0 equivalent given in the message.",
+    ),
+    TYPE_ARRAY = (
+        "document/type-array",
+        "unsupported-schema-keywords",
+        Error,
+        [Compat],
+        "OpenAPI 3.0 requires a schema's type to be a single type.",
+        "Use a single type (with nullable if needed) or oneOf.",
+    ),
+    UNCONVERTED_KEYWORD = (
+        "document/unconverted-keyword",
+        "draft-2020-12-schemas",
+        Error,
+        [Compat],
+        "The conversion from draft 2020-12 to OpenAPI 3.0 doesn't handle this \
+        keyword, so it would reach the published spec.",
+        "Use the OpenAPI 3.0 equivalent given in the message.",
+    ),
+    UNCONVERTED_TYPE_ARRAY = (
+        "document/unconverted-type-array",
+        "draft-2020-12-schemas",
+        Error,
+        [Compat],
+        "The conversion from draft 2020-12 to OpenAPI 3.0 only handles a type \
+        array that pairs a single type with null.",
+        "Use oneOf instead.",
+    ),
+    REQUEST_BODY_UNION = (
+        "request/body-union",
+        "request-body-unions",
+        Error,
+        [Compat],
+        "Polymorphic request bodies are awkward to build with SDKs.",
+        "Use separate operations, or a single object with an explicit type \
+        field.",
+    ),
+    NULLABLE_REQUEST_BODY = (
+        "request/nullable-body",
+        "nullable-request-bodies",
+        Warning,
+        [Compat],
+        "Most client generators can't express a request body that may be \
+        literally null.",
+        "Make the request body optional (\"required\": false), or accept an \
+        empty object instead of null.",
+    ),
+    MISSING_CONTENT = (
+        "response/missing-content",
+        "response-content",
+        Error,
+        [Structure],
+        "A successful GET response without content is almost always a mistake \
+        in the endpoint's declared response type.",
+        "Declare the response body, or use 204 if no content is intended.",
+    ),
+    EMPTY_CONTENT = (
+        "response/empty-content",
+        "response-content",
+        Error,
+        [Structure],
+        "A content map that is present but empty is indistinguishable from one \
+        that is absent once parsed, and usually indicates a mistake.",
+        "Omit the content map if there is no content.",
+    ),
+    STATUS_CODE = (
+        "response/status-code",
+        "status-codes",
+        Warning,
+        [Structure],
+        "A response key that isn't a status code is silently dropped when the \
+        spec is parsed, and an unregistered code means nothing to clients, \
+        which treat it as the first code of its class.",
+        "Use a registered status code, a range such as 4XX, or default; \
+        allow a deliberate unregistered code in the configuration.",
+    ),
+    BINARY_IN_JSON = (
+        "content/binary-in-json",
+        "binary-data",
+        Error,
+        [Compat],
+        "JSON can't carry raw bytes, so a string with format binary in a JSON \
+        body can't be represented.",
+        "Use format byte (base64) instead.",
+    ),
+    BYTE_IN_RAW = (
+        "content/byte-in-raw",
+        "binary-data",
+        Error,
+        [Compat],
+        "Non-JSON, non-text content is sent as raw bytes, not base64.",
+        "Use format binary instead.",
+    ),
+    REDIRECT = (
+        "response/redirect",
+        "redirects",
+        Error,
+        [Compat],
+        "Generated SDKs follow or surface redirects inconsistently.",
+        "Remove the redirect response, or allow the operation in the \
+        configuration if it is meant for browsers.",
+    ),
+    RETRY_AFTER = (
+        "response/retry-after",
+        "retry-after",
+        Error,
+        [Compat],
+        "Clients need to know when to retry a request that was rate limited or \
+        that hit an unavailable service.",
+        "Declare a Retry-After header on the response.",
+    ),
+    MISSING_LOCATION = (
+        "response/missing-location",
+        "location-headers",
+        Error,
+        [Structure],
+        "Clients need to know where to find the resource that a request \
+        created.",
+        "Declare a Location header on the response, or mark the response with \
+        x-location: false if it deliberately has none.",
+    ),
+    UNKNOWN_LOCATION = (
+        "response/unknown-location",
+        "location-headers",
+        Error,
+        [Structure],
+        "A Location header that points at a path the spec doesn't have can't \
+        be followed by clients.",
+        "Point the Location header at the path of the created resource.",
+    ),
+    IDEMPOTENCY_KEY_ON_GET = (
+        "operation/idempotency-key-on-get",
+        "idempotency-keys",
+        Error,
+        [OxideConvention],
+        "GET operations are inherently idempotent, so an Idempotency-Key \
+        header is meaningless.",
+        "Remove the Idempotency-Key header parameter.",
+    ),
+    MISSING_IDEMPOTENCY_KEY = (
+        "operation/missing-idempotency-key",
+        "idempotency-keys",
+        Error,
+        [OxideConvention],
+        "Clients can only safely retry an idempotent POST if they can send an \
+        idempotency key.",
+        "Accept an Idempotency-Key header parameter.",
+    ),
+    IDEMPOTENCY_KEY_FORMAT = (
+        "operation/idempotency-key-format",
+        "idempotency-keys",
+        Error,
+        [OxideConvention],
+        "Idempotency keys must be unique, and generators only produce them \
+        automatically for uuids.",
+        "Make the Idempotency-Key header parameter a string with format uuid.",
+    ),
+    REQUIRED_EXTENSION = (
+        "extension/required",
+        "required-extensions",
+        Error,
+        [OxideConvention],
+        "Tools that depend on an extension can't handle an element that lacks \
+        it.",
+        "Add the extension to the element.",
+    ),
+    UNUSED_WAIVER = (
+        "waiver/unused",
+        "waivers",
+        Error,
+        [Structure],
+        "A waiver that waives nothing, because the problem it was added for \
+        has been fixed or the code is misspelled, would silently hide the \
+        problem if it came back.",
+        "Remove the code from the waiver, or correct it.",
+    ),
+    RUST_DOCUMENTATION = (
+        "docs/rust-documentation",
+        "rust-documentation",
+        Warning,
+        [Docs],
+        "Doc comments written for Rust readers (intra-doc links, paths) make \
+        no sense in external documentation.",
+        "Rewrite the description for API consumers.",
+    ),
+    HTML_IN_DESCRIPTION = (
+        "docs/html",
+        "html-in-descriptions",
+        Error,
+        [Docs],
+        "Our documentation renderer and SDK generators escape HTML \
+        inconsistently.",
+        "Use Markdown instead, or allow the tag in the configuration.",
+    ),
+    DESCRIPTION_STYLE = (
+        "docs/style",
+        "description-style",
+        Advice,
+        [Docs],
+        "Descriptions are rendered within the documentation page, which \
+        constrains their headings, tables, and line lengths.",
+        "Reformat the description as the message describes.",
+    ),
+    EXAMPLE_STYLE = (
+        "docs/example-style",
+        "examples",
+        Warning,
+        [Docs],
+        "OpenAPI gives examples of parameters, headers, and media types two \
+        ways, a singular example and a map of named examples; documentation \
+        renderers pull from one or the other, so a spec that mixes them \
+        renders some examples and drops the rest.",
+        "Give examples in a named examples map (or, with singular examples \
+        configured, as a single example).",
+    ),
+    INVALID_DOCUMENT = (
+        "document/invalid",
+        "invalid-documents",
+        Error,
+        [Structure],
+        "A document that isn't JSON or YAML, or isn't an OpenAPI 3.0 document, \
+        can't be checked, nor used by generators.",
+        "Correct the document at the position given in the message.",
+    ),
+    UNKNOWN_FIELD = (
+        "document/unknown-field",
+        "unknown-fields",
+        Error,
+        [Structure],
+        "Fields that aren't part of OpenAPI 3.0.3 are silently ignored, so a \
+        misspelled keyword has no effect.",
+        "Correct the spelling, or remove the field.",
+    ),
+    EXTENSION_VALUE = (
+        "extension/invalid-value",
+        "extension-values",
+        Error,
+        [Structure],
+        "Tools that consume an extension typically ignore a value they can't \
+        make sense of.",
+        "Correct the value to match the extension's schema.",
+    ),
+}
@@ -0,0 +1,668 @@
+// Copyright 2026 Oxide Computer Company
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use anyhow::{Context, Result};
+use clap::{Parser, ValueEnum};
+use indexmap::IndexMap;
+use openapi_lint::naming::Target;
+use openapi_lint::{
+    Baseline, Category, Config, Diagnostic, InputError, Profile, Resolver, Severity, SourceMap,
+    CONFIG_FILE,
+};
+use openapiv3::OpenAPI;
+use serde::{
+    de::IgnoredAny,
+    ser::{SerializeMap, SerializeSeq},
+    Serialize, Serializer,
+};
+
+/// Validate an OpenAPI document against the openapi-lint rules.
+#[derive(Parser)]
+#[command(version, about)]
+struct Args {
+    /// Path to an OpenAPI document in JSON or YAML format.
+    #[arg(required_unless_present = "explain")]
+    spec: Option<PathBuf>,
+
+    /// Explain the check with the given code (e.g. "response/trivial-null")
+    /// rather than validating a document.
+    #[arg(long, value_name = "CODE", conflicts_with = "spec")]
+    explain: Option<String>,
+
+    /// Read settings from this file rather than from openapi-lint.toml in
+    /// the current directory (if there is one). Flags take precedence.
+    #[arg(long, value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    /// Apply the checks and severities of this profile.
+    #[arg(long, value_name = "PROFILE")]
+    profile: Option<ProfileName>,
+
+    /// Also apply the rules for external (i.e. published) APIs; with
+    /// `--external=false`, don't, whatever the configuration file says.
+    #[arg(
+        long,
+        value_name = "BOOL",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true"
+    )]
+    external: Option<bool>,
+
+    /// Report what the checks in this category (e.g. "naming") find as
+    /// errors. May be repeated.
+    #[arg(long, value_name = "CATEGORY", value_parser = parse_category)]
+    deny_category: Vec<Category>,
+
+    /// Don't apply the checks in this category. May be repeated.
+    #[arg(long, value_name = "CATEGORY", value_parser = parse_category)]
+    allow_category: Vec<Category>,
+
+    /// Report fields that are not part of OpenAPI 3.0.3 rather than silently
+    /// ignoring them; with `--strict-parse=false`, ignore them.
+    #[arg(
+        long,
+        value_name = "BOOL",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true"
+    )]
+    strict_parse: Option<bool>,
+
+    /// Check a document whose schemas target JSON Schema draft 2020-12 for
+    /// constructs that the conversion to OpenAPI 3.0 does not handle; with
+    /// `--draft-2020-12=false`, check it as OpenAPI 3.0.
+    #[arg(
+        long,
+        value_name = "BOOL",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true"
+    )]
+    draft_2020_12: Option<bool>,
+
+    /// How to print the problems found: as text, or as a JSON report that
+    /// also records the configuration applied, the constructs skipped, and
+    /// the waivers that waived something. With the `miette` feature, `pretty`
+    /// prints each problem with an excerpt of the spec.
+    #[arg(long, value_name = "FORMAT", default_value = "text")]
+    format: Format,
+
+    /// Also list the constructs that rules skipped rather than checked.
+    #[arg(long)]
+    show_skipped: bool,
+
+    /// Print the detail of each problem, such as the whole of the schema in
+    /// which it was found, after its message.
+    #[arg(long)]
+    verbose: bool,
+
+    /// Stop after this many problems, leaving the rest of the spec unchecked,
+    /// and say so; a spec that wasn't checked to the end doesn't pass.
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = parse_max_findings,
+        conflicts_with = "write_baseline"
+    )]
+    max_findings: Option<usize>,
+
+    /// Stop at the first problem, as with `--max-findings 1`.
+    #[arg(long, conflicts_with_all = ["max_findings", "write_baseline"])]
+    fail_fast: bool,
+
+    /// Also write the JSON report (as printed with `--format json`) to this
+    /// file, whatever the format in which problems are printed, e.g. to keep
+    /// as a CI artifact.
+    #[arg(long, value_name = "PATH")]
+    report_out: Option<PathBuf>,
+
+    /// Write the renames that the naming rules call for, grouped by kind, to
+    /// this file as JSON.
+    #[arg(long, value_name = "PATH")]
+    rename_map: Option<PathBuf>,
+
+    /// Write the serde attributes that would fix the names of component
+    /// schemas, their fields, and their variants to this file as JSON.
+    #[arg(long, value_name = "PATH")]
+    attribute_plan: Option<PathBuf>,
+
+    /// Write the document, with the entries of each components section sorted
+    /// by name, to this file as JSON.
+    #[arg(long, value_name = "PATH")]
+    sort_components: Option<PathBuf>,
+
+    /// Write the document, with the fixes that problems suggest (such as
+    /// renames) applied, to this file as JSON in the order of the original,
+    /// and report only the problems that couldn't be fixed. Only an OpenAPI
+    /// 3.0 document in JSON, in a single file, can be fixed.
+    #[arg(long, value_name = "PATH")]
+    fix: Option<PathBuf>,
+
+    /// Report only the problems that this baseline file doesn't account for.
+    #[arg(long, value_name = "PATH")]
+    baseline: Option<PathBuf>,
+
+    /// Write the problems found to the baseline file, replacing its contents,
+    /// rather than reporting them.
+    #[arg(long, requires = "baseline")]
+    write_baseline: bool,
+
+    /// Rather than validating the document, print its operations grouped by
+    /// tag, calling out tags with a single operation and operations with
+    /// several tags.
+    #[arg(long, value_name = "FORMAT")]
+    tag_report: Option<TagReportFormat>,
+
+    /// Validate each of the documents of a YAML stream (e.g. specs
+    /// concatenated with `---` between them) rather than requiring a single
+    /// one.
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "rename_map",
+            "attribute_plan",
+            "sort_components",
+            "fix",
+            "tag_report",
+        ],
+    )]
+    each_document: bool,
+}
+
+fn parse_category(name: &str) -> Result<Category, String> {
+    Category::by_name(name).ok_or_else(|| format!("there is no category named {}", name))
+}
+
+fn parse_max_findings(max: &str) -> Result<usize, String> {
+    match max.parse::<usize>() {
+        Ok(0) => Err("the maximum must be at least 1; leave it out for no limit".to_string()),
+        Ok(max) => Ok(max),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ProfileName {
+    Internal,
+    External,
+    Strict,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+    #[cfg(feature = "miette")]
+    Pretty,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum TagReportFormat {
+    Markdown,
+    Json,
+}
+
+fn main() -> Result<ExitCode> {
+    let args = Args::parse();
+
+    if let Some(code) = &args.explain {
+        let lint = openapi_lint::explain(code)
+            .with_context(|| format!("there is no check with the code {}", code))?;
+        let categories = lint.categories().iter().map(|category| category.name());
+        println!(
+            "{}\n\n{}\n\nCategories: {}\n{}",
+            lint.rationale(),
+            lint.fix(),
+            categories.collect::<Vec<_>>().join(", "),
+            lint.url(),
+        );
+        return Ok(ExitCode::SUCCESS);
+    }
+    let spec = args
+        .spec
+        .as_ref()
+        .expect("a spec is required without --explain");
+
+    let contents = std::fs::read_to_string(spec)
+        .with_context(|| format!("failed to read {}", spec.display()))?;
+    // The fixed spec is written back as JSON, in the order of the original;
+    // there's no writing YAML back as it was (e.g. with its comments).
+    if args.fix.is_some() && serde_json::from_str::<IgnoredAny>(&contents).is_err() {
+        anyhow::bail!(
+            "--fix only rewrites specs in JSON, and {} isn't JSON",
+            spec.display(),
+        );
+    }
+    let documents = if args.each_document {
+        openapi_lint::parse_stream(&contents).map(|documents| {
+            documents
+                .into_iter()
+                .map(|document| (document.line(), document.text(), document.into_value()))
+                .collect()
+        })
+    } else {
+        openapi_lint::parse_document(&contents).map(|value| vec![(1, contents.as_str(), value)])
+    };
+    let documents = documents.map_err(|err| {
+        let context = match err {
+            InputError::MultipleDocuments { .. } => format!(
+                "failed to parse {}; to validate each of its documents, use --each-document",
+                spec.display(),
+            ),
+            _ => format!("failed to parse {}", spec.display()),
+        };
+        anyhow::Error::new(err).context(context)
+    })?;
+
+    if let Some(format) = args.tag_report {
+        let [(_, _, value)] = <[_; 1]>::try_from(documents).expect("a single document");
+        let (value, _) = load(spec, value)?;
+        let spec = serde_json::from_value::<OpenAPI>(value)
+            .with_context(|| format!("{} is not a valid OpenAPI document", spec.display()))?;
+        let report = openapi_lint::tag_report(&spec);
+        match format {
+            TagReportFormat::Markdown => print!("{}", report.to_markdown()),
+            TagReportFormat::Json => {
+                println!("{}", serde_json::to_string_pretty(&report.to_json())?)
+            }
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let mut config = match &args.config {
+        Some(path) => Config::load(path)?,
+        None if Path::new(CONFIG_FILE).exists() => Config::load(CONFIG_FILE)?,
+        None => Config::default(),
+    };
+    if let Some(profile) = args.profile {
+        config.with_profile(&match profile {
+            ProfileName::Internal => Profile::internal(),
+            ProfileName::External => Profile::external(),
+            ProfileName::Strict => Profile::strict(),
+        });
+    }
+    if let Some(external) = args.external {
+        config.with_external(external);
+    }
+    for &category in &args.deny_category {
+        config.with_category_severity(category, Severity::Error);
+    }
+    for &category in &args.allow_category {
+        config.with_category(category, false);
+    }
+    if let Some(strict_parse) = args.strict_parse {
+        config.with_strict_parse(strict_parse);
+    }
+    if let Some(draft_2020_12) = args.draft_2020_12 {
+        config.with_draft_2020_12(draft_2020_12);
+    }
+    let max_findings = match args.fail_fast {
+        true => Some(1),
+        false => args.max_findings.or(config.max_findings()),
+    };
+    // Problems that the baseline accounts for don't count toward the
+    // maximum, so with a baseline every problem is looked for.
+    config.with_max_findings(max_findings.filter(|_| args.baseline.is_none()));
+
+    let mut findings = Vec::new();
+    let mut truncated = false;
+    let mut json = serde_json::json!({
+        "config": config.to_json(),
+        "skipped": [],
+        "waived": [],
+    });
+    for (line, text, value) in documents {
+        let source = SourceMap::new(spec.display().to_string(), text).with_first_line(line);
+        let (document_findings, report) = validate(&args, &config, spec, &source, value)?;
+        findings.extend(document_findings);
+        // The skipped constructs and applied waivers of each document are
+        // reported together.
+        if let Some(report) = report {
+            truncated |= report["truncated"] == true;
+            for key in ["skipped", "waived"] {
+                if let (Some(all), Some(more)) = (json[key].as_array_mut(), report[key].as_array())
+                {
+                    all.extend(more.iter().cloned());
+                }
+            }
+        }
+    }
+
+    if let Some(path) = &args.baseline {
+        if args.write_baseline {
+            let json = serde_json::to_string_pretty(&Baseline::new(&findings).to_json())?;
+            std::fs::write(path, json + "\n")
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            eprintln!("wrote {} problems to {}", findings.len(), path.display());
+            return Ok(ExitCode::SUCCESS);
+        }
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let baseline = serde_json::from_str(&contents)
+            .and_then(Baseline::from_json)
+            .with_context(|| format!("{} is not a valid baseline", path.display()))?;
+        findings = baseline.filter(findings);
+    }
+
+    // Only errors are fatal, so that new checks can be rolled out as
+    // warnings. A spec that wasn't checked to the end may have errors in the
+    // rest, so it doesn't pass either; problems that were found but go
+    // unreported past the maximum were checked, though.
+    let failed = truncated
+        || findings
+            .iter()
+            .any(|finding| finding.severity() >= Severity::Error);
+    if let Some(max) = max_findings {
+        truncated |= findings.len() > max;
+        findings.truncate(max);
+    }
+    if truncated && !matches!(args.format, Format::Json) {
+        eprintln!(
+            "stopped after {} problems; the rest of the spec wasn't checked",
+            findings.len(),
+        );
+    }
+
+    // The findings are those of the document checks too, less those that the
+    // baseline accounts for.
+    json["findings"] = serde_json::to_value(&findings)?;
+    json["truncated"] = serde_json::Value::from(truncated);
+    let count = |severity| {
+        findings
+            .iter()
+            .filter(|finding| finding.severity() == severity)
+            .count()
+    };
+    json["summary"] = serde_json::json!({
+        "errors": count(Severity::Error),
+        "warnings": count(Severity::Warning),
+        "advice": count(Severity::Advice),
+        "passed": !failed,
+    });
+    if let Some(path) = &args.report_out {
+        let report = serde_json::to_string_pretty(&json)?;
+        std::fs::write(path, report + "\n")
+            .with_context(|| format!("failed to write {}", path.display()))?;
+    }
+
+    match args.format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(&json)?),
+        Format::Text if !findings.is_empty() => {
+            let rendered = findings
+                .iter()
+                .map(|finding| {
+                    let location = finding.location();
+                    let text = if args.verbose {
+                        format!("{:#}", finding)
+                    } else {
+                        finding.to_string()
+                    };
+                    match (location.file(), location.position()) {
+                        (Some(file), Some(position)) => format!(
+                            "{}:{}: {}[{}]: {}",
+                            file,
+                            position,
+                            finding.severity(),
+                            finding.code(),
+                            text,
+                        ),
+                        _ => format!("{}[{}]: {}", finding.severity(), finding.code(), text),
+                    }
+                })
+                .collect::<Vec<_>>();
+            println!("{}", rendered.join("\n\n"));
+        }
+        Format::Text => {}
+        #[cfg(feature = "miette")]
+        Format::Pretty => {
+            let text = std::sync::Arc::<str>::from(contents);
+            let handler = miette::GraphicalReportHandler::new();
+            for finding in &findings {
+                let mut rendered = String::new();
+                handler.render_report(&mut rendered, &finding.with_source_code(text.clone()))?;
+                println!("{}", rendered);
+            }
+        }
+    }
+
+    if failed {
+        Ok(ExitCode::FAILURE)
+    } else {
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+/// Bundle the documents to which a spec refers into it, and convert it to
+/// OpenAPI 3.0 if it is an OpenAPI 3.1 document, noting whether it was.
+fn load(spec: &Path, value: serde_json::Value) -> Result<(serde_json::Value, bool)> {
+    // A spec split across files is checked as if it had been bundled.
+    let mut value = Resolver::new()
+        .bundle(value, spec)
+        .with_context(|| format!("failed to resolve the references in {}", spec.display()))?;
+    // An OpenAPI 3.1 document is checked as its 3.0 equivalent.
+    let converted = openapi_lint::convert_3_1(&mut value);
+    Ok((value, converted))
+}
+
+/// Validate one document, writing whatever else the arguments ask for, and
+/// return the problems found, placed in the text of the spec, along with the
+/// JSON report of the rules for the parsed spec (if it parsed).
+fn validate(
+    args: &Args,
+    config: &Config,
+    spec: &Path,
+    source: &SourceMap,
+    value: serde_json::Value,
+) -> Result<(Vec<Diagnostic>, Option<serde_json::Value>)> {
+    // The fixes are applied to the spec as it was written, which is only
+    // what was checked if there was nothing to bundle or convert.
+    let mut document = args.fix.as_ref().map(|_| value.clone());
+    let (value, converted) = load(spec, value)?;
+    if converted && document.is_some() {
+        anyhow::bail!(
+            "--fix doesn't rewrite OpenAPI 3.1 documents, which are checked as \
+            converted to 3.0",
+        );
+    }
+    if document.as_ref().is_some_and(|document| *document != value) {
+        anyhow::bail!("--fix doesn't rewrite specs that refer to other documents");
+    }
+    let mut findings = openapi_lint::validate_document(&value, config);
+    let mut json = None;
+
+    match serde_json::from_value::<OpenAPI>(value) {
+        Ok(mut spec) => {
+            restore_components_order(&mut spec, source);
+            let report = openapi_lint::validate_with_report(&spec, config);
+            if args.show_skipped {
+                for skipped in report.skipped() {
+                    eprintln!("skipped {}", skipped);
+                }
+            }
+            json = Some(report.to_json());
+            findings.extend(report.into_findings());
+
+            if let Some(path) = &args.rename_map {
+                let map = openapi_lint::rename_map(&spec);
+                let json = serde_json::to_string_pretty(&map.to_json())?;
+                std::fs::write(path, json + "\n")
+                    .with_context(|| format!("failed to write {}", path.display()))?;
+            }
+            if let Some(path) = &args.attribute_plan {
+                let plan = openapi_lint::attribute_plan(&spec);
+                let json = serde_json::to_string_pretty(&plan.to_json())?;
+                std::fs::write(path, json + "\n")
+                    .with_context(|| format!("failed to write {}", path.display()))?;
+            }
+            if let Some(path) = &args.sort_components {
+                openapi_lint::sort_components(&mut spec);
+                let json = serde_json::to_string_pretty(&spec)?;
+                std::fs::write(path, json + "\n")
+                    .with_context(|| format!("failed to write {}", path.display()))?;
+            }
+        }
+        // Schemas that have yet to be converted (e.g. with type arrays) may
+        // not parse as OpenAPI 3.0, nor may what's left of an OpenAPI 3.1
+        // document that couldn't be fully converted; the document checks are
+        // all we can do.
+        Err(_) if config.draft_2020_12() || converted => {}
+        Err(err) => {
+            return Err(err)
+                .with_context(|| format!("{} is not a valid OpenAPI document", spec.display()))
+        }
+    }
+
+    if let (Some(path), Some(document)) = (&args.fix, &mut document) {
+        let fixes = openapi_lint::apply_fixes(document, findings);
+        // A renamed (or moved) field keeps the place of the original.
+        let mut renamed = BTreeMap::new();
+        for finding in fixes.fixed() {
+            let (Some(pointer), Some(suggestion)) =
+                (finding.location().pointer(), finding.suggestion())
+            else {
+                continue;
+            };
+            let renames_key = matches!(
+                suggestion.target(),
+                Some(Target::Type | Target::Property | Target::PathSegment),
+            );
+            if renames_key || suggestion.kind() == "move" {
+                if let Some((parent, _)) = pointer.rsplit_once('/') {
+                    renamed.insert(
+                        (parent.to_string(), suggestion.to().to_string()),
+                        suggestion.from().to_string(),
+                    );
+                }
+            }
+        }
+        let json = serde_json::to_string_pretty(&InOrder {
+            value: document,
+            pointer: String::new(),
+            source,
+            renamed: &renamed,
+        })?;
+        std::fs::write(path, json + "\n")
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        eprintln!(
+            "fixed {} problems in {}; {} couldn't be fixed automatically",
+            fixes.fixed().len(),
+            path.display(),
+            fixes.unfixed().len(),
+        );
+        findings = fixes.into_unfixed();
+    }
+
+    // Place each problem in the text of the spec, for editors and CI
+    // annotations.
+    for finding in &mut findings {
+        source.annotate(finding);
+    }
+
+    Ok((findings, json))
+}
+
+/// A value from the text of a spec, serialized with the members of each
+/// object in the order in which they appear in the text, which
+/// `serde_json::Value` (sorting the keys of objects) loses. Members that
+/// aren't in the text follow the rest.
+struct InOrder<'a> {
+    value: &'a serde_json::Value,
+    /// The JSON pointer to the value in the text.
+    pointer: String,
+    source: &'a SourceMap,
+    /// The original names of renamed members, by the pointer to their object
+    /// and their new names.
+    renamed: &'a BTreeMap<(String, String), String>,
+}
+
+impl Serialize for InOrder<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.value {
+            serde_json::Value::Array(items) => {
+                let mut seq = serializer.serialize_seq(Some(items.len()))?;
+                for (index, value) in items.iter().enumerate() {
+                    seq.serialize_element(&self.member(value, index.to_string()))?;
+                }
+                seq.end()
+            }
+            serde_json::Value::Object(object) => {
+                let parent = self.source.position(&self.pointer);
+                let mut members = object
+                    .iter()
+                    .map(|(key, value)| {
+                        let name = self
+                            .renamed
+                            .get(&(self.pointer.clone(), key.clone()))
+                            .unwrap_or(key);
+                        let member = self.member(value, escape(name));
+                        let position = self
+                            .source
+                            .position(&member.pointer)
+                            .filter(|position| Some(*position) != parent)
+                            .map_or((usize::MAX, usize::MAX), |p| (p.line(), p.column()));
+                        (position, key, member)
+                    })
+                    .collect::<Vec<_>>();
+                members.sort_by_key(|(position, _, _)| *position);
+                let mut map = serializer.serialize_map(Some(members.len()))?;
+                for (_, key, member) in members {
+                    map.serialize_entry(key, &member)?;
+                }
+                map.end()
+            }
+            value => value.serialize(serializer),
+        }
+    }
+}
+
+impl<'a> InOrder<'a> {
+    fn member(&self, value: &'a serde_json::Value, key: String) -> InOrder<'a> {
+        InOrder {
+            value,
+            pointer: format!("{}/{}", self.pointer, key),
+            source: self.source,
+            renamed: self.renamed,
+        }
+    }
+}
+
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Put the entries of each components section back in the order in which
+/// they appear in the text of the spec, which `serde_json::Value` (sorting
+/// the keys of objects) loses, for the check that they are sorted. Entries
+/// brought in from other files follow the rest.
+fn restore_components_order(spec: &mut OpenAPI, source: &SourceMap) {
+    fn restore<T>(source: &SourceMap, section: &str, entries: &mut IndexMap<String, T>) {
+        let parent = source.position(&format!("/components/{}", section));
+        let position = |name: &str| {
+            let name = escape(name);
+            source
+                .position(&format!("/components/{}/{}", section, name))
+                .filter(|position| Some(*position) != parent)
+                .map_or((usize::MAX, usize::MAX), |p| (p.line(), p.column()))
+        };
+        entries.sort_by(|a, _, b, _| position(a).cmp(&position(b)));
+    }
+
+    let Some(components) = &mut spec.components else {
+        return;
+    };
+    restore(source, "securitySchemes", &mut components.security_schemes);
+    restore(source, "responses", &mut components.responses);
+    restore(source, "parameters", &mut components.parameters);
+    restore(source, "examples", &mut components.examples);
+    restore(source, "requestBodies", &mut components.request_bodies);
+    restore(source, "headers", &mut components.headers);
+    restore(source, "schemas", &mut components.schemas);
+    restore(source, "links", &mut components.links);
+    restore(source, "callbacks", &mut components.callbacks);
+}
@@ -0,0 +1,248 @@
+// Copyright 2026 Oxide Computer Company
+
+//! The naming conventions that the linter applies, exposed so that code
+//! generating OpenAPI content (e.g. endpoint macros) can check names up front
+//! using exactly the same logic.
+//!
+//! ```
+//! use openapi_lint::naming::{suggest_rename, Target};
+//!
+//! assert!(openapi_lint::naming::is_valid_type_name("InstanceCreate"));
+//! assert_eq!(
+//!     suggest_rename("timeCreated", Target::Property).as_deref(),
+//!     Some("time_created"),
+//! );
+//! assert_eq!(suggest_rename("time_created", Target::Property), None);
+//! ```
+
+use std::collections::BTreeMap;
+
+use heck::{ToKebabCase, ToLowerCamelCase, ToPascalCase, ToShoutySnakeCase, ToSnakeCase};
+use serde::{Deserialize, Serialize};
+
+/// The kind of element being named. New kinds may be added without a major
+/// version bump, so matches on this must have a wildcard arm. Kinds serialize
+/// as their [`Target::kind`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Target {
+    /// A named component schema; `PascalCase`.
+    Type,
+    /// An object property; `snake_case`.
+    Property,
+    /// An operation parameter; `snake_case`.
+    Parameter,
+    /// An operation ID; `snake_case`.
+    OperationId,
+    /// A string enumeration value; `snake_case` or `SCREAMING_SNAKE_CASE`.
+    EnumValue,
+    /// A literal (i.e. non-parameter) path segment; `kebab-case`.
+    PathSegment,
+}
+
+impl Target {
+    /// The case convention for the kind of element; enumeration values may
+    /// also be `SCREAMING_SNAKE_CASE`.
+    pub fn case(self) -> Case {
+        match self {
+            Target::Type => Case::Pascal,
+            Target::Property | Target::Parameter | Target::OperationId | Target::EnumValue => {
+                Case::Snake
+            }
+            Target::PathSegment => Case::Kebab,
+        }
+    }
+
+    /// The name of the kind in `snake_case`, as used for the keys of a
+    /// [`RenameMap`] rendered as JSON.
+    pub fn kind(self) -> &'static str {
+        match self {
+            Target::Type => "type",
+            Target::Property => "property",
+            Target::Parameter => "parameter",
+            Target::OperationId => "operation_id",
+            Target::EnumValue => "enum_value",
+            Target::PathSegment => "path_segment",
+        }
+    }
+}
+
+/// A case convention for names, which may be configured for each kind of
+/// element with [`crate::Config::with_case`]. New conventions may be added
+/// without a major version bump, so matches on this must have a wildcard
+/// arm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Case {
+    /// `snake_case`
+    Snake,
+    /// `SCREAMING_SNAKE_CASE`
+    ScreamingSnake,
+    /// `kebab-case`
+    Kebab,
+    /// `PascalCase`
+    Pascal,
+    /// `camelCase`
+    Camel,
+}
+
+impl Case {
+    /// The name of the convention, which is also its name for serde's
+    /// `rename_all`, e.g. "snake_case" or "camelCase".
+    pub fn name(self) -> &'static str {
+        match self {
+            Case::Snake => "snake_case",
+            Case::ScreamingSnake => "SCREAMING_SNAKE_CASE",
+            Case::Kebab => "kebab-case",
+            Case::Pascal => "PascalCase",
+            Case::Camel => "camelCase",
+        }
+    }
+
+    /// The convention with the given name, as returned by [`Case::name`].
+    pub fn by_name(name: &str) -> Option<Self> {
+        [
+            Case::Snake,
+            Case::ScreamingSnake,
+            Case::Kebab,
+            Case::Pascal,
+            Case::Camel,
+        ]
+        .into_iter()
+        .find(|case| case.name() == name)
+    }
+
+    /// Whether `name` follows the convention.
+    pub fn is(self, name: &str) -> bool {
+        name == self.convert(name)
+    }
+
+    /// `name` converted to the convention.
+    pub fn convert(self, name: &str) -> String {
+        match self {
+            Case::Snake => name.to_snake_case(),
+            Case::ScreamingSnake => name.to_shouty_snake_case(),
+            Case::Kebab => name.to_kebab_case(),
+            Case::Pascal => name.to_pascal_case(),
+            Case::Camel => name.to_lower_camel_case(),
+        }
+    }
+}
+
+impl std::fmt::Display for Case {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The renames that would bring every name in a spec in line with the
+/// conventions, grouped by kind; see [`crate::rename_map`]. Each name appears
+/// once however many times it is used, so that the renames can be applied as a
+/// batch (e.g. as `#[serde(rename)]` attributes) rather than finding by
+/// finding.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RenameMap {
+    pub(crate) renames: BTreeMap<Target, BTreeMap<String, String>>,
+}
+
+impl RenameMap {
+    /// Note the rename for `name`, if it needs one.
+    pub(crate) fn insert(&mut self, name: &str, target: Target) {
+        if let Some(rename) = suggest_rename(name, target) {
+            self.renames
+                .entry(target)
+                .or_default()
+                .insert(name.to_string(), rename);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.renames.is_empty()
+    }
+
+    /// The old and suggested names for the given kind, ordered by old name.
+    pub fn renames(&self, target: Target) -> impl Iterator<Item = (&str, &str)> {
+        self.renames
+            .get(&target)
+            .into_iter()
+            .flatten()
+            .map(|(old, new)| (old.as_str(), new.as_str()))
+    }
+
+    /// The map as a JSON object keyed by [`Target::kind`], with an object
+    /// mapping old names to new names for each kind that has renames.
+    pub fn to_json(&self) -> serde_json::Value {
+        self.renames
+            .iter()
+            .map(|(target, renames)| {
+                let renames = renames
+                    .iter()
+                    .map(|(old, new)| (old.clone(), serde_json::Value::from(new.as_str())))
+                    .collect::<serde_json::Map<_, _>>();
+                (
+                    target.kind().to_string(),
+                    serde_json::Value::Object(renames),
+                )
+            })
+            .collect::<serde_json::Map<_, _>>()
+            .into()
+    }
+}
+
+pub fn is_valid_type_name(name: &str) -> bool {
+    is_valid(name, Target::Type)
+}
+
+pub fn is_valid_property_name(name: &str) -> bool {
+    is_valid(name, Target::Property)
+}
+
+pub fn is_valid_parameter_name(name: &str) -> bool {
+    is_valid(name, Target::Parameter)
+}
+
+pub fn is_valid_operation_id(name: &str) -> bool {
+    is_valid(name, Target::OperationId)
+}
+
+pub fn is_valid_enum_value(name: &str) -> bool {
+    is_valid(name, Target::EnumValue)
+}
+
+pub fn is_valid_path_segment(name: &str) -> bool {
+    is_valid(name, Target::PathSegment)
+}
+
+/// Whether `name` follows the convention for the given target.
+pub fn is_valid(name: &str, target: Target) -> bool {
+    match target {
+        Target::EnumValue => Case::Snake.is(name) || Case::ScreamingSnake.is(name),
+        _ => target.case().is(name),
+    }
+}
+
+/// The name that `name` should be renamed to for the given target, or `None`
+/// if it already follows the convention.
+pub fn suggest_rename(name: &str, target: Target) -> Option<String> {
+    (!is_valid(name, target)).then(|| target.case().convert(name))
+}
+
+/// `name` without leading, trailing, or repeated underscores (typically left
+/// behind by mechanical renames), or `None` if it has none.
+///
+/// ```
+/// use openapi_lint::naming::trim_underscores;
+///
+/// assert_eq!(trim_underscores("time__created_").as_deref(), Some("time_created"));
+/// assert_eq!(trim_underscores("time_created"), None);
+/// ```
+pub fn trim_underscores(name: &str) -> Option<String> {
+    let trimmed = name
+        .split('_')
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("_");
+    (!trimmed.is_empty() && trimmed != name).then_some(trimmed)
+}
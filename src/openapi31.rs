@@ -0,0 +1,250 @@
+// Copyright 2026 Oxide Computer Company
+
+//! Rewriting OpenAPI 3.1 documents in terms of OpenAPI 3.0, which is what the
+//! rules (and the `openapiv3` crate) understand.
+
+use std::collections::BTreeMap;
+
+use serde_json::{json, Map, Value};
+
+use crate::fields;
+
+/// Whether a raw document declares itself to be OpenAPI 3.1.
+pub fn is_3_1(document: &Value) -> bool {
+    document
+        .get("openapi")
+        .and_then(Value::as_str)
+        .is_some_and(|version| version.starts_with("3.1"))
+}
+
+/// Rewrite an OpenAPI 3.1 document as OpenAPI 3.0, so that it can be parsed
+/// and checked like any other; returns whether the document was 3.1 (others
+/// are left alone).
+///
+/// Within schemas, type arrays that pair a type with `"null"` become
+/// `nullable`, as do `anyOf` and `oneOf` unions with a `"null"` variant;
+/// `const` becomes a single-valued `enum`; `examples` becomes `example`;
+/// numeric `exclusiveMinimum` and `exclusiveMaximum` become their boolean
+/// forms, unless an inclusive bound alongside is stricter; a `$ref` with
+/// sibling keywords is wrapped in an `allOf`; and schemas under `$defs` (or
+/// `definitions`) become component schemas, prefixed with the name of the
+/// component in which they were defined should theirs be taken.
+/// Constructs with no 3.0 equivalent are left for [`crate::validate_document`]
+/// to report. Webhooks and component path items, which 3.0 has no place for,
+/// are kept as the extensions `x-webhooks` and `x-path-items`, where they
+/// aren't checked.
+pub fn convert_3_1(document: &mut Value) -> bool {
+    if !is_3_1(document) {
+        return false;
+    }
+    let Some(root) = document.as_object_mut() else {
+        return false;
+    };
+
+    root.insert("openapi".to_string(), json!("3.0.3"));
+    root.remove("jsonSchemaDialect");
+    root.entry("paths").or_insert_with(|| json!({}));
+    rename(root, "webhooks", "x-webhooks");
+    if let Some(info) = root.get_mut("info").and_then(Value::as_object_mut) {
+        info.remove("summary");
+        if let Some(license) = info.get_mut("license").and_then(Value::as_object_mut) {
+            license.remove("identifier");
+        }
+    }
+    if let Some(components) = root.get_mut("components").and_then(Value::as_object_mut) {
+        rename(components, "pathItems", "x-path-items");
+    }
+
+    fields::for_each_schema(document, &mut |_, schema| convert_schema(schema));
+
+    // Subschemas under $defs have been converted in place; now they become
+    // components, bringing up any $defs of their own in turn. References to
+    // each (and to what's within it) follow it to where it now is.
+    let mut hoisted = BTreeMap::new();
+    loop {
+        let mut defs = Vec::new();
+        fields::for_each_schema(document, &mut |pointer, schema| {
+            for keyword in ["$defs", "definitions"] {
+                if let Some(Value::Object(map)) = schema.remove(keyword) {
+                    defs.extend(map.into_iter().map(|(name, def)| {
+                        let pointer = format!("#{}/{}/{}", pointer, keyword, fields::escape(&name));
+                        (pointer, name, def)
+                    }));
+                }
+            }
+        });
+        if defs.is_empty() {
+            break;
+        }
+        let schemas = document
+            .as_object_mut()
+            .and_then(|root| {
+                root.entry("components")
+                    .or_insert_with(|| json!({}))
+                    .as_object_mut()
+            })
+            .and_then(|components| {
+                components
+                    .entry("schemas")
+                    .or_insert_with(|| json!({}))
+                    .as_object_mut()
+            });
+        let Some(schemas) = schemas else {
+            break;
+        };
+        let mut moved = Vec::new();
+        for (pointer, name, schema) in defs {
+            let component = component_name(schemas, &pointer, &name);
+            moved.push((
+                pointer,
+                format!("#/components/schemas/{}", fields::escape(&component)),
+            ));
+            hoisted.entry(name).or_insert_with(|| component.clone());
+            schemas.insert(component, schema);
+        }
+        rewrite_references(document, &|reference| {
+            moved.iter().find_map(|(from, to)| {
+                let rest = reference.strip_prefix(from.as_str())?;
+                (rest.is_empty() || rest.starts_with('/')).then(|| format!("{}{}", to, rest))
+            })
+        });
+    }
+    // What's left are references to subschemas by name alone (e.g.
+    // "#/$defs/Name", as in a standalone JSON Schema document).
+    rewrite_references(document, &|reference| {
+        let (base, name) = reference
+            .rsplit_once("/$defs/")
+            .or_else(|| reference.rsplit_once("/definitions/"))?;
+        let name = fields::unescape(name);
+        let component = hoisted.get(&name).unwrap_or(&name);
+        base.starts_with('#')
+            .then(|| format!("#/components/schemas/{}", fields::escape(component)))
+    });
+
+    true
+}
+
+fn rename(object: &mut Map<String, Value>, from: &str, to: &str) {
+    if let Some(value) = object.remove(from) {
+        object.insert(to.to_string(), value);
+    }
+}
+
+fn convert_schema(schema: &mut Map<String, Value>) {
+    if let Some(Value::Array(types)) = schema.get("type") {
+        let null = json!("null");
+        let others = types.iter().filter(|t| **t != null).collect::<Vec<_>>();
+        // Arrays of several non-null types have no 3.0 equivalent.
+        if let ([only], true) = (others.as_slice(), types.contains(&null)) {
+            let only = (*only).clone();
+            schema.insert("type".to_string(), only);
+            schema.insert("nullable".to_string(), json!(true));
+        } else if let [only] = others.as_slice() {
+            let only = (*only).clone();
+            schema.insert("type".to_string(), only);
+        }
+    }
+
+    for keyword in ["anyOf", "oneOf"] {
+        let Some(Value::Array(variants)) = schema.get_mut(keyword) else {
+            continue;
+        };
+        let before = variants.len();
+        variants.retain(|variant| *variant != json!({ "type": "null" }));
+        if variants.len() == before {
+            continue;
+        }
+        schema.insert("nullable".to_string(), json!(true));
+        // A union of one type and null is just that type, made nullable.
+        if let Some(Value::Array(variants)) = schema.remove(keyword) {
+            let keyword = if variants.len() == 1 {
+                "allOf"
+            } else {
+                keyword
+            };
+            schema.insert(keyword.to_string(), Value::Array(variants));
+        }
+    }
+
+    if let Some(value) = schema.remove("const") {
+        schema
+            .entry("enum")
+            .or_insert_with(|| Value::Array(vec![value]));
+    }
+    if let Some(Value::Array(examples)) = schema.get("examples") {
+        if let Some(example) = examples.first().cloned() {
+            schema.entry("example").or_insert(example);
+        }
+        schema.remove("examples");
+    }
+    for (exclusive, bound) in [
+        ("exclusiveMinimum", "minimum"),
+        ("exclusiveMaximum", "maximum"),
+    ] {
+        let Some(value @ Value::Number(_)) = schema.get(exclusive).cloned() else {
+            continue;
+        };
+        // Where there's an inclusive bound too, the stricter of the two
+        // stands.
+        let limit = value.as_f64();
+        let inclusive = schema.get(bound).and_then(Value::as_f64);
+        let stricter = match (limit, inclusive) {
+            (Some(limit), Some(inclusive)) if bound == "minimum" => inclusive > limit,
+            (Some(limit), Some(inclusive)) => inclusive < limit,
+            _ => false,
+        };
+        if stricter {
+            schema.remove(exclusive);
+        } else {
+            schema.insert(bound.to_string(), value);
+            schema.insert(exclusive.to_string(), json!(true));
+        }
+    }
+
+    // A 3.0 reference ignores its siblings, such as a description.
+    if schema.len() > 1 {
+        if let Some(reference) = schema.remove("$ref") {
+            schema.insert("allOf".to_string(), json!([{ "$ref": reference }]));
+        }
+    }
+}
+
+/// The name of the component that a subschema under `$defs` becomes: its
+/// own, unless a component already has it, in which case it's prefixed with
+/// the name of the component in which it was defined (and numbered, should
+/// that be taken too).
+fn component_name(schemas: &Map<String, Value>, pointer: &str, name: &str) -> String {
+    if !schemas.contains_key(name) {
+        return name.to_string();
+    }
+    let parent = pointer
+        .strip_prefix("#/components/schemas/")
+        .and_then(|rest| rest.split('/').next())
+        .map(fields::unescape)
+        .unwrap_or_default();
+    let prefixed = format!("{}{}", parent, name);
+    std::iter::once(prefixed.clone())
+        .chain((2..).map(|n| format!("{}{}", prefixed, n)))
+        .find(|candidate| !schemas.contains_key(candidate))
+        .expect("some name is free")
+}
+
+/// Replace each `$ref` for which `rewrite` returns a new reference.
+fn rewrite_references(value: &mut Value, rewrite: &dyn Fn(&str) -> Option<String>) {
+    match value {
+        Value::Object(object) => {
+            if let Some(Value::String(reference)) = object.get_mut("$ref") {
+                if let Some(rewritten) = rewrite(reference) {
+                    *reference = rewritten;
+                }
+            }
+            object
+                .values_mut()
+                .for_each(|value| rewrite_references(value, rewrite));
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| rewrite_references(item, rewrite)),
+        _ => {}
+    }
+}
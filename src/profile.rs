@@ -0,0 +1,118 @@
+// Copyright 2026 Oxide Computer Company
+
+use std::collections::BTreeMap;
+
+use crate::Severity;
+
+/// A named selection of checks and their severities, applied with
+/// [`crate::ValidatorBuilder::profile`] or [`crate::Config::with_profile`].
+///
+/// The built-in profiles are [`Profile::internal`] (the default),
+/// [`Profile::external`], and [`Profile::strict`]; an organization can define
+/// its own with [`Profile::new`]:
+///
+/// ```
+/// use openapi_lint::{Profile, Severity};
+///
+/// let profile = Profile::new("acme")
+///     .external_rules(true)
+///     .disable("docs/html")
+///     .severity("naming/plural-type-name", Severity::Error);
+/// let validator = openapi_lint::Validator::builder().profile(profile).build();
+/// assert_eq!(validator.config().profile(), Some("acme"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Profile {
+    pub(crate) name: String,
+    pub(crate) external: bool,
+    pub(crate) all_lints: bool,
+    pub(crate) lints: BTreeMap<String, bool>,
+    pub(crate) severities: BTreeMap<String, Severity>,
+}
+
+impl Profile {
+    /// A profile with the given name that, until changed, applies every
+    /// check other than those of the external rules at its default severity.
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Profile {
+            name: name.into(),
+            external: false,
+            all_lints: true,
+            lints: BTreeMap::new(),
+            severities: BTreeMap::new(),
+        }
+    }
+
+    /// For internal APIs: every check other than those of the external
+    /// rules, at its default severity. This is what [`crate::validate`]
+    /// applies.
+    pub fn internal() -> Self {
+        Profile::new("internal")
+    }
+
+    /// For external (i.e. published) APIs: every check at its default
+    /// severity. This is what [`crate::validate_external`] applies.
+    pub fn external() -> Self {
+        Profile::new("external").external_rules(true)
+    }
+
+    /// For APIs held to the highest standard: every check, with whatever it
+    /// finds reported as an error.
+    pub fn strict() -> Self {
+        crate::lints().fold(
+            Profile::new("strict").external_rules(true),
+            |profile, lint| profile.severity(lint.code(), Severity::Error),
+        )
+    }
+
+    /// The built-in profile with the given name: "internal", "external", or
+    /// "strict".
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "internal" => Some(Profile::internal()),
+            "external" => Some(Profile::external()),
+            "strict" => Some(Profile::strict()),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Whether to apply the rules for external APIs; see
+    /// [`crate::Config::with_external`].
+    pub fn external_rules(mut self, external: bool) -> Self {
+        self.external = external;
+        self
+    }
+
+    /// Enable the check with the given code; see
+    /// [`crate::Config::with_lint`].
+    pub fn enable<S: Into<String>>(mut self, code: S) -> Self {
+        self.lints.insert(code.into(), true);
+        self
+    }
+
+    /// Disable the check with the given code.
+    pub fn disable<S: Into<String>>(mut self, code: S) -> Self {
+        self.lints.insert(code.into(), false);
+        self
+    }
+
+    /// Disable every check, including any enabled so far; follow with
+    /// [`Profile::enable`] to apply only a few.
+    pub fn disable_all(mut self) -> Self {
+        self.all_lints = false;
+        self.lints.clear();
+        self
+    }
+
+    /// Report what the check with the given code finds at the given
+    /// severity; see [`crate::Config::with_severity`].
+    pub fn severity<S: Into<String>>(mut self, code: S, severity: Severity) -> Self {
+        self.severities.insert(code.into(), severity);
+        self
+    }
+}
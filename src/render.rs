@@ -0,0 +1,130 @@
+// Copyright 2026 Oxide Computer Company
+
+//! Rendering problems with [miette], quoting the text of the spec around each
+//! with the element at fault underlined, which is easier to scan than plain
+//! messages when there are dozens of problems.
+
+use std::sync::Arc;
+
+use miette::{LabeledSpan, NamedSource, SourceCode, SourceOffset};
+
+use crate::{Diagnostic, Severity};
+
+/// A problem together with the text of the spec in which it was found, as
+/// returned by [`Diagnostic::with_source_code`]; it implements
+/// [`miette::Diagnostic`], so any miette handler can render it.
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// let text: Arc<str> = include_str!("tests/errors.json").into();
+/// let spec = serde_json::from_str(&text).unwrap();
+/// let source = openapi_lint::SourceMap::new("errors.json", &text);
+/// let mut finding = openapi_lint::validate(&spec).remove(0);
+/// source.annotate(&mut finding);
+///
+/// let mut rendered = String::new();
+/// miette::GraphicalReportHandler::new()
+///     .render_report(&mut rendered, &finding.with_source_code(text))
+///     .unwrap();
+/// assert!(rendered.contains("errors.json:"));
+/// ```
+pub struct Snippet<'a> {
+    diagnostic: &'a Diagnostic,
+    source: NamedSource<Arc<str>>,
+    span: Option<SourceOffset>,
+    len: usize,
+}
+
+impl Diagnostic {
+    /// The problem with the text of the spec in which it was found, for
+    /// rendering with miette. The problem is placed in the text by the file,
+    /// line, and column noted by [`crate::SourceMap::annotate`]; one that
+    /// hasn't been placed is rendered without an excerpt.
+    pub fn with_source_code(&self, text: Arc<str>) -> Snippet<'_> {
+        let location = self.location();
+        let (span, len) = match location.position() {
+            Some(position) => {
+                let offset = SourceOffset::from_location(&text, position.line(), position.column());
+                // Underline the key (or value) at the position, e.g. `"paths"`
+                // or `paths`, rather than the rest of the line.
+                let rest = &text[offset.offset()..];
+                let len = rest.find([':', ',', '\n', '\r']).unwrap_or(rest.len());
+                (Some(offset), rest[..len].trim_end().len())
+            }
+            None => (None, 0),
+        };
+        Snippet {
+            diagnostic: self,
+            source: NamedSource::new(location.file().unwrap_or("spec"), text),
+            span,
+            len,
+        }
+    }
+}
+
+impl std::fmt::Debug for Snippet<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Snippet")
+            .field("diagnostic", self.diagnostic)
+            .field("file", &self.source.name())
+            .finish()
+    }
+}
+
+impl std::fmt::Display for Snippet<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.diagnostic.message())?;
+        let sites = self
+            .diagnostic
+            .referenced_from()
+            .iter()
+            .map(|site| {
+                site.operation_id()
+                    .or(site.pointer())
+                    .unwrap_or("<unknown>")
+            })
+            .collect::<Vec<_>>();
+        if !sites.is_empty() {
+            write!(f, "\nUsed by {}", sites.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Snippet<'_> {}
+
+impl miette::Diagnostic for Snippet<'_> {
+    fn code<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        Some(Box::new(self.diagnostic.code()))
+    }
+
+    fn severity(&self) -> Option<miette::Severity> {
+        Some(match self.diagnostic.severity() {
+            Severity::Advice => miette::Severity::Advice,
+            Severity::Warning => miette::Severity::Warning,
+            Severity::Error => miette::Severity::Error,
+        })
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        match self.diagnostic.suggestion() {
+            Some(suggestion) => Some(Box::new(suggestion)),
+            None => Some(Box::new(self.diagnostic.lint().fix())),
+        }
+    }
+
+    fn url<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        Some(Box::new(self.diagnostic.url()))
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        self.span.map(|_| &self.source as &dyn SourceCode)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let span = self.span?;
+        let label = LabeledSpan::new_primary_with_span(None, (span.offset(), self.len));
+        Some(Box::new(std::iter::once(label)))
+    }
+}
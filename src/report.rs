@@ -0,0 +1,245 @@
+// Copyright 2026 Oxide Computer Company
+
+use std::collections::BTreeMap;
+
+use serde_json::{json, Value};
+
+use crate::{Category, Diagnostic, Location, Severity};
+
+/// The outcome of validating a spec: the problems found, along with the
+/// constructs that some rule couldn't check.
+///
+/// The findings can be narrowed down, keeping the rest of the report:
+///
+/// ```
+/// let spec = serde_json::from_str(include_str!("tests/errors.json")).unwrap();
+/// let config = openapi_lint::Config::default();
+/// let report = openapi_lint::validate_with_report(&spec, &config)
+///     .for_operation("projects_get")
+///     .errors_only();
+/// assert!(report
+///     .findings()
+///     .iter()
+///     .all(|finding| finding.severity() == openapi_lint::Severity::Error));
+/// ```
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct Report {
+    pub(crate) findings: Vec<Diagnostic>,
+    pub(crate) skipped: Vec<Skipped>,
+    pub(crate) waived: Vec<Waived>,
+    pub(crate) config: Value,
+    pub(crate) truncated: bool,
+}
+
+impl Report {
+    /// The problems found, as from [`crate::validate_with_config`].
+    pub fn findings(&self) -> &[Diagnostic] {
+        &self.findings
+    }
+
+    /// The constructs that were not fully checked. An empty list means that
+    /// every rule was able to examine everything it applies to.
+    pub fn skipped(&self) -> &[Skipped] {
+        &self.skipped
+    }
+
+    /// The waivers declared in the spec that waived something, one for each
+    /// code that they waived.
+    pub fn waived(&self) -> &[Waived] {
+        &self.waived
+    }
+
+    /// The configuration that was applied, as from [`crate::Config::to_json`].
+    pub fn config(&self) -> &Value {
+        &self.config
+    }
+
+    /// Whether validation stopped at the maximum number of findings set with
+    /// [`crate::Config::with_max_findings`], so that the rest of the spec
+    /// wasn't checked.
+    pub fn truncated(&self) -> bool {
+        self.truncated
+    }
+
+    pub fn into_findings(self) -> Vec<Diagnostic> {
+        self.findings
+    }
+
+    /// Whether nothing was found (e.g. after [`Report::errors_only`], whether
+    /// no errors were).
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// The report with only the findings for which `predicate` holds.
+    pub fn filter<F: FnMut(&Diagnostic) -> bool>(mut self, mut predicate: F) -> Self {
+        self.findings.retain(|finding| predicate(finding));
+        self
+    }
+
+    /// The report with only the findings of the check with the given code,
+    /// e.g. "naming/snake-case-property".
+    pub fn filter_code(self, code: &str) -> Self {
+        self.filter(|finding| finding.code() == code)
+    }
+
+    /// The report with only the findings of checks in the given category.
+    pub fn filter_category(self, category: Category) -> Self {
+        self.filter(|finding| finding.lint().categories().contains(&category))
+    }
+
+    /// The report with only the findings at or above the given severity, as
+    /// configured.
+    pub fn at_least(self, severity: Severity) -> Self {
+        self.filter(|finding| finding.severity() >= severity)
+    }
+
+    /// The report with only the errors, i.e. the findings that fail the CLI.
+    pub fn errors_only(self) -> Self {
+        self.at_least(Severity::Error)
+    }
+
+    /// The report with only the findings in the operation with the given ID,
+    /// or in a component that notes the operation as one of its
+    /// [users](Diagnostic::referenced_from).
+    pub fn for_operation(self, operation_id: &str) -> Self {
+        self.filter(|finding| {
+            finding.location().operation_id() == Some(operation_id)
+                || finding
+                    .referenced_from()
+                    .iter()
+                    .any(|site| site.operation_id() == Some(operation_id))
+        })
+    }
+
+    /// The findings in each operation, by operation ID, for routing them to
+    /// those who own the operations. A finding in a component is listed under
+    /// each operation noted as [using](Diagnostic::referenced_from) it;
+    /// findings in no operation (e.g. in a path or in the tags) are left out.
+    pub fn by_operation(&self) -> BTreeMap<&str, Vec<&Diagnostic>> {
+        let mut groups = BTreeMap::<_, Vec<_>>::new();
+        for finding in &self.findings {
+            let mut operation_ids = std::iter::once(finding.location())
+                .chain(finding.referenced_from())
+                .filter_map(Location::operation_id)
+                .collect::<Vec<_>>();
+            operation_ids.sort_unstable();
+            operation_ids.dedup();
+            for operation_id in operation_ids {
+                groups.entry(operation_id).or_default().push(finding);
+            }
+        }
+        groups
+    }
+
+    /// The findings in each component (e.g. a schema), by name, for routing
+    /// them to those who own the types; findings in no component are left
+    /// out.
+    pub fn by_component(&self) -> BTreeMap<&str, Vec<&Diagnostic>> {
+        let mut groups = BTreeMap::<_, Vec<_>>::new();
+        for finding in &self.findings {
+            if let Some(component) = finding.location().component() {
+                groups.entry(component).or_default().push(finding);
+            }
+        }
+        groups
+    }
+
+    /// The report as a JSON object, with the configuration applied, so that
+    /// it can be understood without the settings that produced it.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "config": self.config,
+            "findings": self.findings.iter().map(Diagnostic::to_json).collect::<Vec<_>>(),
+            "skipped": self
+                .skipped
+                .iter()
+                .map(|skipped| json!({
+                    "location": skipped.location,
+                    "reason": skipped.reason,
+                }))
+                .collect::<Vec<_>>(),
+            "waived": self
+                .waived
+                .iter()
+                .map(|waived| json!({
+                    "code": waived.code,
+                    "element": waived.element,
+                    "location": waived.location,
+                }))
+                .collect::<Vec<_>>(),
+            "truncated": self.truncated,
+        })
+    }
+}
+
+/// A report of the given findings alone, e.g. those of [`crate::validate`],
+/// for filtering them.
+impl From<Vec<Diagnostic>> for Report {
+    fn from(findings: Vec<Diagnostic>) -> Self {
+        Report {
+            findings,
+            ..Report::default()
+        }
+    }
+}
+
+/// A construct that a rule bailed on (e.g. a permissive schema or a
+/// composition it doesn't understand) rather than checking.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Skipped {
+    pub(crate) location: String,
+    pub(crate) reason: String,
+}
+
+impl Skipped {
+    /// Where the construct appears, e.g. "schema Instance".
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+
+    /// Why it was skipped and what therefore wasn't checked.
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl std::fmt::Display for Skipped {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.location, self.reason)
+    }
+}
+
+/// A check waived, with the `x-openapi-lint` extension, by an element of the
+/// spec.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Waived {
+    pub(crate) code: String,
+    pub(crate) element: String,
+    pub(crate) location: Location,
+}
+
+impl Waived {
+    /// The code of the check waived.
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// The element that declares the waiver, e.g. "the schema Instance".
+    pub fn element(&self) -> &str {
+        &self.element
+    }
+
+    pub fn location(&self) -> &Location {
+        &self.location
+    }
+}
+
+impl std::fmt::Display for Waived {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} waived by {}", self.code, self.element)
+    }
+}
@@ -0,0 +1,331 @@
+// Copyright 2026 Oxide Computer Company
+
+//! Resolving references to other documents, so that a spec split across
+//! files (e.g. `"$ref": "./common.yaml#/components/schemas/Error"`) can be
+//! checked without first being bundled.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use serde_json::{Map, Value};
+
+use crate::fields::unescape;
+
+/// Loads the documents to which a spec refers and bundles them into it.
+///
+/// A reference to a component of another document (e.g.
+/// `common.yaml#/components/schemas/Error`) brings the component into the
+/// spec under the same name, and becomes a reference to it there; any other
+/// reference to another document (e.g. `error.yaml`, or
+/// `common.yaml#/definitions/Error`) is replaced by what it refers to.
+/// References within the documents brought in are resolved in turn. Each
+/// document is loaded once, however often it is referred to.
+///
+/// ```no_run
+/// let path = std::path::Path::new("openapi.json");
+/// let contents = std::fs::read_to_string(path).unwrap();
+/// let document = serde_json::from_str(&contents).unwrap();
+/// let bundled = openapi_lint::Resolver::new().bundle(document, path).unwrap();
+/// let spec: openapiv3::OpenAPI = serde_json::from_value(bundled).unwrap();
+/// let findings = openapi_lint::validate(&spec);
+/// ```
+#[derive(Default)]
+pub struct Resolver {
+    documents: BTreeMap<String, Value>,
+    fetch: Option<Box<Fetch>>,
+}
+
+/// Fetches the document at a URL; see [`Resolver::with_fetch`].
+type Fetch = dyn Fn(&str) -> Result<String, String>;
+
+/// A problem with a reference to another document.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ResolveError {
+    /// A file couldn't be read, or there is no way to read files (without the
+    /// `fs` feature).
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// A URL couldn't be fetched, or there is no way to fetch URLs; see
+    /// [`Resolver::with_fetch`].
+    Fetch { url: String, message: String },
+    /// A document is neither JSON nor YAML.
+    Parse { location: String },
+    /// A reference refers to something that its document doesn't have.
+    Missing { reference: String },
+    /// A component brought in from another document has the same name as,
+    /// but differs from, one that the spec already has.
+    Conflict { kind: String, name: String },
+    /// References to other documents that, replaced by what they refer to,
+    /// would contain themselves.
+    Cycle { reference: String },
+}
+
+impl std::fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResolveError::Read { path, .. } => write!(f, "failed to read {}", path.display()),
+            ResolveError::Fetch { url, message } => {
+                write!(f, "failed to fetch {}: {}", url, message)
+            }
+            ResolveError::Parse { location } => {
+                write!(f, "{} is neither JSON nor YAML", location)
+            }
+            ResolveError::Missing { reference } => {
+                write!(f, "the reference {} refers to nothing", reference)
+            }
+            ResolveError::Conflict { kind, name } => write!(
+                f,
+                "the component {} brought in from another document differs from the \
+                {} of the same name in the spec",
+                name, kind,
+            ),
+            ResolveError::Cycle { reference } => {
+                write!(f, "the reference {} refers to itself", reference)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ResolveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ResolveError::Read { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl Resolver {
+    /// A resolver that reads files; references to URLs are errors unless a
+    /// way to fetch them is given with [`Resolver::with_fetch`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fetch the documents referred to by `http` and `https` URLs with the
+    /// given function, which returns the body of the response or a
+    /// description of what went wrong.
+    pub fn with_fetch<F>(mut self, fetch: F) -> Self
+    where
+        F: Fn(&str) -> Result<String, String> + 'static,
+    {
+        self.fetch = Some(Box::new(fetch));
+        self
+    }
+
+    /// Bundle the documents to which the spec at `path` refers into it. A
+    /// spec without such references is returned as is.
+    pub fn bundle(&mut self, mut document: Value, path: &Path) -> Result<Value, ResolveError> {
+        let mut bundle = Bundle {
+            resolver: self,
+            components: BTreeMap::new(),
+            stack: Vec::new(),
+        };
+        let root = path.to_string_lossy().into_owned();
+        bundle.resolve(&mut document, &root, true)?;
+
+        let components = std::mem::take(&mut bundle.components);
+        if components.is_empty() {
+            return Ok(document);
+        }
+        let Some(root) = document.as_object_mut() else {
+            return Ok(document);
+        };
+        let all = root
+            .entry("components")
+            .or_insert_with(|| Value::Object(Map::new()));
+        for ((kind, name), (_, component)) in components {
+            let Some(of_kind) = all
+                .as_object_mut()
+                .map(|all| {
+                    all.entry(kind.clone())
+                        .or_insert_with(|| Value::Object(Map::new()))
+                })
+                .and_then(Value::as_object_mut)
+            else {
+                continue;
+            };
+            match of_kind.get(&name) {
+                Some(existing) if *existing != component => {
+                    return Err(ResolveError::Conflict { kind, name });
+                }
+                Some(_) => {}
+                None => {
+                    of_kind.insert(name, component);
+                }
+            }
+        }
+        Ok(document)
+    }
+
+    /// The document at the given path or URL, loaded if it hasn't been.
+    fn load(&mut self, location: &str) -> Result<&Value, ResolveError> {
+        if !self.documents.contains_key(location) {
+            let contents = if is_url(location) {
+                let fetch = self.fetch.as_ref().ok_or_else(|| ResolveError::Fetch {
+                    url: location.to_string(),
+                    message: "fetching URLs isn't enabled".to_string(),
+                })?;
+                fetch(location).map_err(|message| ResolveError::Fetch {
+                    url: location.to_string(),
+                    message,
+                })?
+            } else {
+                read_file(location).map_err(|source| ResolveError::Read {
+                    path: PathBuf::from(location),
+                    source,
+                })?
+            };
+            let document = serde_json::from_str::<Value>(&contents);
+            #[cfg(feature = "yaml")]
+            let document = document.or_else(|_| serde_yaml::from_str::<Value>(&contents));
+            let document = document.map_err(|_| ResolveError::Parse {
+                location: location.to_string(),
+            })?;
+            self.documents.insert(location.to_string(), document);
+        }
+        Ok(&self.documents[location])
+    }
+}
+
+/// The state of one call to [`Resolver::bundle`].
+struct Bundle<'r> {
+    resolver: &'r mut Resolver,
+    /// The components brought in from other documents, by kind and name,
+    /// with the references by which they were first brought in.
+    components: BTreeMap<(String, String), (String, Value)>,
+    /// The references being replaced by what they refer to, innermost last.
+    stack: Vec<String>,
+}
+
+impl Bundle<'_> {
+    /// Resolve the references within a value from the document at
+    /// `location`; references within the spec itself are left as they are.
+    fn resolve(
+        &mut self,
+        value: &mut Value,
+        location: &str,
+        root: bool,
+    ) -> Result<(), ResolveError> {
+        match value {
+            Value::Object(object) => {
+                if let Some(Value::String(reference)) = object.get("$ref") {
+                    let (file, fragment) = reference.split_once('#').unwrap_or((reference, ""));
+                    if root && file.is_empty() {
+                        return Ok(());
+                    }
+                    let target = if file.is_empty() {
+                        location.to_string()
+                    } else {
+                        relative_to(location, file)
+                    };
+                    *value = self.resolve_reference(&target, fragment)?;
+                    return Ok(());
+                }
+                for value in object.values_mut() {
+                    self.resolve(value, location, root)?;
+                }
+            }
+            Value::Array(items) => {
+                for value in items {
+                    self.resolve(value, location, root)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// What a reference to `fragment` of the document at `location` becomes
+    /// in the bundle.
+    fn resolve_reference(&mut self, location: &str, fragment: &str) -> Result<Value, ResolveError> {
+        let reference = format!("{}#{}", location, fragment);
+        let mut target = self
+            .resolver
+            .load(location)?
+            .pointer(fragment)
+            .cloned()
+            .ok_or_else(|| ResolveError::Missing {
+                reference: reference.clone(),
+            })?;
+
+        let component = fragment
+            .strip_prefix("/components/")
+            .and_then(|rest| rest.split_once('/'))
+            .filter(|(_, name)| !name.contains('/'));
+        if let Some((kind, name)) = component {
+            let local = serde_json::json!({ "$ref": format!("#/components/{}/{}", kind, name) });
+            let key = (kind.to_string(), unescape(name));
+            match self.components.get(&key) {
+                Some((first, _)) if *first == reference => return Ok(local),
+                Some((_, existing)) => {
+                    // The same name brought in from another document is only
+                    // a problem if the components differ.
+                    let existing = existing.clone();
+                    self.resolve(&mut target, location, false)?;
+                    if target != existing {
+                        let (kind, name) = key;
+                        return Err(ResolveError::Conflict { kind, name });
+                    }
+                }
+                None => {
+                    // Noted before its own references are resolved, so that
+                    // a component that refers to itself refers to the
+                    // bundled copy.
+                    self.components
+                        .insert(key.clone(), (reference.clone(), Value::Null));
+                    self.resolve(&mut target, location, false)?;
+                    self.components.insert(key, (reference, target));
+                }
+            }
+            return Ok(local);
+        }
+
+        if self.stack.contains(&reference) {
+            return Err(ResolveError::Cycle { reference });
+        }
+        self.stack.push(reference);
+        self.resolve(&mut target, location, false)?;
+        self.stack.pop();
+        Ok(target)
+    }
+}
+
+/// The contents of a local file; without the `fs` feature, there is no way to
+/// read one.
+#[cfg(feature = "fs")]
+fn read_file(path: &str) -> std::io::Result<String> {
+    std::fs::read_to_string(path)
+}
+
+#[cfg(not(feature = "fs"))]
+fn read_file(_: &str) -> std::io::Result<String> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "reading files isn't enabled",
+    ))
+}
+
+fn is_url(location: &str) -> bool {
+    location.starts_with("http://") || location.starts_with("https://")
+}
+
+/// The location of `file` relative to the document at `location`.
+fn relative_to(location: &str, file: &str) -> String {
+    if is_url(file) || Path::new(file).is_absolute() {
+        return file.to_string();
+    }
+    if is_url(location) {
+        let base = location.rsplit_once('/').map_or(location, |(base, _)| base);
+        return format!("{}/{}", base, file.trim_start_matches("./"));
+    }
+    let base = Path::new(location).parent().unwrap_or(Path::new(""));
+    base.join(file.trim_start_matches("./"))
+        .to_string_lossy()
+        .into_owned()
+}
@@ -0,0 +1,386 @@
+// Copyright 2026 Oxide Computer Company
+
+//! Finding where in the text of a spec the elements of the parsed document
+//! came from, so that problems can be reported by line and column.
+
+use std::collections::BTreeMap;
+
+use crate::{fields::escape, Diagnostic};
+
+/// A line and column in the text of a spec, both counted from 1; columns
+/// count characters rather than bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position {
+    line: usize,
+    column: usize,
+}
+
+impl Position {
+    pub(crate) fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl std::fmt::Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// The positions in the text of a spec (JSON or YAML) of the elements of the
+/// document, by JSON pointer; a member of an object is at its key.
+///
+/// Block-style YAML is mapped throughout; within flow-style collections
+/// (e.g. `[a, b]`), and for elements brought in from other documents by a
+/// [`crate::Resolver`], the position is that of the nearest enclosing element
+/// that could be found.
+///
+/// ```
+/// let text = "openapi: 3.0.3\npaths:\n  /widgets:\n    get: {}\n";
+/// let map = openapi_lint::SourceMap::new("openapi.yaml", text);
+/// let position = map.position("/paths/~1widgets/get").unwrap();
+/// assert_eq!((position.line(), position.column()), (4, 5));
+/// ```
+#[derive(Clone, Debug)]
+pub struct SourceMap {
+    file: String,
+    positions: BTreeMap<String, Position>,
+}
+
+impl SourceMap {
+    /// Map the text of a spec, read from `file` (which is only used to label
+    /// findings). Text that isn't valid JSON or YAML is mapped as far as it
+    /// can be.
+    pub fn new(file: impl Into<String>, text: &str) -> Self {
+        let mut positions = BTreeMap::new();
+        if text.trim_start().starts_with(['{', '[']) {
+            JsonScanner::new(text, &mut positions).scan();
+        } else {
+            scan_yaml(text, &mut positions);
+        }
+        Self {
+            file: file.into(),
+            positions,
+        }
+    }
+
+    /// The same map, for text that starts on the given line (counted from 1)
+    /// of its file, such as one of the documents of a YAML stream.
+    pub fn with_first_line(mut self, line: usize) -> Self {
+        for position in self.positions.values_mut() {
+            position.line += line - 1;
+        }
+        self
+    }
+
+    /// The file from which the spec was read.
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    /// The position of the element at the given JSON pointer or, failing
+    /// that, of the nearest element that contains it.
+    pub fn position(&self, pointer: &str) -> Option<Position> {
+        let mut pointer = pointer;
+        loop {
+            if let Some(position) = self.positions.get(pointer) {
+                return Some(*position);
+            }
+            pointer = pointer.rsplit_once('/')?.0;
+        }
+    }
+
+    /// Note the file, line, and column of a problem, found from its JSON
+    /// pointer; problems without pointers are left as they are.
+    pub fn annotate(&self, diagnostic: &mut Diagnostic) {
+        let Some(position) = diagnostic.location.pointer().and_then(|p| self.position(p)) else {
+            return;
+        };
+        diagnostic.location.file = Some(self.file.clone());
+        diagnostic.location.position = Some(position);
+    }
+}
+
+struct JsonScanner<'a> {
+    chars: Vec<char>,
+    index: usize,
+    line: usize,
+    column: usize,
+    positions: &'a mut BTreeMap<String, Position>,
+}
+
+impl<'a> JsonScanner<'a> {
+    fn new(text: &str, positions: &'a mut BTreeMap<String, Position>) -> Self {
+        Self {
+            chars: text.chars().collect(),
+            index: 0,
+            line: 1,
+            column: 1,
+            positions,
+        }
+    }
+
+    fn scan(&mut self) {
+        self.skip_whitespace();
+        let position = self.position();
+        self.value(String::new(), position);
+    }
+
+    fn position(&self) -> Position {
+        Position {
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.index += 1;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.next();
+        }
+    }
+
+    /// Scan the value at the current position, noting `position` (which is
+    /// that of its key, for the member of an object) for it.
+    fn value(&mut self, pointer: String, position: Position) -> Option<()> {
+        self.positions.insert(pointer.clone(), position);
+        match self.peek()? {
+            '{' => {
+                self.next();
+                loop {
+                    self.skip_whitespace();
+                    if self.peek()? == '}' {
+                        self.next();
+                        return Some(());
+                    }
+                    let position = self.position();
+                    let key = self.string()?;
+                    self.skip_whitespace();
+                    (self.next()? == ':').then_some(())?;
+                    self.skip_whitespace();
+                    self.value(format!("{}/{}", pointer, escape(&key)), position)?;
+                    self.skip_whitespace();
+                    if self.next()? == '}' {
+                        return Some(());
+                    }
+                }
+            }
+            '[' => {
+                self.next();
+                for index in 0.. {
+                    self.skip_whitespace();
+                    if self.peek()? == ']' {
+                        self.next();
+                        return Some(());
+                    }
+                    let position = self.position();
+                    self.value(format!("{}/{}", pointer, index), position)?;
+                    self.skip_whitespace();
+                    if self.next()? == ']' {
+                        return Some(());
+                    }
+                }
+                Some(())
+            }
+            '"' => self.string().map(|_| ()),
+            _ => {
+                while !self
+                    .peek()
+                    .is_none_or(|c| c.is_whitespace() || matches!(c, ',' | ']' | '}'))
+                {
+                    self.next();
+                }
+                Some(())
+            }
+        }
+    }
+
+    /// Scan a string, returning its contents with escapes resolved.
+    fn string(&mut self) -> Option<String> {
+        let start = self.index;
+        (self.next()? == '"').then_some(())?;
+        loop {
+            match self.next()? {
+                '\\' => {
+                    self.next()?;
+                }
+                '"' => break,
+                _ => {}
+            }
+        }
+        let raw = self.chars[start..self.index].iter().collect::<String>();
+        serde_json::from_str(&raw).ok()
+    }
+}
+
+/// A collection in block-style YAML whose entries are indented by `indent`.
+struct Frame {
+    indent: usize,
+    pointer: String,
+    /// The index of the next item, for a sequence.
+    next_item: Option<usize>,
+}
+
+fn scan_yaml(text: &str, positions: &mut BTreeMap<String, Position>) {
+    positions.insert(String::new(), Position { line: 1, column: 1 });
+    let mut stack = vec![Frame {
+        indent: 0,
+        pointer: String::new(),
+        next_item: None,
+    }];
+    // The pointer of a key or item whose value is on the following lines.
+    let mut pending: Option<String> = None;
+    // The indentation of a block scalar's key, for skipping its lines.
+    let mut block_scalar: Option<usize> = None;
+
+    for (line, text) in text.lines().enumerate() {
+        let content = text.trim_start_matches(' ');
+        let indent = text.len() - content.len();
+        if content.is_empty() || content.starts_with('#') {
+            continue;
+        }
+        if content.starts_with("---") || content.starts_with("...") {
+            continue;
+        }
+        match block_scalar {
+            Some(key_indent) if indent > key_indent => continue,
+            _ => block_scalar = None,
+        }
+
+        while stack.len() > 1 && stack.last().is_some_and(|frame| frame.indent > indent) {
+            stack.pop();
+        }
+        let is_item = content == "-" || content.starts_with("- ");
+        let top = stack.last().map_or(0, |frame| frame.indent);
+        match pending.take() {
+            // The value of a key, or the contents of an item, is a
+            // collection; a sequence may be indented no further than its key.
+            Some(pointer) if indent > top || (indent == top && is_item) => {
+                stack.push(Frame {
+                    indent,
+                    pointer,
+                    next_item: is_item.then_some(0),
+                });
+            }
+            // The continuation of a multi-line scalar.
+            None if indent > top => continue,
+            _ => {}
+        }
+        // A sequence indented no further than its key ends with its last item.
+        if !is_item
+            && stack.len() > 1
+            && stack
+                .last()
+                .is_some_and(|frame| frame.indent == indent && frame.next_item.is_some())
+        {
+            stack.pop();
+        }
+
+        let (pending_value, scalar) = yaml_entry(&mut stack, content, indent, line + 1, positions);
+        pending = pending_value;
+        if scalar {
+            block_scalar = Some(indent);
+        }
+    }
+}
+
+/// Note the position of the entry (a key or an item, possibly with more
+/// entries of a collection within it on the same line) at the start of
+/// `content`. Returns the pointer of the entry if its value is on the
+/// following lines, and whether that value is a block scalar.
+fn yaml_entry(
+    stack: &mut Vec<Frame>,
+    content: &str,
+    indent: usize,
+    line: usize,
+    positions: &mut BTreeMap<String, Position>,
+) -> (Option<String>, bool) {
+    let Some(frame) = stack.last_mut() else {
+        return (None, false);
+    };
+    let column = indent + 1;
+
+    if content == "-" || content.starts_with("- ") {
+        let index = frame.next_item.unwrap_or(0);
+        frame.next_item = Some(index + 1);
+        let pointer = format!("{}/{}", frame.pointer, index);
+        positions.insert(pointer.clone(), Position { line, column });
+        let rest = content[1..].trim_start_matches(' ');
+        if rest.is_empty() {
+            return (Some(pointer), false);
+        }
+        let rest_indent = indent + (content.len() - rest.len());
+        let is_item = rest == "-" || rest.starts_with("- ");
+        if !is_item && split_key(rest).is_none() {
+            return (None, false);
+        }
+        // A collection starts on the same line as its item.
+        stack.push(Frame {
+            indent: rest_indent,
+            pointer,
+            next_item: is_item.then_some(0),
+        });
+        return yaml_entry(stack, rest, rest_indent, line, positions);
+    }
+
+    let Some((key, value)) = split_key(content) else {
+        return (None, false);
+    };
+    let pointer = format!("{}/{}", frame.pointer, escape(&key));
+    positions.insert(pointer.clone(), Position { line, column });
+    let value = value.trim();
+    if value.is_empty() || value.starts_with('#') {
+        (Some(pointer), false)
+    } else {
+        (None, value.starts_with(['|', '>']))
+    }
+}
+
+/// The key of a mapping entry and the rest of the line, if `content` is one.
+fn split_key(content: &str) -> Option<(String, &str)> {
+    let (key, rest) = match content.chars().next()? {
+        // A flow collection, rather than a key.
+        '{' | '[' => return None,
+        quote @ ('"' | '\'') => {
+            let end = content[1..].find(quote)? + 1;
+            let key = &content[..=end];
+            let key = if quote == '"' {
+                serde_json::from_str(key).ok()?
+            } else {
+                key[1..key.len() - 1].replace("''", "'")
+            };
+            (key, content[end + 1..].strip_prefix(':')?)
+        }
+        _ => {
+            let end = content
+                .match_indices(':')
+                .map(|(i, _)| i)
+                .find(|&i| content[i + 1..].is_empty() || content[i + 1..].starts_with(' '))?;
+            (content[..end].trim_end().to_string(), &content[end + 1..])
+        }
+    };
+    (rest.is_empty() || rest.starts_with(' ')).then_some((key, rest))
+}
@@ -0,0 +1,188 @@
+// Copyright 2026 Oxide Computer Company
+
+use std::collections::BTreeMap;
+
+use serde_json::{json, Value};
+
+/// The operations of a spec grouped by tag, for planning SDKs that generate a
+/// module per tag; see [`crate::tag_report`]. Tags with a single operation
+/// and operations with several tags, both of which make for awkward modules,
+/// are called out.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TagReport {
+    pub(crate) tags: BTreeMap<String, Vec<TaggedOperation>>,
+    pub(crate) untagged: Vec<TaggedOperation>,
+}
+
+/// An operation as listed in a [`TagReport`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TaggedOperation {
+    pub(crate) operation_id: Option<String>,
+    pub(crate) method: String,
+    pub(crate) path: String,
+    pub(crate) success_type: Option<String>,
+    pub(crate) tags: Vec<String>,
+}
+
+impl TagReport {
+    pub(crate) fn insert(&mut self, operation: TaggedOperation) {
+        if operation.tags.is_empty() {
+            self.untagged.push(operation);
+            return;
+        }
+        for tag in &operation.tags {
+            self.tags
+                .entry(tag.clone())
+                .or_default()
+                .push(operation.clone());
+        }
+    }
+
+    /// Each tag, ordered by name, with its operations in the order in which
+    /// the spec lists them. An operation with several tags is listed under
+    /// each.
+    pub fn tags(&self) -> impl Iterator<Item = (&str, &[TaggedOperation])> {
+        self.tags
+            .iter()
+            .map(|(tag, operations)| (tag.as_str(), operations.as_slice()))
+    }
+
+    /// The operations that have no tag.
+    pub fn untagged(&self) -> &[TaggedOperation] {
+        &self.untagged
+    }
+
+    /// The tags that have only one operation.
+    pub fn single_operation_tags(&self) -> impl Iterator<Item = &str> {
+        self.tags()
+            .filter(|(_, operations)| operations.len() == 1)
+            .map(|(tag, _)| tag)
+    }
+
+    /// The operations that have more than one tag, each listed once.
+    pub fn multi_tag_operations(&self) -> impl Iterator<Item = &TaggedOperation> {
+        self.tags.iter().flat_map(|(tag, operations)| {
+            // List each operation under the first of its tags only.
+            operations
+                .iter()
+                .filter(move |op| op.tags.len() > 1 && op.tags.iter().min() == Some(tag))
+        })
+    }
+
+    /// The report as a JSON object with the operations by tag, the untagged
+    /// operations, and the tags and operations called out.
+    pub fn to_json(&self) -> Value {
+        let tags = self
+            .tags()
+            .map(|(tag, operations)| {
+                let operations = operations.iter().map(TaggedOperation::to_json);
+                (tag.to_string(), Value::Array(operations.collect()))
+            })
+            .collect::<serde_json::Map<_, _>>();
+        json!({
+            "tags": tags,
+            "untagged": self.untagged.iter().map(TaggedOperation::to_json).collect::<Vec<_>>(),
+            "single_operation_tags": self.single_operation_tags().collect::<Vec<_>>(),
+            "multi_tag_operations": self
+                .multi_tag_operations()
+                .map(TaggedOperation::to_json)
+                .collect::<Vec<_>>(),
+        })
+    }
+
+    /// The report as a Markdown document: the tags and operations called
+    /// out, followed by a table of operations for each tag.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("# Operations by tag\n");
+
+        let single = self
+            .single_operation_tags()
+            .map(|tag| format!("`{}`", tag))
+            .collect::<Vec<_>>();
+        if !single.is_empty() {
+            out += &format!("\nTags with a single operation: {}.\n", single.join(", "));
+        }
+        let multi = self
+            .multi_tag_operations()
+            .map(|op| {
+                let tags = op.tags.iter().map(|tag| format!("`{}`", tag));
+                format!("{} ({})", op.name(), tags.collect::<Vec<_>>().join(", "))
+            })
+            .collect::<Vec<_>>();
+        if !multi.is_empty() {
+            out += &format!("\nOperations with several tags: {}.\n", multi.join("; "));
+        }
+
+        let untagged = (!self.untagged.is_empty()).then_some(("Untagged", &self.untagged));
+        for (heading, operations) in self
+            .tags
+            .iter()
+            .map(|(tag, operations)| (tag.as_str(), operations))
+            .chain(untagged)
+        {
+            out += &format!(
+                "\n## {}\n\n| Operation | Method | Path | Success type |\n|---|---|---|---|\n",
+                heading,
+            );
+            for op in operations {
+                out += &format!(
+                    "| {} | {} | `{}` | {} |\n",
+                    op.name(),
+                    op.method.to_uppercase(),
+                    op.path,
+                    op.success_type
+                        .as_deref()
+                        .map_or_else(|| "-".to_string(), |ty| format!("`{}`", ty)),
+                );
+            }
+        }
+
+        out
+    }
+}
+
+impl TaggedOperation {
+    pub fn operation_id(&self) -> Option<&str> {
+        self.operation_id.as_deref()
+    }
+
+    /// The HTTP method, in lowercase.
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The type of the JSON body of the first successful response: the name
+    /// of the schema it refers to, or "inline" if the schema isn't named.
+    /// `None` if there is no such body.
+    pub fn success_type(&self) -> Option<&str> {
+        self.success_type.as_deref()
+    }
+
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// How to refer to the operation in Markdown.
+    fn name(&self) -> String {
+        match &self.operation_id {
+            Some(operation_id) => format!("`{}`", operation_id),
+            None => format!("`{} {}`", self.method.to_uppercase(), self.path),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "operation_id": self.operation_id,
+            "method": self.method,
+            "path": self.path,
+            "success_type": self.success_type,
+            "tags": self.tags,
+        })
+    }
+}
@@ -0,0 +1,191 @@
+// Copyright 2026 Oxide Computer Company
+
+use openapiv3::{OpenAPI, Operation, Schema};
+
+use crate::{Category, Check, Config, Diagnostic, Lint, Profile, Report};
+
+/// A validator that applies a particular selection of checks, built with
+/// [`Validator::builder`].
+///
+/// ```
+/// let validator = openapi_lint::Validator::builder()
+///     .external(true)
+///     .disable("naming/pascal-case-type")
+///     .build();
+/// # let spec: openapiv3::OpenAPI = serde_json::from_str(
+/// #     r#"{"openapi": "3.0.3", "info": {"title": "", "version": ""}, "paths": {}}"#,
+/// # ).unwrap();
+/// assert!(validator.validate(&spec).is_empty());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Validator {
+    config: Config,
+}
+
+/// Builds a [`Validator`]. Every check is enabled unless disabled.
+#[derive(Clone, Debug, Default)]
+pub struct ValidatorBuilder {
+    config: Config,
+}
+
+impl Validator {
+    pub fn builder() -> ValidatorBuilder {
+        ValidatorBuilder::default()
+    }
+
+    /// A validator with the settings in the given file (e.g.
+    /// `openapi-lint.toml`); see [`Config::load`].
+    #[cfg(feature = "fs")]
+    pub fn from_config<P: AsRef<std::path::Path>>(
+        path: P,
+    ) -> Result<Validator, crate::ConfigError> {
+        Ok(Validator {
+            config: Config::load(path)?,
+        })
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// As [`crate::validate_with_config`].
+    pub fn validate(&self, spec: &OpenAPI) -> Vec<Diagnostic> {
+        crate::validate_with_config(spec, &self.config)
+    }
+
+    /// As [`crate::validate_iter_with_config`].
+    pub fn validate_iter<'a>(&'a self, spec: &'a OpenAPI) -> impl Iterator<Item = Diagnostic> + 'a {
+        crate::validate_iter_with_config(spec, &self.config)
+    }
+
+    /// As [`crate::validate_with_report`].
+    pub fn validate_with_report(&self, spec: &OpenAPI) -> Report {
+        crate::validate_with_report(spec, &self.config)
+    }
+
+    /// As [`crate::validate_document`].
+    pub fn validate_document(&self, value: &serde_json::Value) -> Vec<Diagnostic> {
+        crate::validate_document(value, &self.config)
+    }
+
+    /// As [`crate::validate_value_with_config`].
+    pub fn validate_value(&self, value: &serde_json::Value) -> Vec<Diagnostic> {
+        crate::validate_value_with_config(value, &self.config)
+    }
+}
+
+impl ValidatorBuilder {
+    /// Start from the given settings rather than the defaults, keeping the
+    /// checks it enables and disables.
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Apply the checks and severities of the given profile, replacing any
+    /// selection made so far; see [`Config::with_profile`].
+    pub fn profile(mut self, profile: Profile) -> Self {
+        self.config.with_profile(&profile);
+        self
+    }
+
+    /// Also apply the rules for external (i.e. published) APIs; see
+    /// [`Config::with_external`].
+    pub fn external(mut self, external: bool) -> Self {
+        self.config.with_external(external);
+        self
+    }
+
+    /// Enable the check with the given code (e.g. "naming/pascal-case-type");
+    /// see [`Config::with_lint`].
+    pub fn enable<S: Into<String>>(mut self, code: S) -> Self {
+        self.config.with_lint(code, true);
+        self
+    }
+
+    /// Disable the check with the given code.
+    pub fn disable<S: Into<String>>(mut self, code: S) -> Self {
+        self.config.with_lint(code, false);
+        self
+    }
+
+    /// Enable the checks in the given category; see
+    /// [`Config::with_category`].
+    pub fn enable_category(mut self, category: Category) -> Self {
+        self.config.with_category(category, true);
+        self
+    }
+
+    /// Disable the checks in the given category.
+    pub fn disable_category(mut self, category: Category) -> Self {
+        self.config.with_category(category, false);
+        self
+    }
+
+    /// Enable every check, including any disabled so far.
+    pub fn enable_all(mut self) -> Self {
+        self.config.with_all_lints(true);
+        self
+    }
+
+    /// Disable every check, including any enabled so far; follow with
+    /// [`ValidatorBuilder::enable`] to apply only a few.
+    pub fn disable_all(mut self) -> Self {
+        self.config.with_all_lints(false);
+        self
+    }
+
+    /// Also apply a check defined outside this crate; see [`Check`].
+    pub fn check<C: Check + 'static>(mut self, check: C) -> Self {
+        self.config.with_check(check);
+        self
+    }
+
+    /// Also apply a closure to every schema; see
+    /// [`Config::with_schema_check`].
+    ///
+    /// ```
+    /// use openapi_lint::{Lint, Severity};
+    ///
+    /// static NO_FLOATS: Lint = Lint::custom(
+    ///     "acme/no-floats",
+    ///     "https://acme.example/api-guidelines#numbers",
+    ///     Severity::Warning,
+    ///     "Floating-point numbers lose precision.",
+    ///     "Use an integer or a decimal string.",
+    /// );
+    ///
+    /// let validator = openapi_lint::Validator::builder()
+    ///     .schema_check(&NO_FLOATS, |name, schema| {
+    ///         let openapiv3::SchemaKind::Type(openapiv3::Type::Number(_)) = &schema.schema_kind
+    ///         else {
+    ///             return None;
+    ///         };
+    ///         Some(format!("{} has a number", name.unwrap_or("A schema")))
+    ///     })
+    ///     .build();
+    /// ```
+    pub fn schema_check<F>(mut self, lint: &'static Lint, check: F) -> Self
+    where
+        F: Fn(Option<&str>, &Schema) -> Option<String> + Send + Sync + 'static,
+    {
+        self.config.with_schema_check(lint, check);
+        self
+    }
+
+    /// Also apply a closure to every operation; see
+    /// [`Config::with_operation_check`].
+    pub fn operation_check<F>(mut self, lint: &'static Lint, check: F) -> Self
+    where
+        F: Fn(&str, &str, &Operation) -> Option<String> + Send + Sync + 'static,
+    {
+        self.config.with_operation_check(lint, check);
+        self
+    }
+
+    pub fn build(self) -> Validator {
+        Validator {
+            config: self.config,
+        }
+    }
+}
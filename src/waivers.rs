@@ -0,0 +1,224 @@
+// Copyright 2026 Oxide Computer Company
+
+//! Waivers of checks declared where an element is defined, with the
+//! `x-openapi-lint` extension:
+//!
+//! ```json
+//! "x-openapi-lint": { "ignore": ["naming/snake-case-property"] }
+//! ```
+//!
+//! A waiver on an operation or a named schema covers everything reported
+//! within it; one on any other schema (such as that of a property) covers the
+//! checks of that schema and those of its parent that concern it. Waivers
+//! that waive nothing are reported, so that they don't outlive their reason.
+
+use std::{collections::BTreeSet, sync::Mutex};
+
+use indexmap::IndexMap;
+use openapiv3::OpenAPI;
+use serde_json::Value;
+
+use crate::{lints, report::Waived, walker::SchemaWalker, Config, Diagnostic, Location};
+
+pub(crate) const EXTENSION: &str = "x-openapi-lint";
+
+/// The waivers declared in a spec, and which of them have been used. Uses
+/// are noted behind a lock, so that schemas can be checked on several
+/// threads at once.
+#[derive(Default)]
+pub(crate) struct Waivers<'s> {
+    declared: Vec<Waiver<'s>>,
+    used: Mutex<BTreeSet<(usize, &'static str)>>,
+}
+
+struct Waiver<'s> {
+    /// The value of the extension, by which the waiver is found again from
+    /// the element that declares it.
+    value: &'s Value,
+    /// The element that declares it, for messages.
+    element: String,
+    location: Location,
+    /// The JSON pointer to the element, for waivers that cover everything
+    /// reported within it.
+    scope: Option<String>,
+    codes: Vec<String>,
+}
+
+impl<'s> Waivers<'s> {
+    pub(crate) fn collect(spec: &'s OpenAPI) -> Self {
+        let mut waivers = Waivers::default();
+
+        for (path, method, op) in spec.operations() {
+            let location = Location::operation(path, method, op);
+            let operation_id = op.operation_id.as_deref().unwrap_or("<unknown>");
+            waivers.declare(
+                &op.extensions,
+                format!("the operation {}", operation_id),
+                location.clone(),
+                location.pointer.clone(),
+            );
+            for walked in SchemaWalker::walk(op) {
+                waivers.declare(
+                    &walked.schema.schema_data.extensions,
+                    format!("a schema within the operation {}", operation_id),
+                    location.within(&walked.pointer),
+                    None,
+                );
+            }
+        }
+
+        // Named schemas come first so that each is declared with its scope
+        // before being seen again as a schema within itself.
+        let components = spec.components.iter();
+        for (name, schema) in components.clone().flat_map(|c| &c.schemas) {
+            if let Some(schema) = schema.as_item() {
+                let location = Location::named("schemas", name);
+                waivers.declare(
+                    &schema.schema_data.extensions,
+                    format!("the schema {}", name),
+                    location.clone(),
+                    location.pointer,
+                );
+            }
+        }
+        for walked in components.flat_map(|c| c.walk()) {
+            let Some(name) = &walked.component else {
+                continue;
+            };
+            waivers.declare(
+                &walked.schema.schema_data.extensions,
+                format!("a schema within {}", name),
+                Location::from_pointer(&format!("/components{}", walked.pointer)),
+                None,
+            );
+        }
+
+        waivers
+    }
+
+    fn declare(
+        &mut self,
+        extensions: &'s IndexMap<String, Value>,
+        element: String,
+        location: Location,
+        scope: Option<String>,
+    ) {
+        let Some(value) = extensions.get(EXTENSION) else {
+            return;
+        };
+        if self.find(value).is_some() {
+            return;
+        }
+        // Malformed values are reported by the check of extension values.
+        let codes = value
+            .get("ignore")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(String::from)
+            .collect();
+        self.declared.push(Waiver {
+            value,
+            element,
+            location,
+            scope,
+            codes,
+        });
+    }
+
+    fn find(&self, value: &Value) -> Option<usize> {
+        self.declared
+            .iter()
+            .position(|waiver| std::ptr::eq(waiver.value, value))
+    }
+
+    /// Whether the element with the given extensions waives the check that
+    /// found `diagnostic`, noting the use of the waiver if so.
+    pub(crate) fn waives(
+        &self,
+        extensions: &IndexMap<String, Value>,
+        diagnostic: &Diagnostic,
+    ) -> bool {
+        extensions
+            .get(EXTENSION)
+            .and_then(|value| self.find(value))
+            .is_some_and(|index| self.check(index, diagnostic))
+    }
+
+    fn check(&self, index: usize, diagnostic: &Diagnostic) -> bool {
+        let waived = self.declared[index]
+            .codes
+            .iter()
+            .any(|code| code == diagnostic.code());
+        if waived {
+            self.used.lock().unwrap().insert((index, diagnostic.code()));
+        }
+        waived
+    }
+
+    /// Whether an operation or named schema within which `finding` was
+    /// reported waives its check.
+    pub(crate) fn waived(&self, finding: &Diagnostic) -> bool {
+        let Some(pointer) = finding.location().pointer() else {
+            return false;
+        };
+        self.declared.iter().enumerate().any(|(index, waiver)| {
+            waiver.scope.as_deref().is_some_and(|scope| {
+                pointer
+                    .strip_prefix(scope)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+            }) && self.check(index, finding)
+        })
+    }
+
+    /// Each waived code that waived something, in the order declared.
+    pub(crate) fn applied(&self) -> Vec<Waived> {
+        let used = &*self.used.lock().unwrap();
+        self.declared
+            .iter()
+            .enumerate()
+            .flat_map(|(index, waiver)| {
+                waiver
+                    .codes
+                    .iter()
+                    .filter(move |code| used.iter().any(|&(i, used)| i == index && used == *code))
+                    .map(|code| Waived {
+                        code: code.clone(),
+                        element: waiver.element.clone(),
+                        location: waiver.location.clone(),
+                    })
+            })
+            .collect()
+    }
+
+    /// A problem for each waived code that waived nothing, other than those
+    /// of checks that are turned off.
+    pub(crate) fn unused(&self, config: &Config) -> Vec<Diagnostic> {
+        let used = &*self.used.lock().unwrap();
+        self.declared
+            .iter()
+            .enumerate()
+            .flat_map(|(index, waiver)| {
+                waiver.codes.iter().filter_map(move |code| {
+                    let message = match config.lint(code) {
+                        None => format!(
+                            "The waiver on {} names \"{}\", which is not the code of \
+                            any check.",
+                            waiver.element, code,
+                        ),
+                        Some(lint) if !config.is_enabled(lint.code()) => return None,
+                        Some(lint) if used.contains(&(index, lint.code())) => return None,
+                        Some(_) => format!(
+                            "The waiver of {} on {} waives nothing; remove it.",
+                            code, waiver.element,
+                        ),
+                    };
+                    Some(
+                        Diagnostic::new(&lints::UNUSED_WAIVER, message).at(waiver.location.clone()),
+                    )
+                })
+            })
+            .collect::<Vec<_>>()
+    }
+}
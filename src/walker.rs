@@ -1,261 +1,478 @@
 // Copyright 2022 Oxide Computer Company
 
+//! Walking every schema in a spec, with the same coverage as the rules.
+
+use std::fmt::{Display, Write};
+
 use indexmap::IndexMap;
 use openapiv3::{
-    AdditionalProperties, AnySchema, ArrayType, Components, MediaType, ObjectType, OpenAPI,
-    Operation, Parameter, ParameterSchemaOrContent, PathItem, ReferenceOr, RequestBody, Response,
-    Schema, Type,
+    AdditionalProperties, ArrayType, Components, Header, MediaType, ObjectType, OpenAPI, Operation,
+    Parameter, ParameterSchemaOrContent, PathItem, ReferenceOr, RequestBody, Response, Schema,
+    SchemaKind, Type,
 };
 
-pub(crate) trait SchemaWalker<'a> {
-    type SchemaIterator: Iterator<Item = (Option<String>, &'a Schema)>;
+/// A schema found by [`SchemaWalker::walk`].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct Walked<'a> {
+    pub(crate) component: Option<&'a str>,
+    pub(crate) pointer: String,
+    pub(crate) schema: &'a Schema,
+}
+
+impl<'a> Walked<'a> {
+    /// The name of the component schema within which the schema appears (or
+    /// which it is), if any.
+    pub fn component(&self) -> Option<&'a str> {
+        self.component
+    }
+
+    /// A JSON pointer to the schema, relative to the value walked; for a walk
+    /// of a whole spec, this is the pointer within the spec.
+    pub fn pointer(&self) -> &str {
+        &self.pointer
+    }
+
+    pub fn schema(&self) -> &'a Schema {
+        self.schema
+    }
+}
+
+/// Walking the schemas within a value from a spec: every inline schema, and
+/// every schema nested within one (properties, items, additional properties,
+/// and the subschemas of `allOf`, `oneOf`, `anyOf`, and `not`), with nested
+/// schemas before those that contain them. References aren't followed, so
+/// each schema is found once, where it is defined.
+///
+/// This is implemented for the types of the `openapiv3` crate that contain
+/// schemas, and can't be implemented outside this crate, so that methods can
+/// be added.
+///
+/// ```
+/// use openapi_lint::SchemaWalker;
+///
+/// # let spec: openapiv3::OpenAPI = serde_json::from_str(
+/// #     r#"{"openapi": "3.0.3", "info": {"title": "", "version": ""}, "paths": {},
+/// #         "components": {"schemas": {"Disk": {"type": "object", "properties":
+/// #         {"size": {"type": "integer"}}}}}}"#,
+/// # ).unwrap();
+/// let pointers = spec
+///     .walk()
+///     .map(|walked| walked.pointer().to_string())
+///     .collect::<Vec<_>>();
+/// assert_eq!(
+///     pointers,
+///     ["/components/schemas/Disk/properties/size", "/components/schemas/Disk"],
+/// );
+/// ```
+///
+/// Other types can't implement it:
+///
+/// ```compile_fail
+/// use openapi_lint::{SchemaWalker, Walked};
+///
+/// struct Nothing;
+///
+/// impl<'a> SchemaWalker<'a> for Nothing {
+///     type SchemaIterator = std::iter::Empty<Walked<'a>>;
+///
+///     fn walk(&'a self) -> Self::SchemaIterator {
+///         std::iter::empty()
+///     }
+/// }
+/// ```
+pub trait SchemaWalker<'a>: private::Sealed<'a> {
+    type SchemaIterator: Iterator<Item = Walked<'a>>;
+
+    /// The schemas within the value.
     fn walk(&'a self) -> Self::SchemaIterator;
 }
 
-impl<'a> SchemaWalker<'a> for OpenAPI {
-    type SchemaIterator = std::vec::IntoIter<(Option<String>, &'a Schema)>;
+impl<'a, T: private::Sealed<'a>> SchemaWalker<'a> for T {
+    type SchemaIterator = Walk<'a>;
+
+    fn walk(&'a self) -> Walk<'a> {
+        Walk {
+            steps: vec![Step::Enter {
+                depth: 0,
+                segment: Segment::Path(""),
+                node: self,
+                component: None,
+            }],
+            pointer: String::new(),
+            ends: vec![0],
+        }
+    }
+}
 
-    fn walk(&'a self) -> Self::SchemaIterator {
-        self.paths
-            .iter()
-            .flat_map(|(_, path)| path.walk())
-            .chain(self.components.walk())
-            .collect::<Vec<_>>()
-            .into_iter()
+mod private {
+    /// The types that can be walked, each of which adds what it contains to
+    /// the walk.
+    pub trait Sealed<'a> {
+        fn expand(&'a self, children: &mut super::Children<'a, '_>);
     }
 }
 
-impl<'a, T> SchemaWalker<'a> for ReferenceOr<T>
-where
-    T: SchemaWalker<'a>,
-{
-    type SchemaIterator = std::vec::IntoIter<(Option<String>, &'a Schema)>;
+/// The schemas within a value from a spec, found lazily: the walk goes
+/// depth-first with a stack of what is left to visit, and builds up JSON
+/// pointers in a single buffer, so that it allocates little beyond the
+/// pointer of each schema found, however large the spec.
+#[derive(Debug)]
+pub struct Walk<'a> {
+    steps: Vec<Step<'a>>,
+    /// The pointer to the value most recently entered, whose prefixes are the
+    /// pointers to the values that contain it.
+    pointer: String,
+    /// The length of the pointer to the value entered at each depth, for the
+    /// values now being walked; the first is that of the empty prefix.
+    ends: Vec<usize>,
+}
+
+enum Step<'a> {
+    /// Enter a value, adding what it contains to the walk.
+    Enter {
+        depth: usize,
+        segment: Segment<'a>,
+        node: &'a dyn private::Sealed<'a>,
+        component: Option<&'a str>,
+    },
+    /// Yield a schema, once everything nested within it has been.
+    Yield {
+        depth: usize,
+        schema: &'a Schema,
+        component: Option<&'a str>,
+    },
+}
 
-    fn walk(&'a self) -> Self::SchemaIterator {
+impl std::fmt::Debug for Step<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            ReferenceOr::Reference { .. } => vec![].into_iter(),
-            ReferenceOr::Item(walker) => walker.walk().collect::<Vec<_>>().into_iter(),
+            Step::Enter { depth, .. } => f.debug_struct("Enter").field("depth", depth).finish(),
+            Step::Yield { depth, .. } => f.debug_struct("Yield").field("depth", depth).finish(),
         }
     }
 }
 
-impl<'a, T> SchemaWalker<'a> for Box<T>
-where
-    T: SchemaWalker<'a>,
-{
-    type SchemaIterator = std::vec::IntoIter<(Option<String>, &'a Schema)>;
+/// How to get from a value to one within it, as a suffix of a JSON pointer.
+enum Segment<'a> {
+    /// Fixed keys, e.g. "/responses/default".
+    Path(&'static str),
+    /// Fixed keys followed by a key from the spec, which is escaped, e.g.
+    /// "/properties/{name}".
+    Member(&'static str, &'a dyn Display),
+    /// Fixed keys followed by an index, e.g. "/oneOf/{i}".
+    Index(&'static str, usize),
+    /// A key from the spec, which is escaped, e.g. "/get".
+    Key(&'a str),
+}
 
-    fn walk(&'a self) -> Self::SchemaIterator {
-        self.as_ref().walk().collect::<Vec<_>>().into_iter()
+impl Segment<'_> {
+    fn write(&self, pointer: &mut String) {
+        match self {
+            Segment::Path(path) => pointer.push_str(path),
+            Segment::Member(path, key) => {
+                pointer.push_str(path);
+                pointer.push('/');
+                let _ = write!(Escaped(pointer), "{}", key);
+            }
+            Segment::Index(path, index) => {
+                let _ = write!(pointer, "{}/{}", path, index);
+            }
+            Segment::Key(key) => {
+                pointer.push('/');
+                let _ = Escaped(pointer).write_str(key);
+            }
+        }
     }
 }
 
-impl<'a, T> SchemaWalker<'a> for Option<T>
-where
-    T: SchemaWalker<'a>,
-{
-    type SchemaIterator = std::vec::IntoIter<(Option<String>, &'a Schema)>;
+/// Escapes what is written to it as a JSON pointer token.
+struct Escaped<'s>(&'s mut String);
 
-    fn walk(&'a self) -> Self::SchemaIterator {
-        match self {
-            None => Vec::<(Option<String>, &Schema)>::new().into_iter(),
-            Some(walker) => walker.walk().collect::<Vec<_>>().into_iter(),
+impl Write for Escaped<'_> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        for c in s.chars() {
+            match c {
+                '~' => self.0.push_str("~0"),
+                '/' => self.0.push_str("~1"),
+                c => self.0.push(c),
+            }
         }
+        Ok(())
     }
 }
 
-impl<'a> SchemaWalker<'a> for IndexMap<String, ReferenceOr<Schema>> {
-    type SchemaIterator = std::vec::IntoIter<(Option<String>, &'a Schema)>;
-
-    fn walk(&'a self) -> Self::SchemaIterator {
-        self.iter()
-            .flat_map(|(key, value)| {
-                value
-                    .walk()
-                    .map(|(_, schema)| (Some(key.clone()), schema))
-                    .collect::<Vec<_>>()
-                    .into_iter()
-            })
-            .collect::<Vec<_>>()
-            .into_iter()
+impl<'a> Iterator for Walk<'a> {
+    type Item = Walked<'a>;
+
+    fn next(&mut self) -> Option<Walked<'a>> {
+        while let Some(step) = self.steps.pop() {
+            match step {
+                Step::Enter {
+                    depth,
+                    segment,
+                    node,
+                    component,
+                } => {
+                    self.pointer.truncate(self.ends[depth]);
+                    self.ends.truncate(depth + 1);
+                    segment.write(&mut self.pointer);
+                    self.ends.push(self.pointer.len());
+
+                    // Children are added in order, but visited from the top
+                    // of the stack.
+                    let start = self.steps.len();
+                    node.expand(&mut Children {
+                        steps: &mut self.steps,
+                        depth: depth + 1,
+                        component,
+                    });
+                    self.steps[start..].reverse();
+                }
+                Step::Yield {
+                    depth,
+                    schema,
+                    component,
+                } => {
+                    return Some(Walked {
+                        component,
+                        pointer: self.pointer[..self.ends[depth]].to_string(),
+                        schema,
+                    });
+                }
+            }
+        }
+        None
     }
 }
 
-impl<'a, K> SchemaWalker<'a> for IndexMap<K, MediaType> {
-    type SchemaIterator = std::vec::IntoIter<(Option<String>, &'a Schema)>;
+/// What a value contains, in the order in which it is to be walked.
+pub struct Children<'a, 's> {
+    steps: &'s mut Vec<Step<'a>>,
+    depth: usize,
+    component: Option<&'a str>,
+}
 
-    fn walk(&'a self) -> Self::SchemaIterator {
-        self.iter()
-            .flat_map(|(_key, value)| value.walk())
-            .collect::<Vec<_>>()
-            .into_iter()
+impl<'a> Children<'a, '_> {
+    fn add(&mut self, segment: Segment<'a>, node: &'a dyn private::Sealed<'a>) {
+        self.steps.push(Step::Enter {
+            depth: self.depth,
+            segment,
+            node,
+            component: self.component,
+        });
     }
-}
 
-impl<'a, K> SchemaWalker<'a> for IndexMap<K, ReferenceOr<Response>> {
-    type SchemaIterator = std::vec::IntoIter<(Option<String>, &'a Schema)>;
+    /// Add a component schema, to which the schemas within it belong.
+    fn add_component(&mut self, name: &'a str, node: &'a dyn private::Sealed<'a>) {
+        self.steps.push(Step::Enter {
+            depth: self.depth,
+            segment: Segment::Key(name),
+            node,
+            component: Some(name),
+        });
+    }
 
-    fn walk(&'a self) -> Self::SchemaIterator {
-        self.iter()
-            .flat_map(|(_key, value)| value.walk())
-            .collect::<Vec<_>>()
-            .into_iter()
+    fn add_schema(&mut self, schema: &'a Schema) {
+        self.steps.push(Step::Yield {
+            depth: self.depth,
+            schema,
+            component: self.component,
+        });
     }
 }
 
-impl<'a> SchemaWalker<'a> for PathItem {
-    type SchemaIterator = std::vec::IntoIter<(Option<String>, &'a Schema)>;
+impl<'a> private::Sealed<'a> for OpenAPI {
+    fn expand(&'a self, children: &mut Children<'a, '_>) {
+        for (path, item) in &self.paths.paths {
+            children.add(Segment::Member("/paths", path), item);
+        }
+        children.add(Segment::Path("/components"), &self.components);
+    }
+}
 
-    fn walk(&'a self) -> Self::SchemaIterator {
-        self.iter()
-            .flat_map(|(_, op)| SchemaWalker::walk(op))
-            .chain(self.parameters.iter().flat_map(SchemaWalker::walk))
-            .collect::<Vec<_>>()
-            .into_iter()
+impl<'a, T: private::Sealed<'a>> private::Sealed<'a> for ReferenceOr<T> {
+    fn expand(&'a self, children: &mut Children<'a, '_>) {
+        if let ReferenceOr::Item(item) = self {
+            item.expand(children);
+        }
     }
 }
 
-impl<'a> SchemaWalker<'a> for Operation {
-    type SchemaIterator = std::vec::IntoIter<(Option<String>, &'a Schema)>;
+impl<'a, T: private::Sealed<'a>> private::Sealed<'a> for Box<T> {
+    fn expand(&'a self, children: &mut Children<'a, '_>) {
+        self.as_ref().expand(children);
+    }
+}
 
-    fn walk(&'a self) -> Self::SchemaIterator {
-        self.parameters
-            .iter()
-            .flat_map(SchemaWalker::walk)
-            .chain(self.request_body.walk())
-            .chain(self.responses.default.walk())
-            .chain(self.responses.responses.walk())
-            .collect::<Vec<_>>()
-            .into_iter()
+impl<'a, T: private::Sealed<'a>> private::Sealed<'a> for Option<T> {
+    fn expand(&'a self, children: &mut Children<'a, '_>) {
+        if let Some(item) = self {
+            item.expand(children);
+        }
     }
 }
 
-impl<'a> SchemaWalker<'a> for Components {
-    type SchemaIterator = std::vec::IntoIter<(Option<String>, &'a Schema)>;
-
-    fn walk(&'a self) -> Self::SchemaIterator {
-        self.responses
-            .walk()
-            .chain(
-                self.parameters
-                    .iter()
-                    .flat_map(|(_, parameter)| parameter.walk()),
-            )
-            .chain(
-                self.request_bodies
-                    .iter()
-                    .flat_map(|(_, request_body)| request_body.walk()),
-            )
-            .chain(self.schemas.walk())
-            .collect::<Vec<_>>()
-            .into_iter()
+impl<'a> private::Sealed<'a> for IndexMap<String, ReferenceOr<Schema>> {
+    fn expand(&'a self, children: &mut Children<'a, '_>) {
+        for (name, schema) in self {
+            children.add_component(name, schema);
+        }
     }
 }
 
-impl<'a> SchemaWalker<'a> for Response {
-    type SchemaIterator = <IndexMap<String, MediaType> as SchemaWalker<'a>>::SchemaIterator;
+impl<'a, K: Display + 'a> private::Sealed<'a> for IndexMap<K, MediaType> {
+    fn expand(&'a self, children: &mut Children<'a, '_>) {
+        for (key, value) in self {
+            children.add(Segment::Member("", key), value);
+        }
+    }
+}
 
-    fn walk(&'a self) -> Self::SchemaIterator {
-        self.content.walk()
+impl<'a, K: Display + 'a> private::Sealed<'a> for IndexMap<K, ReferenceOr<Response>> {
+    fn expand(&'a self, children: &mut Children<'a, '_>) {
+        for (key, value) in self {
+            children.add(Segment::Member("", key), value);
+        }
     }
 }
 
-impl<'a> SchemaWalker<'a> for MediaType {
-    type SchemaIterator = <Option<Schema> as SchemaWalker<'a>>::SchemaIterator;
+impl<'a, K: Display + 'a> private::Sealed<'a> for IndexMap<K, ReferenceOr<Header>> {
+    fn expand(&'a self, children: &mut Children<'a, '_>) {
+        for (key, value) in self {
+            children.add(Segment::Member("", key), value);
+        }
+    }
+}
 
-    fn walk(&'a self) -> Self::SchemaIterator {
-        self.schema.walk()
+impl<'a> private::Sealed<'a> for PathItem {
+    fn expand(&'a self, children: &mut Children<'a, '_>) {
+        for (method, op) in self.iter() {
+            children.add(Segment::Key(method), op);
+        }
+        for (i, param) in self.parameters.iter().enumerate() {
+            children.add(Segment::Index("/parameters", i), param);
+        }
     }
 }
 
-impl<'a> SchemaWalker<'a> for Parameter {
-    type SchemaIterator = std::vec::IntoIter<(Option<String>, &'a Schema)>;
+impl<'a> private::Sealed<'a> for Operation {
+    fn expand(&'a self, children: &mut Children<'a, '_>) {
+        for (i, param) in self.parameters.iter().enumerate() {
+            children.add(Segment::Index("/parameters", i), param);
+        }
+        children.add(Segment::Path("/requestBody"), &self.request_body);
+        children.add(Segment::Path("/responses/default"), &self.responses.default);
+        children.add(Segment::Path("/responses"), &self.responses.responses);
+    }
+}
 
-    fn walk(&'a self) -> Self::SchemaIterator {
-        match self {
-            Parameter::Query { parameter_data, .. } => parameter_data,
-            Parameter::Header { parameter_data, .. } => parameter_data,
-            Parameter::Path { parameter_data, .. } => parameter_data,
-            Parameter::Cookie { parameter_data, .. } => parameter_data,
+impl<'a> private::Sealed<'a> for Components {
+    fn expand(&'a self, children: &mut Children<'a, '_>) {
+        children.add(Segment::Path("/responses"), &self.responses);
+        for (name, parameter) in &self.parameters {
+            children.add(Segment::Member("/parameters", name), parameter);
+        }
+        for (name, request_body) in &self.request_bodies {
+            children.add(Segment::Member("/requestBodies", name), request_body);
         }
-        .format
-        .walk()
+        children.add(Segment::Path("/headers"), &self.headers);
+        children.add(Segment::Path("/schemas"), &self.schemas);
     }
 }
 
-impl<'a> SchemaWalker<'a> for ParameterSchemaOrContent {
-    type SchemaIterator = std::vec::IntoIter<(Option<String>, &'a Schema)>;
+impl<'a> private::Sealed<'a> for Response {
+    fn expand(&'a self, children: &mut Children<'a, '_>) {
+        children.add(Segment::Path("/headers"), &self.headers);
+        children.add(Segment::Path("/content"), &self.content);
+    }
+}
+
+impl<'a> private::Sealed<'a> for MediaType {
+    fn expand(&'a self, children: &mut Children<'a, '_>) {
+        children.add(Segment::Path("/schema"), &self.schema);
+    }
+}
 
-    fn walk(&'a self) -> Self::SchemaIterator {
+impl<'a> private::Sealed<'a> for Parameter {
+    fn expand(&'a self, children: &mut Children<'a, '_>) {
+        self.parameter_data_ref().format.expand(children);
+    }
+}
+
+impl<'a> private::Sealed<'a> for Header {
+    fn expand(&'a self, children: &mut Children<'a, '_>) {
+        self.format.expand(children);
+    }
+}
+
+impl<'a> private::Sealed<'a> for ParameterSchemaOrContent {
+    fn expand(&'a self, children: &mut Children<'a, '_>) {
         match self {
             ParameterSchemaOrContent::Schema(schema) => {
-                schema.walk().collect::<Vec<_>>().into_iter()
+                children.add(Segment::Path("/schema"), schema)
             }
             ParameterSchemaOrContent::Content(content) => {
-                content.walk().collect::<Vec<_>>().into_iter()
+                children.add(Segment::Path("/content"), content)
             }
         }
     }
 }
 
-impl<'a> SchemaWalker<'a> for RequestBody {
-    type SchemaIterator = std::vec::IntoIter<(Option<String>, &'a Schema)>;
-
-    fn walk(&'a self) -> Self::SchemaIterator {
-        self.content.walk()
+impl<'a> private::Sealed<'a> for RequestBody {
+    fn expand(&'a self, children: &mut Children<'a, '_>) {
+        children.add(Segment::Path("/content"), &self.content);
     }
 }
 
-impl<'a> SchemaWalker<'a> for Schema {
-    type SchemaIterator = std::vec::IntoIter<(Option<String>, &'a Schema)>;
-
-    fn walk(&'a self) -> Self::SchemaIterator {
-        let children: Vec<_> = match &self.schema_kind {
+impl<'a> private::Sealed<'a> for Schema {
+    fn expand(&'a self, children: &mut Children<'a, '_>) {
+        match &self.schema_kind {
             // Objects have properties and additional (i.e. arbitrarily-
             // named) properties that have schemas.
-            openapiv3::SchemaKind::Type(Type::Object(ObjectType {
+            SchemaKind::Type(Type::Object(ObjectType {
                 properties,
                 additional_properties,
                 ..
             })) => {
-                let additional = match additional_properties {
-                    Some(AdditionalProperties::Schema(schema)) => schema.walk().collect(),
-                    _ => vec![],
-                };
-                properties
-                    .iter()
-                    .flat_map(|(_, prop)| match prop {
-                        ReferenceOr::Reference { .. } => vec![],
-                        ReferenceOr::Item(schema) => schema.walk().collect(),
-                    })
-                    .chain(additional)
-                    .collect()
+                for (name, prop) in properties {
+                    children.add(Segment::Member("/properties", name), prop);
+                }
+                if let Some(AdditionalProperties::Schema(schema)) = additional_properties {
+                    children.add(Segment::Path("/additionalProperties"), schema);
+                }
             }
             // Arrays have items with schemas.
-            openapiv3::SchemaKind::Type(Type::Array(ArrayType {
+            SchemaKind::Type(Type::Array(ArrayType {
                 items: Some(schema),
                 ..
-            })) => schema.walk().collect(),
+            })) => children.add(Segment::Path("/items"), schema),
             // Other types don't have subordinate schemas.
-            openapiv3::SchemaKind::Type(_) => vec![],
+            SchemaKind::Type(_) => {}
 
             // Lists of subschemas...
-            openapiv3::SchemaKind::OneOf { one_of: subschemas }
-            | openapiv3::SchemaKind::AllOf { all_of: subschemas }
-            | openapiv3::SchemaKind::AnyOf { any_of: subschemas } => {
-                subschemas.iter().flat_map(SchemaWalker::walk).collect()
+            SchemaKind::OneOf { one_of: subschemas } => {
+                for (i, schema) in subschemas.iter().enumerate() {
+                    children.add(Segment::Index("/oneOf", i), schema);
+                }
+            }
+            SchemaKind::AllOf { all_of: subschemas } => {
+                for (i, schema) in subschemas.iter().enumerate() {
+                    children.add(Segment::Index("/allOf", i), schema);
+                }
+            }
+            SchemaKind::AnyOf { any_of: subschemas } => {
+                for (i, schema) in subschemas.iter().enumerate() {
+                    children.add(Segment::Index("/anyOf", i), schema);
+                }
             }
             // Not is an odd case, but it should still be formatted properly...
-            openapiv3::SchemaKind::Not { not } => not.walk().collect(),
+            SchemaKind::Not { not } => children.add(Segment::Path("/not"), not),
 
             // TODO we may need to look in here...
-            openapiv3::SchemaKind::Any(AnySchema { .. }) => vec![],
-        };
-
-        children
-            .into_iter()
-            .chain(std::iter::once((None, self)))
-            .collect::<Vec<_>>()
-            .into_iter()
+            SchemaKind::Any(_) => {}
+        }
+
+        children.add_schema(self);
     }
 }
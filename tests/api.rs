@@ -0,0 +1,10 @@
+// Copyright 2026 Oxide Computer Company
+
+//! Checks that the public API leaves room to grow: adding a severity or a
+//! naming target must not break code that uses this crate.
+
+#[test]
+fn non_exhaustive() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,12 @@
+use openapi_lint::Severity;
+
+fn is_fatal(severity: Severity) -> bool {
+    match severity {
+        Severity::Error => true,
+        Severity::Warning | Severity::Advice => false,
+    }
+}
+
+fn main() {
+    println!("{}", is_fatal(Severity::Error));
+}
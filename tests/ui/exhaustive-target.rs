@@ -0,0 +1,16 @@
+use openapi_lint::naming::Target;
+
+fn convention(target: Target) -> &'static str {
+    match target {
+        Target::Type => "PascalCase",
+        Target::Property
+        | Target::Parameter
+        | Target::OperationId
+        | Target::EnumValue => "snake_case",
+        Target::PathSegment => "kebab-case",
+    }
+}
+
+fn main() {
+    println!("{}", convention(Target::Type));
+}